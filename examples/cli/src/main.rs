@@ -1,7 +1,10 @@
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use flate2::read::GzDecoder;
+use timeline_core::export::trends_to_csv;
 use timeline_core::TimelineConfig;
 use timeline_fhir::summarize_bundle_str;
 
@@ -11,25 +14,58 @@ use timeline_fhir::summarize_bundle_str;
     about = "Tạo tóm tắt timeline từ bundle FHIR JSON."
 )]
 struct Args {
-    /// Đường dẫn tới file JSON bundle.
+    /// Đường dẫn tới file JSON bundle; bỏ trống hoặc `-` để đọc từ stdin.
     #[arg(short, long)]
-    input: PathBuf,
+    input: Option<PathBuf>,
+
+    /// Định dạng đầu ra.
+    #[arg(short, long, value_enum, default_value_t = Format::Summary)]
+    format: Format,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    /// Báo cáo tóm tắt dạng văn bản.
+    Summary,
+    /// Xu hướng chỉ số sống dạng CSV (metric, timestamp, value, unit).
+    TrendsCsv,
 }
 
+/// Hai byte đầu của dữ liệu gzip.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let data = std::fs::read_to_string(&args.input)
-        .with_context(|| format!("Không đọc được file {:?}", args.input))?;
+    let data = match args.input.as_deref() {
+        Some(path) if path != Path::new("-") => std::fs::read_to_string(path)
+            .with_context(|| format!("Không đọc được file {:?}", path))?,
+        _ => read_stdin()?,
+    };
 
     let config = TimelineConfig::default();
     let snapshot = summarize_bundle_str(&data, &config)?;
 
-    println!(
-        "Generated at: {}\nCritical alerts: {}\nTimeline events: {}",
-        snapshot.generated_at,
-        snapshot.critical.alerts.len(),
-        snapshot.events.len()
-    );
+    match args.format {
+        Format::Summary => println!("{}", snapshot.summary_report()),
+        Format::TrendsCsv => print!("{}", trends_to_csv(&snapshot.critical)),
+    }
 
     Ok(())
 }
+
+/// Đọc bundle từ stdin, tự giải nén khi dữ liệu bắt đầu bằng magic bytes gzip.
+fn read_stdin() -> anyhow::Result<String> {
+    let mut bytes = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut bytes)
+        .context("Không đọc được stdin")?;
+
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut data = String::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut data)
+            .context("Không giải nén được dữ liệu gzip từ stdin")?;
+        return Ok(data);
+    }
+    String::from_utf8(bytes).context("Dữ liệu stdin không phải UTF-8")
+}
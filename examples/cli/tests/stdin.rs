@@ -0,0 +1,66 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+fn fixture_path() -> String {
+    format!(
+        "{}/../../timeline-fhir/tests/data/emergency_observation_bundle.json",
+        env!("CARGO_MANIFEST_DIR")
+    )
+}
+
+/// CLI report without the `Generated at` line, which differs per run.
+fn run(args: &[&str], stdin: Option<&[u8]>) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_timeline-cli"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start timeline-cli");
+    {
+        let mut pipe = child.stdin.take().expect("stdin is piped");
+        if let Some(bytes) = stdin {
+            pipe.write_all(bytes).expect("Failed to write stdin");
+        }
+    }
+    let output = child.wait_with_output().expect("timeline-cli did not exit");
+    assert!(
+        output.status.success(),
+        "timeline-cli failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .expect("Report is not UTF-8")
+        .lines()
+        .filter(|line| !line.starts_with("Generated at"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn stdin_matches_input_path() {
+    let path = fixture_path();
+    let bundle = std::fs::read(&path).expect("Failed to read fixture");
+
+    let from_file = run(&["--input", &path], None);
+    assert!(!from_file.is_empty());
+
+    assert_eq!(run(&[], Some(&bundle)), from_file);
+    assert_eq!(run(&["--input", "-"], Some(&bundle)), from_file);
+}
+
+#[test]
+fn gzipped_stdin_is_detected() {
+    let path = fixture_path();
+    let bundle = std::fs::read(&path).expect("Failed to read fixture");
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&bundle)
+        .expect("Failed to compress fixture");
+    let gzipped = encoder.finish().expect("Failed to compress fixture");
+
+    assert_eq!(run(&[], Some(&gzipped)), run(&["--input", &path], None));
+}
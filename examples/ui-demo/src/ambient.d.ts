@@ -5,5 +5,50 @@ declare module "../../../pkg/timeline-wasm/timeline_wasm.js" {
 
 declare module "../../../pkg/timeline-ui/timeline_ui.js" {
   export default function init(module?: RequestInfo): Promise<unknown>;
-  export function mount_timeline_view(selector: string, snapshot: any): void;
+  export interface TimelineViewOptions {
+    style_scope?: string;
+    relative_time_granularity?: "minute" | "hour" | "day" | "week" | "month" | "year";
+    density?: "comfortable" | "compact";
+    duration_bars?: boolean;
+    locale?: "en-US" | "de-DE" | "fr-FR" | "vi-VN";
+    severity_shortcuts?: { all?: string; critical?: string; high?: string; moderate?: string };
+    markdown_details?: boolean;
+    vital_order?: string[];
+    trend_window_hours?: number;
+    critical_sections?: Array<
+      | "code_status"
+      | "trend_insights"
+      | "vitals"
+      | "diagnostics"
+      | "vital_trends"
+      | "alerts"
+      | "allergies"
+      | "medications"
+      | "discontinued_medications"
+      | "conditions"
+    >;
+    minimap?: boolean;
+    trend_delta_thresholds?: {
+      default?: number;
+      by_metric?: Record<string, number>;
+      by_unit?: Record<string, number>;
+    };
+    group_by_class?: boolean;
+    class_map?: Record<string, string[]>;
+    hot_strip_pinned?: string[];
+    weighted_trend_insights?: boolean;
+    layout?: "grid" | "stream";
+  }
+
+  export function mount_timeline_view(
+    selector: string,
+    snapshot: any,
+    options?: TimelineViewOptions
+  ): void;
+  export function mount_timeline_view_json(
+    selector: string,
+    snapshot_json: string,
+    options?: TimelineViewOptions
+  ): void;
+  export function render_trend_png(trend_json: string, width: number, height: number): Uint8Array;
 }
@@ -0,0 +1,441 @@
+//! Phân tích bổ sung trên snapshot (chất lượng dữ liệu, thống kê).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::vitals::classify_vital;
+use crate::{
+    CriticalSummary, Severity, SeverityCounts, TimelineEvent, TimelineSnapshot, VitalSnapshot,
+    VitalTrend,
+};
+
+/// Bộ chỉ số sống mặc định cần theo dõi, trùng với nhãn do bộ chuyển đổi FHIR sinh ra,
+/// xếp theo thứ tự khám tại giường (giao diện dùng làm thứ tự hiển thị mặc định).
+pub const EXPECTED_VITALS: [&str; 5] = [
+    "Heart rate",
+    "Blood pressure",
+    "Respiratory rate",
+    "SpO2",
+    "Temperature",
+];
+
+/// Mức độ đầy đủ của chỉ số sống gần đây so với bộ kỳ vọng.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VitalsCompleteness {
+    /// Chỉ số có ít nhất một giá trị gần đây.
+    pub present: Vec<String>,
+    /// Chỉ số kỳ vọng nhưng không có giá trị gần đây.
+    pub missing: Vec<String>,
+    /// Tỷ lệ phần trăm (0-100) chỉ số kỳ vọng đã có giá trị.
+    pub percentage: f64,
+}
+
+/// Đối chiếu `recent_vitals` với danh sách `expected` (so khớp không phân biệt hoa thường).
+///
+/// Danh sách rỗng được coi là đầy đủ 100%.
+pub fn vitals_completeness(summary: &CriticalSummary, expected: &[&str]) -> VitalsCompleteness {
+    let (present, missing): (Vec<&str>, Vec<&str>) = expected.iter().partition(|name| {
+        summary
+            .recent_vitals
+            .iter()
+            .any(|vital| vital.name.eq_ignore_ascii_case(name))
+    });
+
+    let percentage = if expected.is_empty() {
+        100.0
+    } else {
+        present.len() as f64 * 100.0 / expected.len() as f64
+    };
+
+    VitalsCompleteness {
+        present: present.into_iter().map(str::to_string).collect(),
+        missing: missing.into_iter().map(str::to_string).collect(),
+        percentage,
+    }
+}
+
+/// Tên chỉ số BMI suy ra từ chiều cao và cân nặng.
+pub const BMI_VITAL_NAME: &str = "BMI";
+
+/// Tính BMI từ chiều cao và cân nặng mới nhất trong `recent_vitals`.
+///
+/// Nhận chiều cao theo cm, m hoặc inch và cân nặng theo kg, g hoặc pound; trả về
+/// `None` khi thiếu một trong hai, đơn vị không nhận diện được hoặc chiều cao bằng 0.
+/// Thời điểm của kết quả là thời điểm muộn hơn trong hai số đo.
+pub fn derived_bmi(summary: &CriticalSummary) -> Option<VitalSnapshot> {
+    let height = latest_measurement(summary, "height")?;
+    let weight = latest_measurement(summary, "weight")?;
+
+    let height_m = height_in_meters(height.value, height.unit)?;
+    let weight_kg = weight_in_kilograms(weight.value, weight.unit)?;
+    if height_m <= 0.0 || weight_kg <= 0.0 {
+        return None;
+    }
+
+    let bmi = (weight_kg / (height_m * height_m) * 10.0).round() / 10.0;
+    Some(VitalSnapshot {
+        name: BMI_VITAL_NAME.to_string(),
+        value: format!("{bmi:.1} kg/m2"),
+        recorded_at: height.recorded_at.max(weight.recorded_at),
+        numeric_value: Some(bmi),
+        unit: Some("kg/m2".to_string()),
+        severity: classify_bmi(bmi),
+        watched: false,
+    })
+}
+
+/// Mức độ theo phân loại BMI của WHO: béo phì độ III hoặc gầy nặng là `High`,
+/// ngoài khoảng bình thường là `Moderate`.
+pub fn classify_bmi(bmi: f64) -> Severity {
+    match bmi {
+        v if !(16.0..40.0).contains(&v) => Severity::High,
+        v if !(18.5..30.0).contains(&v) => Severity::Moderate,
+        _ => Severity::Low,
+    }
+}
+
+struct Measurement<'a> {
+    value: f64,
+    unit: &'a str,
+    recorded_at: Option<DateTime<Utc>>,
+}
+
+fn latest_measurement<'a>(summary: &'a CriticalSummary, keyword: &str) -> Option<Measurement<'a>> {
+    summary
+        .recent_vitals
+        .iter()
+        .filter(|vital| vital.name.to_lowercase().contains(keyword))
+        .filter_map(|vital| {
+            let value = vital.numeric_value.or_else(|| {
+                vital
+                    .value
+                    .split_whitespace()
+                    .next()
+                    .and_then(|token| token.parse().ok())
+            })?;
+            let unit = vital
+                .unit
+                .as_deref()
+                .or_else(|| vital.value.split_whitespace().nth(1))
+                .unwrap_or_default();
+            Some(Measurement {
+                value,
+                unit,
+                recorded_at: vital.recorded_at,
+            })
+        })
+        .max_by_key(|measurement| measurement.recorded_at)
+}
+
+fn height_in_meters(value: f64, unit: &str) -> Option<f64> {
+    match unit.trim().to_lowercase().as_str() {
+        "cm" => Some(value / 100.0),
+        "m" => Some(value),
+        "in" | "[in_i]" | "inch" | "inches" => Some(value * 0.0254),
+        _ => None,
+    }
+}
+
+fn weight_in_kilograms(value: f64, unit: &str) -> Option<f64> {
+    match unit.trim().to_lowercase().as_str() {
+        "kg" => Some(value),
+        "g" => Some(value / 1000.0),
+        "lb" | "lbs" | "[lb_av]" => Some(value * 0.453_592_37),
+        _ => None,
+    }
+}
+
+/// Cấu hình phát hiện giai đoạn xấu đi đồng thời của nhiều chỉ số sống.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DeteriorationConfig {
+    /// Tên chỉ số sống được xét (so khớp không phân biệt hoa thường với `VitalTrend::name`).
+    pub vitals: Vec<String>,
+    /// Mức độ từ đây trở lên (nặng hơn) được coi là bất thường.
+    pub abnormal_at: Severity,
+    /// Độ dài tối đa (phút) của một giai đoạn.
+    pub window_minutes: u32,
+    /// Số chỉ số tối thiểu cùng chuyển sang bất thường trong một giai đoạn.
+    pub min_vitals: usize,
+}
+
+impl Default for DeteriorationConfig {
+    fn default() -> Self {
+        Self {
+            vitals: EXPECTED_VITALS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            abnormal_at: Severity::High,
+            window_minutes: 60,
+            min_vitals: 2,
+        }
+    }
+}
+
+/// Giai đoạn có từ `min_vitals` chỉ số trở lên chuyển từ bình thường sang bất thường.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeteriorationWindow {
+    /// Thời điểm chuyển sang bất thường sớm nhất trong giai đoạn.
+    pub start: DateTime<Utc>,
+    /// Thời điểm chuyển sang bất thường muộn nhất trong giai đoạn.
+    pub end: DateTime<Utc>,
+    /// Các chỉ số xấu đi, theo thứ tự thời gian.
+    pub vitals: Vec<String>,
+}
+
+/// Tìm các giai đoạn nhiều chỉ số sống cùng xấu đi trong `vital_trends`.
+///
+/// Một chỉ số "xấu đi" khi điểm trước được phân loại nhẹ hơn `abnormal_at` và
+/// điểm kế tiếp đạt `abnormal_at` (huyết áp xét theo tâm thu). Các lần chuyển
+/// được gom tham lam từ lần sớm nhất: mọi lần chuyển trong `window_minutes` tính
+/// từ đó thuộc cùng giai đoạn. Điểm thiếu thời gian hoặc giá trị bị bỏ qua.
+pub fn deterioration_windows(
+    snapshot: &TimelineSnapshot,
+    config: &DeteriorationConfig,
+) -> Vec<DeteriorationWindow> {
+    let mut crossings: Vec<(DateTime<Utc>, &str)> = snapshot
+        .critical
+        .vital_trends
+        .iter()
+        .filter(|trend| {
+            config
+                .vitals
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(&trend.name))
+        })
+        .flat_map(|trend| worsening_times(trend, config.abnormal_at))
+        .collect();
+    crossings.sort();
+
+    let span = Duration::minutes(i64::from(config.window_minutes));
+    let mut windows = Vec::new();
+    let mut index = 0;
+    while index < crossings.len() {
+        let start = crossings[index].0;
+        let group: Vec<&(DateTime<Utc>, &str)> = crossings[index..]
+            .iter()
+            .take_while(|(at, _)| *at - start <= span)
+            .collect();
+
+        let mut vitals: Vec<String> = Vec::new();
+        for (_, name) in &group {
+            if !vitals.iter().any(|seen| seen == name) {
+                vitals.push(name.to_string());
+            }
+        }
+
+        if vitals.len() >= config.min_vitals.max(1) {
+            let end = group.last().map_or(start, |(at, _)| *at);
+            windows.push(DeteriorationWindow { start, end, vitals });
+            index += group.len();
+        } else {
+            index += 1;
+        }
+    }
+    windows
+}
+
+/// Thời điểm `trend` chuyển từ dưới ngưỡng sang đạt ngưỡng `abnormal_at`.
+fn worsening_times(trend: &VitalTrend, abnormal_at: Severity) -> Vec<(DateTime<Utc>, &str)> {
+    let metric = if trend.name.eq_ignore_ascii_case("blood pressure") {
+        "systolic"
+    } else {
+        trend.name.as_str()
+    };
+    let mut points: Vec<(DateTime<Utc>, bool)> = trend
+        .points
+        .iter()
+        .filter_map(|point| {
+            let severity = classify_vital(metric, point.value?)?;
+            Some((point.recorded_at?, severity <= abnormal_at))
+        })
+        .collect();
+    points.sort_by_key(|(at, _)| *at);
+
+    points
+        .windows(2)
+        .filter(|pair| !pair[0].1 && pair[1].1)
+        .map(|pair| (pair[1].0, trend.name.as_str()))
+        .collect()
+}
+
+/// Mức thay đổi tối thiểu để một xu hướng được báo cáo trong "Trend insights".
+///
+/// Ngưỡng theo tên chỉ số được ưu tiên, sau đó theo đơn vị, cuối cùng là `default`;
+/// tên và đơn vị so khớp nguyên chuỗi, không phân biệt hoa thường.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TrendDeltaThresholds {
+    pub default: f64,
+    pub by_metric: BTreeMap<String, f64>,
+    pub by_unit: BTreeMap<String, f64>,
+}
+
+impl Default for TrendDeltaThresholds {
+    fn default() -> Self {
+        Self {
+            default: 0.5,
+            by_metric: BTreeMap::from([("pH".to_string(), 0.05)]),
+            by_unit: BTreeMap::from([
+                ("mg/dL".to_string(), 5.0),
+                ("mmol/L".to_string(), 0.5),
+                ("Cel".to_string(), 0.3),
+            ]),
+        }
+    }
+}
+
+impl TrendDeltaThresholds {
+    /// Ngưỡng áp dụng cho chỉ số `name` đo bằng `unit`.
+    pub fn threshold_for(&self, name: &str, unit: Option<&str>) -> f64 {
+        let lookup = |table: &BTreeMap<String, f64>, key: &str| {
+            table
+                .iter()
+                .find(|(candidate, _)| candidate.trim().eq_ignore_ascii_case(key.trim()))
+                .map(|(_, threshold)| *threshold)
+        };
+        lookup(&self.by_metric, name)
+            .or_else(|| unit.and_then(|unit| lookup(&self.by_unit, unit)))
+            .unwrap_or(self.default)
+    }
+}
+
+/// Chênh lệch giữa điểm có giá trị đầu và cuối của `trend` khi đủ lớn để báo cáo
+/// (trị tuyệt đối không nhỏ hơn ngưỡng); `None` khi thay đổi nhỏ hơn hoặc có dưới hai điểm.
+pub fn reportable_delta(trend: &VitalTrend, thresholds: &TrendDeltaThresholds) -> Option<f64> {
+    let mut values = trend.points.iter().filter_map(|point| point.value);
+    let first = values.next()?;
+    let last = values.next_back()?;
+    gate_delta(trend, last - first, thresholds)
+}
+
+/// Trọng số của lần đo mới hơn khi làm mượt theo hàm mũ trong [`recent_weighted_delta`].
+pub const RECENT_TREND_WEIGHT: f64 = 0.5;
+
+/// Chênh lệch giữa giá trị cuối và trung bình trượt có trọng số mũ (`weight` cho lần đo
+/// mới hơn, 0–1) của các giá trị trước đó; `None` khi có dưới hai điểm có giá trị.
+///
+/// Khác với so sánh đầu–cuối, chuỗi tăng vọt rồi trở về (80 → 140 → 80) cho kết quả
+/// giảm vì các lần đo gần nhất được tính nặng hơn.
+pub fn recent_weighted_delta(trend: &VitalTrend, weight: f64) -> Option<f64> {
+    let values: Vec<f64> = trend
+        .points
+        .iter()
+        .filter_map(|point| point.value)
+        .collect();
+    let (last, earlier) = values.split_last()?;
+    let (first, rest) = earlier.split_first()?;
+    let weight = weight.clamp(0.0, 1.0);
+    let smoothed = rest.iter().fold(*first, |average, value| {
+        weight * value + (1.0 - weight) * average
+    });
+    Some(last - smoothed)
+}
+
+/// Như [`reportable_delta`] nhưng dùng [`recent_weighted_delta`] với [`RECENT_TREND_WEIGHT`].
+pub fn reportable_weighted_delta(
+    trend: &VitalTrend,
+    thresholds: &TrendDeltaThresholds,
+) -> Option<f64> {
+    let delta = recent_weighted_delta(trend, RECENT_TREND_WEIGHT)?;
+    gate_delta(trend, delta, thresholds)
+}
+
+fn gate_delta(trend: &VitalTrend, delta: f64, thresholds: &TrendDeltaThresholds) -> Option<f64> {
+    let threshold = thresholds.threshold_for(&trend.name, trend.unit.as_deref());
+    (delta.abs() >= threshold).then_some(delta)
+}
+
+/// Các đơn vị khác nhau đã gặp cho một chỉ số.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnitReport {
+    pub metric: String,
+    /// Đơn vị phân biệt, theo thứ tự chữ cái.
+    pub units: Vec<String>,
+    /// `true` khi chỉ số được ghi bằng nhiều hơn một đơn vị (ví dụ mmHg và kPa).
+    pub inconsistent: bool,
+}
+
+/// Đơn vị theo từng chỉ số trong chỉ số sống, xu hướng, huyết áp và kết quả chẩn đoán.
+///
+/// Tên chỉ số so khớp không phân biệt hoa thường (giữ cách viết gặp đầu tiên), đơn vị
+/// so khớp nguyên chuỗi sau khi bỏ khoảng trắng; chỉ số không có đơn vị nào bị bỏ qua.
+/// Kết quả xếp theo tên chỉ số.
+pub fn unit_consistency(summary: &CriticalSummary) -> Vec<UnitReport> {
+    let observed = summary
+        .recent_vitals
+        .iter()
+        .map(|vital| (vital.name.as_str(), vital.unit.as_deref()))
+        .chain(
+            summary
+                .vital_trends
+                .iter()
+                .map(|trend| (trend.name.as_str(), trend.unit.as_deref())),
+        )
+        .chain(
+            summary
+                .blood_pressure_trend
+                .iter()
+                .map(|trend| ("Blood pressure", trend.unit.as_deref())),
+        )
+        .chain(
+            summary
+                .recent_diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.name.as_str(), diagnostic.unit.as_deref())),
+        );
+
+    let mut by_metric: BTreeMap<String, (&str, BTreeSet<&str>)> = BTreeMap::new();
+    for (metric, unit) in observed {
+        let Some(unit) = unit.map(str::trim).filter(|unit| !unit.is_empty()) else {
+            continue;
+        };
+        by_metric
+            .entry(metric.to_lowercase())
+            .or_insert_with(|| (metric, BTreeSet::new()))
+            .1
+            .insert(unit);
+    }
+
+    by_metric
+        .into_values()
+        .map(|(metric, units)| UnitReport {
+            metric: metric.to_string(),
+            inconsistent: units.len() > 1,
+            units: units.into_iter().map(str::to_string).collect(),
+        })
+        .collect()
+}
+
+/// Số sự kiện theo ngày (UTC) của `occurred_at`; sự kiện không có thời điểm bị bỏ qua.
+pub fn events_per_day(snapshot: &TimelineSnapshot) -> BTreeMap<NaiveDate, usize> {
+    let mut histogram = BTreeMap::new();
+    for day in snapshot
+        .events
+        .iter()
+        .filter_map(|event| event.occurred_at)
+        .map(|at| at.date_naive())
+    {
+        *histogram.entry(day).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// Như [`events_per_day`] nhưng tách theo mức độ của từng ngày.
+pub fn events_per_day_by_severity(
+    snapshot: &TimelineSnapshot,
+) -> BTreeMap<NaiveDate, SeverityCounts> {
+    let mut by_day: BTreeMap<NaiveDate, Vec<&TimelineEvent>> = BTreeMap::new();
+    for event in &snapshot.events {
+        if let Some(at) = event.occurred_at {
+            by_day.entry(at.date_naive()).or_default().push(event);
+        }
+    }
+    by_day
+        .into_iter()
+        .map(|(day, events)| (day, SeverityCounts::tally(events)))
+        .collect()
+}
@@ -0,0 +1,38 @@
+//! Xuất dữ liệu snapshot sang định dạng phẳng cho phân tích bên ngoài.
+
+use crate::CriticalSummary;
+
+/// Dòng tiêu đề của [`trends_to_csv`].
+pub const TRENDS_CSV_HEADER: &str = "metric,timestamp,value,unit";
+
+/// Xu hướng chỉ số sống dạng CSV "long": mỗi điểm một dòng với các cột
+/// `metric,timestamp,value,unit`, theo thứ tự xu hướng rồi thứ tự điểm.
+///
+/// Bỏ qua điểm thiếu giá trị hoặc thời điểm. Thời điểm ghi theo RFC 3339 (UTC),
+/// đơn vị trống khi xu hướng không có đơn vị; mỗi dòng kết thúc bằng `\n`.
+pub fn trends_to_csv(summary: &CriticalSummary) -> String {
+    let mut csv = format!("{TRENDS_CSV_HEADER}\n");
+    for trend in &summary.vital_trends {
+        let metric = escape(&trend.name);
+        let unit = escape(trend.unit.as_deref().unwrap_or_default());
+        for point in &trend.points {
+            let (Some(recorded_at), Some(value)) = (point.recorded_at, point.value) else {
+                continue;
+            };
+            csv.push_str(&format!(
+                "{metric},{},{value},{unit}\n",
+                recorded_at.to_rfc3339()
+            ));
+        }
+    }
+    csv
+}
+
+/// Bọc trường trong dấu nháy kép khi chứa dấu phẩy, nháy kép hoặc xuống dòng.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
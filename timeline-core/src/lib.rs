@@ -1,15 +1,167 @@
 //! Logic lõi xây dựng timeline và bảng thông tin quan trọng.
 
-use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
+pub mod analytics;
+pub mod export;
+pub mod notify;
+pub mod redaction;
+pub mod vitals;
+
 /// Cấu hình điều chỉnh thứ tự ưu tiên và các ngưỡng.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct TimelineConfig {
     /// Khoảng thời gian (giờ) coi là "gần đây" cho các chỉ số sống.
     pub vital_recent_hours: u32,
-    /// Khoảng thời gian (ngày) coi là sự kiện lâm sàng đáng chú ý.
+    /// Khoảng thời gian (ngày) coi là sự kiện lâm sàng đáng chú ý; bệnh nền và kết quả
+    /// cận lâm sàng cũ hơn bị bỏ khỏi bảng trọng yếu trừ khi nằm trong `watchlist`.
     pub clinical_event_days: u32,
+    /// Có đưa thêm thông tin nhân khẩu (tên, tuổi, giới) vào danh sách `alerts` hay không.
+    ///
+    /// Thông tin nhân khẩu luôn có ở `TimelineSnapshot::patient`; đặt `false` để bỏ cảnh
+    /// báo "Patient: …" trùng lặp khi giao diện đã hiển thị banner bệnh nhân.
+    pub emit_patient_alert: bool,
+    /// Chuyển resource chưa hỗ trợ (ví dụ `Basic`) có `code` và ngày thành sự kiện `Other`.
+    pub capture_unknown_resources: bool,
+    /// Mốc bắt đầu (bao gồm) cho resource sinh sự kiện; resource sớm hơn bị bỏ qua khi nạp.
+    ///
+    /// Lọc diễn ra trước cửa sổ "gần đây": anchor vẫn tính trên toàn bộ bundle, nên
+    /// `vital_recent_hours`/`clinical_event_days` áp dụng tiếp lên phần còn lại.
+    /// Dị ứng, thuốc và thông tin bệnh nhân luôn được giữ làm ngữ cảnh.
+    pub from: Option<DateTime<Utc>>,
+    /// Mốc kết thúc (bao gồm) cho resource sinh sự kiện.
+    pub to: Option<DateTime<Utc>>,
+    /// Số ngày tối đa một thời điểm được phép vượt quá hiện tại (lịch hẹn sắp tới);
+    /// xa hơn bị coi là lỗi nhập liệu và không được dùng làm anchor.
+    pub future_tolerance_days: u32,
+    /// Quy tắc tự gắn nhãn cho sự kiện khi chuyển đổi.
+    pub tag_rules: Vec<TagRule>,
+    /// Tên chỉ số/bệnh lý luôn hiển thị trên bảng trọng yếu, bỏ qua cửa sổ "gần đây".
+    pub watchlist: Vec<String>,
+    /// Từ khóa (hoặc mã) thủ thuật mức độ cao; khớp với tên hoặc mã coding của `Procedure`.
+    pub high_acuity_procedures: Vec<String>,
+    /// Anchor cố định thay cho anchor suy ra từ bundle (ví dụ thời điểm export của bulk-data).
+    ///
+    /// Khi có giá trị, độ mới được tính theo anchor này và bộ chuyển đổi bỏ qua lượt quét tìm
+    /// thời điểm hợp lệ muộn nhất của bundle; khi không có, anchor là thời điểm đó. Đầu vào
+    /// (kể cả ndjson) luôn được đọc hết trước khi chuyển đổi, chưa có đường nạp dạng luồng.
+    pub anchor_override: Option<DateTime<Utc>>,
+    /// Thứ tự các mục trong bảng trọng yếu.
+    pub critical_item_order: CriticalItemOrder,
+    /// Từ điển mức độ cục bộ: hệ mã (`coding.system`) → mã → mức độ.
+    ///
+    /// Được đối chiếu với `code.coding` của Condition, Observation và Procedure và ưu
+    /// tiên hơn mọi quy tắc suy luận theo tên hoặc giá trị.
+    pub code_severity: BTreeMap<String, BTreeMap<String, Severity>>,
+    /// Bỏ qua Observation có `derivedFrom` trỏ tới một Observation khác trong cùng bundle
+    /// (ví dụ MAP tính từ huyết áp) để tránh hiển thị trùng giá trị.
+    pub suppress_derived_observations: bool,
+    /// Mức độ cho Observation không phân loại được (mặc định `Info`).
+    ///
+    /// Chỉ là mức sàn: kết quả bất thường nhưng không nhận diện được vẫn cần
+    /// `interpretation` hoặc `referenceRange` để được nâng mức.
+    pub unclassified_observation_severity: Severity,
+    /// Số lần đo bất thường liên tiếp gần nhất (theo xu hướng chỉ số sống) để coi là
+    /// "kéo dài" và nâng mức độ thêm một bậc; `None` tắt tính năng.
+    pub sustained_abnormal_readings: Option<u32>,
+    /// Giữ các sự kiện có thời điểm trong tương lai (lịch hẹn, thủ thuật đã lên lịch).
+    ///
+    /// Khi tắt, sự kiện muộn hơn `anchor_override` (hoặc `Utc::now()` nếu không có) bị bỏ.
+    pub include_future_events: bool,
+    /// Thời điểm ghi vào `generated_at` thay cho `Utc::now()`, để pipeline tái lập được kết quả.
+    pub generated_at: Option<DateTime<Utc>>,
+    /// Chuyển thuốc đã ngừng (`stopped`, `not-taken`) sang
+    /// `CriticalSummary::discontinued_medications` thay vì danh sách thuốc đang dùng.
+    pub separate_discontinued_medications: bool,
+    /// Hạ thuốc dùng khi cần (PRN) đang hiệu lực từ `High` xuống `Moderate`, xếp sau
+    /// thuốc dùng theo lịch; mặc định tắt, PRN chỉ được ghi chú trong `detail`.
+    pub lower_prn_medication_severity: bool,
+    /// Khoảng sinh lý theo nhãn chỉ số sống (không phân biệt hoa thường); giá trị ngoài
+    /// khoảng bị loại trước khi ghi vào chỉ số sống, xu hướng và timeline, kèm cảnh báo.
+    ///
+    /// Map rỗng tắt việc kiểm tra.
+    pub vital_physiologic_ranges: BTreeMap<String, vitals::PhysiologicRange>,
+    /// Thời điểm đại diện cho Observation đo trong một khoảng (`effectivePeriod`).
+    pub observation_period_timing: PeriodTiming,
+    /// Từ khóa giai đoạn bệnh (khớp không phân biệt hoa thường với `Condition.stage[].summary`)
+    /// → mức độ tối thiểu; giai đoạn muộn nâng mức độ của bệnh lý lên ít nhất mức này.
+    pub condition_stage_severity: BTreeMap<String, Severity>,
+    /// Hai lần đo cùng chỉ số, cùng giá trị, cách nhau không quá số giây này được gộp thành
+    /// một điểm xu hướng (máy monitor gửi trùng); `0` chỉ gộp lần đo trùng cả thời điểm.
+    pub vital_duplicate_window_seconds: u32,
+}
+
+/// Thủ thuật cấp cứu mặc định được xếp mức `High`.
+pub const DEFAULT_HIGH_ACUITY_PROCEDURES: &[&str] = &[
+    "intubation",
+    "cpr",
+    "cardiopulmonary resuscitation",
+    "defibrillation",
+    "cardioversion",
+    "central line",
+    "central venous",
+    "chest tube",
+    "thoracostomy",
+    "mechanical ventilation",
+];
+
+/// Giai đoạn muộn mặc định (suy thận giai đoạn 5, ung thư giai đoạn IV/di căn).
+pub const DEFAULT_CONDITION_STAGE_SEVERITY: &[(&str, Severity)] = &[
+    ("stage 5", Severity::High),
+    ("end stage", Severity::High),
+    ("end-stage", Severity::High),
+    ("stage iv", Severity::High),
+    ("metasta", Severity::Critical),
+];
+
+impl TimelineConfig {
+    /// Mức độ cấu hình cho cặp `system`/`code` trong `code_severity`, nếu có.
+    pub fn severity_for_code(&self, system: &str, code: &str) -> Option<Severity> {
+        self.code_severity.get(system)?.get(code).copied()
+    }
+
+    /// `true` khi `name` chứa một mục trong `watchlist` (không phân biệt hoa thường).
+    pub fn is_watched(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.watchlist
+            .iter()
+            .map(|entry| entry.trim().to_lowercase())
+            .any(|entry| !entry.is_empty() && name.contains(&entry))
+    }
+
+    /// Mức độ nặng nhất trong `condition_stage_severity` khớp với mô tả giai đoạn `stage`.
+    pub fn stage_severity(&self, stage: &str) -> Option<Severity> {
+        let stage = stage.to_lowercase();
+        Severity::most_severe(
+            self.condition_stage_severity
+                .iter()
+                .filter(|(keyword, _)| {
+                    let keyword = keyword.trim().to_lowercase();
+                    !keyword.is_empty() && stage.contains(&keyword)
+                })
+                .map(|(_, severity)| *severity),
+        )
+    }
+
+    /// Khoảng sinh lý cấu hình cho chỉ số sống `name`, nếu có.
+    pub fn physiologic_range(&self, name: &str) -> Option<vitals::PhysiologicRange> {
+        self.vital_physiologic_ranges
+            .iter()
+            .find(|(metric, _)| metric.eq_ignore_ascii_case(name))
+            .map(|(_, range)| *range)
+    }
+
+    /// `true` khi `timestamp` nằm trong khoảng `from`/`to`; resource không có thời điểm luôn được giữ.
+    pub fn includes(&self, timestamp: Option<DateTime<Utc>>) -> bool {
+        let Some(timestamp) = timestamp else {
+            return true;
+        };
+        self.from.is_none_or(|from| timestamp >= from) && self.to.is_none_or(|to| timestamp <= to)
+    }
 }
 
 impl Default for TimelineConfig {
@@ -17,53 +169,290 @@ impl Default for TimelineConfig {
         Self {
             vital_recent_hours: 6,
             clinical_event_days: 30,
+            emit_patient_alert: true,
+            capture_unknown_resources: false,
+            from: None,
+            to: None,
+            future_tolerance_days: 365,
+            tag_rules: Vec::new(),
+            watchlist: Vec::new(),
+            high_acuity_procedures: DEFAULT_HIGH_ACUITY_PROCEDURES
+                .iter()
+                .map(|keyword| keyword.to_string())
+                .collect(),
+            anchor_override: None,
+            critical_item_order: CriticalItemOrder::default(),
+            code_severity: BTreeMap::new(),
+            suppress_derived_observations: false,
+            unclassified_observation_severity: Severity::Info,
+            sustained_abnormal_readings: None,
+            include_future_events: true,
+            generated_at: None,
+            separate_discontinued_medications: false,
+            lower_prn_medication_severity: false,
+            vital_physiologic_ranges: vitals::default_physiologic_ranges(),
+            observation_period_timing: PeriodTiming::default(),
+            condition_stage_severity: DEFAULT_CONDITION_STAGE_SEVERITY
+                .iter()
+                .map(|(keyword, severity)| (keyword.to_string(), *severity))
+                .collect(),
+            vital_duplicate_window_seconds: 2,
         }
     }
 }
 
+/// Quy tắc gắn nhãn: sự kiện khớp khi thỏa mọi điều kiện được khai báo.
+///
+/// `keywords` so khớp không phân biệt hoa thường với tiêu đề hoặc chi tiết;
+/// `min_severity` yêu cầu mức độ nặng bằng hoặc hơn ngưỡng.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct TagRule {
+    pub tag: String,
+    pub keywords: Vec<String>,
+    pub min_severity: Option<Severity>,
+}
+
+impl TagRule {
+    /// `true` khi `event` thỏa mọi điều kiện của quy tắc.
+    pub fn matches(&self, event: &TimelineEvent) -> bool {
+        if let Some(threshold) = self.min_severity {
+            if !event.severity.is_at_least(threshold) {
+                return false;
+            }
+        }
+
+        if self.keywords.is_empty() {
+            return true;
+        }
+
+        let title = event.title.to_lowercase();
+        let detail = event.detail.as_deref().unwrap_or_default().to_lowercase();
+        self.keywords.iter().any(|keyword| {
+            let keyword = keyword.to_lowercase();
+            title.contains(&keyword) || detail.contains(&keyword)
+        })
+    }
+}
+
 /// Mức độ ưu tiên hiển thị trên timeline.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Thứ tự `Ord` đi từ nặng nhất (`Critical`) tới nhẹ nhất (`Info`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum Severity {
     Critical,
     High,
     Moderate,
     Low,
+    #[default]
     Info,
 }
 
+impl Severity {
+    /// `true` khi mức độ này nặng bằng hoặc hơn `threshold`.
+    pub fn is_at_least(self, threshold: Severity) -> bool {
+        self <= threshold
+    }
+
+    /// Mức độ nặng nhất trong `severities`; `None` khi rỗng.
+    ///
+    /// `Ord` xếp `Critical` nhỏ nhất nên mức nặng nhất là giá trị `min`.
+    pub fn most_severe<I: IntoIterator<Item = Severity>>(severities: I) -> Option<Severity> {
+        severities.into_iter().min()
+    }
+
+    /// Nặng hơn một bậc (`Critical` giữ nguyên).
+    pub fn escalate(self) -> Severity {
+        match self {
+            Severity::Critical | Severity::High => Severity::Critical,
+            Severity::Moderate => Severity::High,
+            Severity::Low => Severity::Moderate,
+            Severity::Info => Severity::Low,
+        }
+    }
+
+    /// Mức độ nhẹ nhất trong `severities`; `None` khi rỗng.
+    pub fn least_severe<I: IntoIterator<Item = Severity>>(severities: I) -> Option<Severity> {
+        severities.into_iter().max()
+    }
+
+    /// Chuỗi mức độ ổn định dùng cho thuộc tính `data-level` và lớp CSS.
+    pub fn level_str(self) -> &'static str {
+        match self {
+            Severity::Critical => "critical",
+            Severity::High => "high",
+            Severity::Moderate => "moderate",
+            Severity::Low => "low",
+            Severity::Info => "info",
+        }
+    }
+
+    /// Tên biến CSS chứa màu của mức độ (ví dụ `--timeline-severity-critical`).
+    pub fn color_token(self) -> &'static str {
+        match self {
+            Severity::Critical => "--timeline-severity-critical",
+            Severity::High => "--timeline-severity-high",
+            Severity::Moderate => "--timeline-severity-moderate",
+            Severity::Low => "--timeline-severity-low",
+            Severity::Info => "--timeline-severity-info",
+        }
+    }
+
+    /// Màu mặc định (trùng giá trị của `color_token` trong stylesheet gốc).
+    pub fn color_hex(self) -> &'static str {
+        match self {
+            Severity::Critical => "#b42318",
+            Severity::High => "#dc6803",
+            Severity::Moderate => "#067647",
+            Severity::Low => "#0b5394",
+            Severity::Info => "#475467",
+        }
+    }
+
+    /// Biểu tượng ngắn gọn cho renderer dạng văn bản.
+    pub fn icon(self) -> char {
+        match self {
+            Severity::Critical => '⛔',
+            Severity::High => '⚠',
+            Severity::Moderate => '●',
+            Severity::Low => '○',
+            Severity::Info => 'ℹ',
+        }
+    }
+}
+
 /// Thông tin quan trọng cần hiển thị tức thời.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct VitalTrend {
     pub name: String,
     pub unit: Option<String>,
     pub points: Vec<VitalTrendPoint>,
 }
 
-impl Default for VitalTrend {
-    fn default() -> Self {
-        Self {
-            name: String::new(),
-            unit: None,
-            points: Vec::new(),
+/// Phép tổng hợp áp dụng cho mỗi khung thời gian khi gộp điểm dữ liệu.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupAggregation {
+    Mean,
+    Min,
+    Max,
+}
+
+impl VitalTrend {
+    /// Gộp các điểm vào khung thời gian cố định (căn theo mốc Unix epoch).
+    ///
+    /// Mỗi khung có dữ liệu sinh một điểm đặt tại đầu khung; khung rỗng bị bỏ qua,
+    /// điểm thiếu thời gian hoặc giá trị không được tính. `window` không dương trả
+    /// về bản sao nguyên vẹn.
+    pub fn rollup(&self, window: Duration, aggregation: RollupAggregation) -> VitalTrend {
+        let window_seconds = window.num_seconds();
+        if window_seconds <= 0 {
+            return self.clone();
+        }
+
+        let mut buckets: BTreeMap<i64, Vec<f64>> = BTreeMap::new();
+        for point in &self.points {
+            let (Some(recorded_at), Some(value)) = (point.recorded_at, point.value) else {
+                continue;
+            };
+            let bucket = recorded_at.timestamp().div_euclid(window_seconds);
+            buckets.entry(bucket).or_default().push(value);
+        }
+
+        let points = buckets
+            .into_iter()
+            .map(|(bucket, values)| {
+                let value = match aggregation {
+                    RollupAggregation::Mean => values.iter().sum::<f64>() / values.len() as f64,
+                    RollupAggregation::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+                    RollupAggregation::Max => {
+                        values.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+                    }
+                };
+                VitalTrendPoint {
+                    recorded_at: DateTime::from_timestamp(bucket * window_seconds, 0),
+                    value: Some(value),
+                    label: None,
+                }
+            })
+            .collect();
+
+        VitalTrend {
+            name: self.name.clone(),
+            unit: self.unit.clone(),
+            points,
         }
     }
 }
 
 /// Một điểm dữ liệu trong biểu đồ chỉ số sống.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct VitalTrendPoint {
     pub recorded_at: Option<DateTime<Utc>>,
     pub value: Option<f64>,
     pub label: Option<String>,
 }
 
-impl Default for VitalTrendPoint {
-    fn default() -> Self {
+/// Thống kê nhỏ nhất/lớn nhất/mới nhất của một chuỗi giá trị.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SeriesStats {
+    pub min: f64,
+    pub max: f64,
+    pub latest: f64,
+}
+
+impl SeriesStats {
+    /// Tính thống kê từ các giá trị đã sắp xếp theo thời gian; `None` khi rỗng.
+    pub fn from_values(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        values.into_iter().fold(None, |stats, value| {
+            Some(match stats {
+                None => SeriesStats {
+                    min: value,
+                    max: value,
+                    latest: value,
+                },
+                Some(stats) => SeriesStats {
+                    min: stats.min.min(value),
+                    max: stats.max.max(value),
+                    latest: value,
+                },
+            })
+        })
+    }
+}
+
+/// Một lần đo huyết áp gồm cả tâm thu và tâm trương.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BloodPressurePoint {
+    pub recorded_at: Option<DateTime<Utc>>,
+    pub systolic: f64,
+    pub diastolic: f64,
+}
+
+/// Xu hướng huyết áp với thống kê riêng cho từng thành phần.
+///
+/// `VitalTrend` "Blood pressure" chỉ mang giá trị tâm thu; cấu trúc này giữ cả
+/// hai chuỗi để giao diện không phải phân tích lại chuỗi hiển thị.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct BloodPressureTrend {
+    pub unit: Option<String>,
+    pub points: Vec<BloodPressurePoint>,
+    pub systolic: Option<SeriesStats>,
+    pub diastolic: Option<SeriesStats>,
+}
+
+impl BloodPressureTrend {
+    /// Dựng xu hướng từ các lần đo, sắp xếp theo thời gian và tính thống kê.
+    pub fn new(unit: Option<String>, mut points: Vec<BloodPressurePoint>) -> Self {
+        points.sort_by_key(|point| point.recorded_at);
+        let systolic = SeriesStats::from_values(points.iter().map(|point| point.systolic));
+        let diastolic = SeriesStats::from_values(points.iter().map(|point| point.diastolic));
         Self {
-            recorded_at: None,
-            value: None,
-            label: None,
+            unit,
+            points,
+            systolic,
+            diastolic,
         }
     }
 }
@@ -77,6 +466,9 @@ pub struct DiagnosticSnapshot {
     pub severity: Severity,
     pub kind: DiagnosticKind,
     pub unit: Option<String>,
+    /// Thuộc `TimelineConfig::watchlist`.
+    #[serde(default)]
+    pub watched: bool,
 }
 
 impl Default for DiagnosticSnapshot {
@@ -88,6 +480,7 @@ impl Default for DiagnosticSnapshot {
             severity: Severity::Info,
             kind: DiagnosticKind::Lab,
             unit: None,
+            watched: false,
         }
     }
 }
@@ -101,10 +494,39 @@ pub enum DiagnosticKind {
     Other,
 }
 
+impl DiagnosticKind {
+    /// Loại chẩn đoán từ chuỗi category (text, display hoặc code của `Observation.category`).
+    ///
+    /// So khớp không phân biệt hoa thường theo từng từ (tách theo ký tự không phải chữ
+    /// hoặc số): "laboratory"/"lab" → `Lab`, "imaging"/"radiology" → `Imaging`. Chỉ số
+    /// sống ("vital-signs") và category không nhận diện được (kể cả "label") trả `None`.
+    pub fn from_category_text(text: &str) -> Option<DiagnosticKind> {
+        let text = text.to_lowercase();
+        let tokens: Vec<&str> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .collect();
+        let has = |words: &[&str]| tokens.iter().any(|token| words.contains(token));
+        if has(&["vital", "vitals"]) {
+            None
+        } else if has(&["lab", "laboratory"]) {
+            Some(DiagnosticKind::Lab)
+        } else if has(&["imaging", "radiology"]) {
+            Some(DiagnosticKind::Imaging)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct CriticalSummary {
     pub allergies: Vec<CriticalItem>,
     pub medications: Vec<CriticalItem>,
+    /// Thuốc đã ngừng (`stopped`, `not-taken`) khi bật
+    /// `TimelineConfig::separate_discontinued_medications`.
+    #[serde(default)]
+    pub discontinued_medications: Vec<CriticalItem>,
     pub chronic_conditions: Vec<CriticalItem>,
     pub code_status: Option<String>,
     pub alerts: Vec<CriticalItem>,
@@ -113,6 +535,167 @@ pub struct CriticalSummary {
     pub vital_trends: Vec<VitalTrend>,
     #[serde(default)]
     pub recent_diagnostics: Vec<DiagnosticSnapshot>,
+    #[serde(default)]
+    pub blood_pressure_trend: Option<BloodPressureTrend>,
+    /// Số chỉ số sống bị loại khỏi `recent_vitals` vì cũ hơn cửa sổ cấu hình.
+    #[serde(default)]
+    pub older_vitals: usize,
+    /// Độ dài cửa sổ (giờ) dùng để lọc `recent_vitals`, nếu biết.
+    #[serde(default)]
+    pub vital_window_hours: Option<u32>,
+}
+
+impl CriticalSummary {
+    /// Kết quả chẩn đoán bất thường (mức độ từ `High` trở lên).
+    pub fn abnormal_diagnostics(&self) -> Vec<&DiagnosticSnapshot> {
+        self.recent_diagnostics
+            .iter()
+            .filter(|item| item.severity.is_at_least(Severity::High))
+            .collect()
+    }
+
+    /// Chỉ số sống gần đây bất thường (mức độ từ `High` trở lên).
+    pub fn abnormal_vitals(&self) -> Vec<&VitalSnapshot> {
+        self.recent_vitals
+            .iter()
+            .filter(|vital| vital.severity.is_at_least(Severity::High))
+            .collect()
+    }
+
+    /// Bắt đầu dựng bảng thông tin trọng yếu từ nguồn không phải FHIR.
+    ///
+    /// ```
+    /// use timeline_core::{CriticalItem, CriticalSummary, Severity};
+    ///
+    /// let item = |label: &str| CriticalItem {
+    ///     label: label.to_string(),
+    ///     detail: None,
+    ///     severity: Severity::High,
+    ///     recorded_at: None,
+    ///     reactions: Vec::new(),
+    ///     watched: false,
+    /// };
+    /// let summary = CriticalSummary::builder()
+    ///     .allergy(item("Penicillin"))
+    ///     .medication(item("Warfarin"))
+    ///     .medication(item("Metformin"))
+    ///     .alert(item("Fall risk"))
+    ///     .code_status("DNR")
+    ///     .build();
+    ///
+    /// assert_eq!(summary.allergies.len(), 1);
+    /// assert_eq!(summary.medications.len(), 2);
+    /// assert_eq!(summary.alerts.len(), 1);
+    /// assert_eq!(summary.code_status.as_deref(), Some("DNR"));
+    /// ```
+    pub fn builder() -> CriticalSummaryBuilder {
+        CriticalSummaryBuilder::default()
+    }
+}
+
+/// Bộ dựng `CriticalSummary`; mỗi phương thức thêm một mục vào danh sách tương ứng.
+#[derive(Debug, Clone, Default)]
+pub struct CriticalSummaryBuilder {
+    summary: CriticalSummary,
+}
+
+impl CriticalSummaryBuilder {
+    pub fn allergy(mut self, item: CriticalItem) -> Self {
+        self.summary.allergies.push(item);
+        self
+    }
+
+    pub fn medication(mut self, item: CriticalItem) -> Self {
+        self.summary.medications.push(item);
+        self
+    }
+
+    pub fn discontinued_medication(mut self, item: CriticalItem) -> Self {
+        self.summary.discontinued_medications.push(item);
+        self
+    }
+
+    pub fn chronic_condition(mut self, item: CriticalItem) -> Self {
+        self.summary.chronic_conditions.push(item);
+        self
+    }
+
+    pub fn alert(mut self, item: CriticalItem) -> Self {
+        self.summary.alerts.push(item);
+        self
+    }
+
+    pub fn code_status(mut self, status: impl Into<String>) -> Self {
+        self.summary.code_status = Some(status.into());
+        self
+    }
+
+    pub fn vital(mut self, vital: VitalSnapshot) -> Self {
+        self.summary.recent_vitals.push(vital);
+        self
+    }
+
+    pub fn diagnostic(mut self, diagnostic: DiagnosticSnapshot) -> Self {
+        self.summary.recent_diagnostics.push(diagnostic);
+        self
+    }
+
+    pub fn trend(mut self, trend: VitalTrend) -> Self {
+        self.summary.vital_trends.push(trend);
+        self
+    }
+
+    pub fn build(self) -> CriticalSummary {
+        self.summary
+    }
+}
+
+/// Thông tin nhân khẩu hiển thị ở đầu giao diện.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct PatientDemographics {
+    pub name: Option<String>,
+    /// Tuổi tính đến ngày hiện tại.
+    pub age: Option<i32>,
+    pub gender: Option<String>,
+    pub birth_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub deceased: bool,
+    #[serde(default)]
+    pub deceased_at: Option<DateTime<Utc>>,
+}
+
+impl PatientDemographics {
+    /// Số trường có giá trị, dùng để chọn bản ghi đầy đủ hơn khi gộp.
+    pub fn completeness(&self) -> usize {
+        [
+            self.name.is_some(),
+            self.age.is_some(),
+            self.gender.is_some(),
+            self.birth_date.is_some(),
+            self.deceased_at.is_some(),
+        ]
+        .into_iter()
+        .filter(|present| *present)
+        .count()
+    }
+
+    /// Gộp thông tin từ một bản ghi `Patient` khác: bản ghi đầy đủ hơn làm gốc,
+    /// các trường còn trống được bổ sung từ bản ghi kia.
+    pub fn merge(&mut self, other: PatientDemographics) {
+        let (base, extra) = if other.completeness() > self.completeness() {
+            (other, std::mem::take(self))
+        } else {
+            (std::mem::take(self), other)
+        };
+        *self = PatientDemographics {
+            name: base.name.or(extra.name),
+            age: base.age.or(extra.age),
+            gender: base.gender.or(extra.gender),
+            birth_date: base.birth_date.or(extra.birth_date),
+            deceased: base.deceased || extra.deceased,
+            deceased_at: base.deceased_at.or(extra.deceased_at),
+        };
+    }
 }
 
 /// Mục thông tin trọng yếu (dị ứng, thuốc, cảnh báo).
@@ -121,6 +704,94 @@ pub struct CriticalItem {
     pub label: String,
     pub detail: Option<String>,
     pub severity: Severity,
+    /// Thời điểm ghi nhận của resource nguồn, dùng khi sắp xếp theo độ mới.
+    #[serde(default)]
+    pub recorded_at: Option<DateTime<Utc>>,
+    /// Phản ứng có cấu trúc của mục dị ứng; rỗng với các loại mục khác.
+    #[serde(default)]
+    pub reactions: Vec<AllergyReaction>,
+    /// Mục khớp `TimelineConfig::watchlist` (hiện chỉ bệnh nền); được giữ dù đã cũ.
+    #[serde(default)]
+    pub watched: bool,
+}
+
+/// Một biểu hiện trong `AllergyIntolerance.reaction`, giữ mức độ và đường phơi nhiễm
+/// của phản ứng chứa nó.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AllergyReaction {
+    pub manifestation: String,
+    /// Mức độ từ `reaction.severity` (`severe` → `Critical`, `moderate` → `High`, `mild` → `Moderate`).
+    #[serde(default)]
+    pub severity: Option<Severity>,
+    /// Chất gây phản ứng cụ thể (`reaction.substance`), nếu khác mã dị ứng.
+    #[serde(default)]
+    pub substance: Option<String>,
+    #[serde(default)]
+    pub exposure_route: Option<String>,
+}
+
+/// Thứ tự các mục trong từng nhóm của bảng trọng yếu (dị ứng, thuốc, bệnh lý, cảnh báo).
+///
+/// Luôn xếp theo mức độ trước (nặng nhất đầu tiên); khóa phụ do biến thể quyết định,
+/// cuối cùng theo `label` để thứ tự ổn định.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CriticalItemOrder {
+    /// Cùng mức độ thì mục mới ghi nhận đứng trước; mục không có thời điểm xếp cuối.
+    #[default]
+    SeverityThenRecency,
+    /// Cùng mức độ thì theo `label` (không phân biệt hoa thường).
+    SeverityThenAlphabetical,
+}
+
+impl CriticalItemOrder {
+    /// So sánh hai mục theo thứ tự đã chọn.
+    pub fn compare(self, a: &CriticalItem, b: &CriticalItem) -> std::cmp::Ordering {
+        let secondary = match self {
+            CriticalItemOrder::SeverityThenRecency => match (a.recorded_at, b.recorded_at) {
+                (Some(a), Some(b)) => b.cmp(&a),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            },
+            CriticalItemOrder::SeverityThenAlphabetical => {
+                a.label.to_lowercase().cmp(&b.label.to_lowercase())
+            }
+        };
+        a.severity
+            .cmp(&b.severity)
+            .then(secondary)
+            .then_with(|| a.label.cmp(&b.label))
+    }
+}
+
+/// Cách chọn một thời điểm đại diện cho khoảng thời gian (`Period`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PeriodTiming {
+    /// Đầu khoảng.
+    #[default]
+    Start,
+    /// Cuối khoảng.
+    End,
+    /// Điểm giữa, hợp với phép đo liên tục trong cả khoảng.
+    Midpoint,
+}
+
+impl PeriodTiming {
+    /// Thời điểm đại diện cho khoảng `start`–`end`; thiếu một đầu thì dùng đầu còn lại.
+    pub fn instant(
+        self,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Option<DateTime<Utc>> {
+        match (self, start, end) {
+            (PeriodTiming::Start, Some(start), _) => Some(start),
+            (PeriodTiming::End, _, Some(end)) => Some(end),
+            (PeriodTiming::Midpoint, Some(start), Some(end)) => Some(start + (end - start) / 2),
+            _ => start.or(end),
+        }
+    }
 }
 
 /// Ảnh chụp chỉ số sống.
@@ -131,6 +802,11 @@ pub struct VitalSnapshot {
     pub recorded_at: Option<DateTime<Utc>>,
     pub numeric_value: Option<f64>,
     pub unit: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+    /// Thuộc `TimelineConfig::watchlist`; luôn hiển thị dù không còn "gần đây".
+    #[serde(default)]
+    pub watched: bool,
 }
 
 /// Một sự kiện trong timeline.
@@ -143,10 +819,164 @@ pub struct TimelineEvent {
     pub occurred_at: Option<DateTime<Utc>>,
     pub severity: Severity,
     pub source: Option<ResourceReference>,
+    /// Tiêu đề panel cha (Observation.hasMember) nếu sự kiện là thành viên của panel.
+    #[serde(default)]
+    pub panel: Option<String>,
+    /// Nhóm lấy từ `Observation.category` của nguồn; giao diện ưu tiên nhóm này hơn từ khóa tiêu đề.
+    #[serde(default)]
+    pub observation_category: Option<ObservationCategory>,
+    /// Nhãn do người dùng hoặc quy tắc `TagRule` gắn (ví dụ "relevant-to-sepsis").
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Kết thúc khoảng thời gian (thuốc, lượt khám có `period`); khi có, `occurred_at` là thời điểm bắt đầu.
+    #[serde(default)]
+    pub period_end: Option<DateTime<Utc>>,
+    /// Độ chính xác của `occurred_at`; ngày không đầy đủ (ví dụ chỉ có năm) được chuẩn hóa
+    /// về nửa đêm UTC nên giao diện cần biết để không hiển thị giờ "00:00" giả.
+    #[serde(default)]
+    pub occurred_at_precision: DatePrecision,
+    /// Ghi chú diễn giải của nguồn (ví dụ `Observation.note[].text`: "bệnh nhân kích động
+    /// khi đo"), giữ riêng khỏi `detail` để giá trị đo vẫn đọc được.
+    #[serde(default)]
+    pub notes: Vec<String>,
 }
 
-/// Nhãn phân loại để trình bày timeline.
+impl TimelineEvent {
+    /// Sự kiện tối thiểu: chưa có thời điểm, mức độ `Info`, các trường tùy chọn để trống.
+    /// Trường khác đặt bằng cú pháp cập nhật struct:
+    /// `TimelineEvent { severity, ..TimelineEvent::new(id, category, title) }`.
+    pub fn new(id: impl Into<String>, category: EventCategory, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            category,
+            title: title.into(),
+            detail: None,
+            occurred_at: None,
+            severity: Severity::Info,
+            source: None,
+            panel: None,
+            observation_category: None,
+            tags: Vec::new(),
+            period_end: None,
+            occurred_at_precision: DatePrecision::DateTime,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Thứ tự toàn phần của timeline: `occurred_at` tăng dần (thiếu thời điểm đứng đầu),
+    /// cùng thời điểm thì mức độ nặng hơn trước, cuối cùng theo `id` tăng dần.
+    pub fn chronological_cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.occurred_at
+            .cmp(&other.occurred_at)
+            .then_with(|| self.tiebreak_cmp(other))
+    }
+
+    /// Khóa phụ khi hai sự kiện cùng thời điểm: mức độ (nặng trước), rồi `id`.
+    ///
+    /// Danh sách "mới nhất trước" của giao diện đảo chiều thời gian nhưng giữ khóa phụ này.
+    pub fn tiebreak_cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.severity
+            .cmp(&other.severity)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+
+    /// Nhóm quan sát của sự kiện: ưu tiên `observation_category` từ nguồn, nếu không có
+    /// thì đoán theo từ khóa trong tiêu đề. Sự kiện không phải `Observation` trả `None`.
+    pub fn observation_bucket(&self) -> Option<ObservationCategory> {
+        if self.category != EventCategory::Observation {
+            return None;
+        }
+        self.observation_category
+            .or_else(|| observation_bucket_from_title(&self.title.to_lowercase()))
+    }
+
+    /// Sự kiện là chỉ số sống (mạch, huyết áp, SpO2...).
+    pub fn is_vital(&self) -> bool {
+        self.observation_bucket() == Some(ObservationCategory::VitalSigns)
+    }
+
+    /// Sự kiện là xét nghiệm.
+    pub fn is_lab(&self) -> bool {
+        self.observation_bucket() == Some(ObservationCategory::Laboratory)
+    }
+
+    /// Sự kiện là chẩn đoán hình ảnh.
+    pub fn is_imaging(&self) -> bool {
+        self.observation_bucket() == Some(ObservationCategory::Imaging)
+    }
+}
+
+const VITAL_EVENT_KEYWORDS: &[&str] = &[
+    "heart rate",
+    "blood pressure",
+    "respiratory rate",
+    "spo2",
+    "oxygen saturation",
+    "temperature",
+    "pulse",
+];
+
+const LAB_EVENT_KEYWORDS: &[&str] = &[
+    "lactate",
+    "troponin",
+    "culture",
+    "panel",
+    "cbc",
+    "chemistry",
+    "creatinine",
+    "glucose",
+    "magnesium",
+    "blood gas",
+];
+
+const IMAGING_EVENT_KEYWORDS: &[&str] = &["ct", "mri", "x-ray", "xray", "ultrasound", "radiograph"];
+
+/// Đoán nhóm quan sát từ tiêu đề đã chuyển chữ thường; ảnh chỉ khớp nguyên từ ("ct").
+fn observation_bucket_from_title(title: &str) -> Option<ObservationCategory> {
+    if VITAL_EVENT_KEYWORDS
+        .iter()
+        .any(|keyword| title.contains(keyword))
+    {
+        Some(ObservationCategory::VitalSigns)
+    } else if IMAGING_EVENT_KEYWORDS
+        .iter()
+        .any(|keyword| title.split_whitespace().any(|token| token == *keyword))
+    {
+        Some(ObservationCategory::Imaging)
+    } else if LAB_EVENT_KEYWORDS
+        .iter()
+        .any(|keyword| title.contains(keyword))
+    {
+        Some(ObservationCategory::Laboratory)
+    } else {
+        None
+    }
+}
+
+/// Độ chính xác của một mốc thời gian lấy từ nguồn (FHIR cho phép `2019`, `2019-03`,
+/// `2019-03-14` hoặc thời điểm đầy đủ).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DatePrecision {
+    Year,
+    Month,
+    Day,
+    #[default]
+    DateTime,
+}
+
+/// Nhóm quan sát theo hệ mã `observation-category` của FHIR.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ObservationCategory {
+    VitalSigns,
+    Laboratory,
+    Imaging,
+    Survey,
+}
+
+/// Nhãn phân loại để trình bày timeline.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum EventCategory {
     Encounter,
     Procedure,
@@ -166,43 +996,309 @@ pub struct ResourceReference {
     pub display: Option<String>,
 }
 
+/// Nhóm của bảng trọng yếu chứa một [`CriticalItem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CriticalGroup {
+    Alert,
+    Allergy,
+    Medication,
+    DiscontinuedMedication,
+    ChronicCondition,
+}
+
+/// Một mục của [`TimelineSnapshot::priority_order`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriorityEntry<'a> {
+    Event(&'a TimelineEvent),
+    Critical {
+        group: CriticalGroup,
+        item: &'a CriticalItem,
+    },
+}
+
+impl PriorityEntry<'_> {
+    pub fn severity(&self) -> Severity {
+        match self {
+            PriorityEntry::Event(event) => event.severity,
+            PriorityEntry::Critical { item, .. } => item.severity,
+        }
+    }
+
+    /// `occurred_at` của sự kiện hoặc `recorded_at` của mục trọng yếu.
+    pub fn recorded_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            PriorityEntry::Event(event) => event.occurred_at,
+            PriorityEntry::Critical { item, .. } => item.recorded_at,
+        }
+    }
+}
+
+/// Phiên bản lược đồ snapshot hiện tại; tăng khi thêm trường gây phá vỡ tương thích.
+/// Lịch sử thay đổi xem `docs/schema.md`.
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 2;
+
 /// Kết quả tổng hợp cuối cùng.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TimelineSnapshot {
+    /// Phiên bản lược đồ; payload cũ không có trường này được hiểu là `0`.
+    #[serde(default)]
+    pub schema_version: u32,
     pub generated_at: DateTime<Utc>,
     pub critical: CriticalSummary,
     pub events: Vec<TimelineEvent>,
+    /// Thông tin nhân khẩu của bệnh nhân nếu bundle có resource `Patient`.
+    #[serde(default)]
+    pub patient: Option<PatientDemographics>,
+    /// Cảnh báo chất lượng dữ liệu phát sinh khi chuyển đổi (ví dụ thời điểm bất thường).
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Số sự kiện theo nhóm, tính khi dựng snapshot (xem [`TimelineSnapshot::category_counts`]).
+    #[serde(default)]
+    pub category_summary: BTreeMap<EventCategory, usize>,
 }
 
 impl TimelineSnapshot {
-    /// Khởi tạo snapshot từ các thành phần đã chuẩn bị.
-    pub fn new(critical: CriticalSummary, mut events: Vec<TimelineEvent>) -> Self {
-        events.sort_by_key(|event| event.occurred_at);
+    /// Khởi tạo snapshot từ các thành phần đã chuẩn bị; sự kiện được sắp xếp
+    /// theo [`TimelineEvent::chronological_cmp`].
+    pub fn new(critical: CriticalSummary, events: Vec<TimelineEvent>) -> Self {
+        Self::new_with_generated_at(critical, events, Utc::now())
+    }
+
+    /// Như [`TimelineSnapshot::new`] nhưng giữ nguyên `generated_at` được truyền vào.
+    pub fn new_with_generated_at(
+        critical: CriticalSummary,
+        mut events: Vec<TimelineEvent>,
+        generated_at: DateTime<Utc>,
+    ) -> Self {
+        events.sort_by(TimelineEvent::chronological_cmp);
         Self {
-            generated_at: Utc::now(),
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            generated_at,
             critical,
+            category_summary: category_counts(&events),
             events,
+            patient: None,
+            warnings: Vec::new(),
         }
     }
 
+    /// Thay bảng thông tin trọng yếu (ví dụ sau khi bác sĩ chỉnh sửa) và làm mới `generated_at`.
+    pub fn with_critical(mut self, critical: CriticalSummary) -> Self {
+        self.critical = critical;
+        self.generated_at = Utc::now();
+        self
+    }
+
+    /// Thay danh sách sự kiện (sắp xếp lại như [`TimelineSnapshot::new`]) và làm mới `generated_at`.
+    pub fn with_events(mut self, mut events: Vec<TimelineEvent>) -> Self {
+        events.sort_by(TimelineEvent::chronological_cmp);
+        self.category_summary = category_counts(&events);
+        self.events = events;
+        self.generated_at = Utc::now();
+        self
+    }
+
     /// Truy cập bảng thông tin trọng yếu.
     pub fn critical_panel(&self) -> &CriticalSummary {
         &self.critical
     }
 
-    /// Danh sách sự kiện đã sắp xếp theo thời gian.
+    /// Danh sách sự kiện đã sắp xếp theo thời gian (xem [`TimelineEvent::chronological_cmp`]).
     pub fn timeline(&self) -> &[TimelineEvent] {
         &self.events
     }
+
+    /// Sự kiện bất thường (mức độ từ `High` trở lên), giữ thứ tự thời gian.
+    pub fn abnormal_events(&self) -> Vec<&TimelineEvent> {
+        self.events
+            .iter()
+            .filter(|event| event.severity.is_at_least(Severity::High))
+            .collect()
+    }
+
+    /// Số sự kiện theo mức độ.
+    pub fn severity_counts(&self) -> SeverityCounts {
+        SeverityCounts::tally(&self.events)
+    }
+
+    /// Đếm lại số sự kiện theo nhóm từ danh sách hiện tại (khi `events` đã bị sửa trực tiếp).
+    pub fn category_counts(&self) -> BTreeMap<EventCategory, usize> {
+        category_counts(&self.events)
+    }
+
+    /// Sự kiện mới nhất của từng nhóm, cho phần tóm tắt phân loại cấp cứu.
+    ///
+    /// Sự kiện không có thời điểm chỉ được chọn khi nhóm không có sự kiện nào có thời điểm;
+    /// cùng thời điểm thì sự kiện đứng sau trong `events` (theo thứ tự timeline) thắng.
+    pub fn latest_per_category(&self) -> HashMap<EventCategory, &TimelineEvent> {
+        let mut latest: HashMap<EventCategory, &TimelineEvent> = HashMap::new();
+        for event in &self.events {
+            latest
+                .entry(event.category)
+                .and_modify(|current| {
+                    if event.occurred_at >= current.occurred_at {
+                        *current = event;
+                    }
+                })
+                .or_insert(event);
+        }
+        latest
+    }
+
+    /// Danh sách công việc chung cho sự kiện và mục trọng yếu: nặng nhất trước, cùng
+    /// mức độ thì mới nhất trước (không có thời điểm xếp cuối).
+    ///
+    /// Khi bằng nhau hoàn toàn, mục trọng yếu đứng trước sự kiện; mục trọng yếu theo thứ tự
+    /// cảnh báo, dị ứng, thuốc, thuốc đã ngừng, bệnh lý; sự kiện theo thứ tự timeline.
+    pub fn priority_order(&self) -> Vec<PriorityEntry<'_>> {
+        let critical = &self.critical;
+        let groups = [
+            (CriticalGroup::Alert, &critical.alerts),
+            (CriticalGroup::Allergy, &critical.allergies),
+            (CriticalGroup::Medication, &critical.medications),
+            (
+                CriticalGroup::DiscontinuedMedication,
+                &critical.discontinued_medications,
+            ),
+            (
+                CriticalGroup::ChronicCondition,
+                &critical.chronic_conditions,
+            ),
+        ];
+        let mut entries: Vec<PriorityEntry<'_>> = groups
+            .into_iter()
+            .flat_map(|(group, items)| {
+                items
+                    .iter()
+                    .map(move |item| PriorityEntry::Critical { group, item })
+            })
+            .chain(self.events.iter().map(PriorityEntry::Event))
+            .collect();
+        entries.sort_by(|a, b| {
+            a.severity()
+                .cmp(&b.severity())
+                .then_with(|| b.recorded_at().cmp(&a.recorded_at()))
+        });
+        entries
+    }
+
+    /// Tóm tắt một dòng cho log, ví dụ "12 events, 2 critical, code status: DNR, 3 allergies".
+    pub fn summary_line(&self) -> String {
+        let counts = self.severity_counts();
+        let allergies = self.critical.allergies.len();
+        format!(
+            "{} {}, {} critical, code status: {}, {} {}",
+            counts.total,
+            if counts.total == 1 { "event" } else { "events" },
+            counts.critical,
+            self.critical
+                .code_status
+                .as_deref()
+                .unwrap_or("not recorded"),
+            allergies,
+            if allergies == 1 {
+                "allergy"
+            } else {
+                "allergies"
+            },
+        )
+    }
+
+    /// Báo cáo nhiều dòng (thời điểm tạo, số sự kiện theo mức độ, các nhóm trọng yếu).
+    pub fn summary_report(&self) -> String {
+        let counts = self.severity_counts();
+        let critical = &self.critical;
+        [
+            format!("Generated at: {}", self.generated_at.to_rfc3339()),
+            format!(
+                "Timeline events: {} (critical {}, high {}, moderate {}, low {}, info {})",
+                counts.total,
+                counts.critical,
+                counts.high,
+                counts.moderate,
+                counts.low,
+                counts.info
+            ),
+            format!(
+                "Code status: {}",
+                critical.code_status.as_deref().unwrap_or("not recorded")
+            ),
+            format!("Critical alerts: {}", critical.alerts.len()),
+            format!("Allergies: {}", critical.allergies.len()),
+            format!("Medications: {}", critical.medications.len()),
+            format!("Chronic conditions: {}", critical.chronic_conditions.len()),
+        ]
+        .join("\n")
+    }
+}
+
+fn category_counts(events: &[TimelineEvent]) -> BTreeMap<EventCategory, usize> {
+    let mut counts = BTreeMap::new();
+    for event in events {
+        *counts.entry(event.category).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Số sự kiện theo từng mức độ.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    pub total: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub moderate: usize,
+    pub low: usize,
+    pub info: usize,
+}
+
+impl SeverityCounts {
+    /// Đếm sự kiện theo mức độ.
+    pub fn tally<'a>(events: impl IntoIterator<Item = &'a TimelineEvent>) -> Self {
+        let mut counts = Self::default();
+        for event in events {
+            counts.total += 1;
+            match event.severity {
+                Severity::Critical => counts.critical += 1,
+                Severity::High => counts.high += 1,
+                Severity::Moderate => counts.moderate += 1,
+                Severity::Low => counts.low += 1,
+                Severity::Info => counts.info += 1,
+            }
+        }
+        counts
+    }
+
+    /// Số sự kiện đúng mức `severity`.
+    pub fn count(&self, severity: Severity) -> usize {
+        match severity {
+            Severity::Critical => self.critical,
+            Severity::High => self.high,
+            Severity::Moderate => self.moderate,
+            Severity::Low => self.low,
+            Severity::Info => self.info,
+        }
+    }
 }
 
 /// Lỗi chung khi tạo timeline.
+///
+/// Lỗi cấu trúc bundle có biến thể riêng để client phân biệt được; `Parse` dành
+/// cho các trường hợp còn lại.
 #[derive(Debug, thiserror::Error)]
 pub enum TimelineError {
     #[error("Dữ liệu đầu vào thiếu thông tin tối thiểu")]
     MissingData,
     #[error("Không đọc được dữ liệu: {0}")]
     Parse(String),
+    /// Đầu vào không phải JSON hợp lệ.
+    #[error("JSON không hợp lệ: {0}")]
+    InvalidJson(String),
+    /// JSON hợp lệ nhưng `resourceType` không phải `Bundle`.
+    #[error("Cần resourceType Bundle, nhận được {found}")]
+    NotABundle { found: String },
+    /// Bundle khai báo phiên bản FHIR ngoài R4/R4B/R5.
+    #[error("Phiên bản FHIR {version} chưa được hỗ trợ")]
+    UnsupportedVersion { version: String },
     #[error("Lỗi khác: {0}")]
     Other(String),
 }
@@ -210,8 +1306,35 @@ pub enum TimelineError {
 /// Tiện ích dựng snapshot rỗng (dùng cho mock/testing).
 pub fn empty_snapshot() -> TimelineSnapshot {
     TimelineSnapshot {
+        schema_version: SNAPSHOT_SCHEMA_VERSION,
         generated_at: Utc::now(),
         critical: CriticalSummary::default(),
         events: Vec::new(),
+        patient: None,
+        warnings: Vec::new(),
+        category_summary: BTreeMap::new(),
+    }
+}
+
+/// Nhãn nhóm theo ngày của timeline so với `now` (theo ngày UTC): "Today",
+/// "Yesterday", "Tomorrow", "N days ago"/"in N days" trong vòng 6 ngày, xa hơn
+/// thì hiển thị ngày dạng `MM/DD/YYYY`.
+pub fn format_day_label(timestamp: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
+    let Some(dt) = timestamp else {
+        return "Unknown time".to_string();
+    };
+
+    let delta_days = now
+        .date_naive()
+        .signed_duration_since(dt.date_naive())
+        .num_days();
+
+    match delta_days {
+        0 => "Today".to_string(),
+        1 => "Yesterday".to_string(),
+        -1 => "Tomorrow".to_string(),
+        2..=6 => format!("{delta_days} days ago"),
+        -6..=-2 => format!("in {} days", delta_days.abs()),
+        _ => dt.format("%m/%d/%Y").to_string(),
     }
 }
@@ -0,0 +1,86 @@
+//! Payload gọn cho hệ thống nhắn tin/paging khi snapshot có mục nguy kịch.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{CriticalItem, CriticalItemOrder, Severity, TimelineSnapshot};
+
+/// Số mục và số chỉ số sống tối đa trong một payload.
+pub const MAX_PAYLOAD_ENTRIES: usize = 5;
+
+/// Tóm tắt mục trọng yếu và chỉ số sống bất thường (mức `High` trở lên) của snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CriticalAlertPayload {
+    pub generated_at: DateTime<Utc>,
+    pub patient_name: Option<String>,
+    pub code_status: Option<String>,
+    /// Mức độ nặng nhất trong `items` và `vitals`.
+    pub highest_severity: Severity,
+    /// Cảnh báo, dị ứng, thuốc và bệnh lý, nặng nhất rồi mới nhất trước.
+    pub items: Vec<PayloadEntry>,
+    /// Chỉ số sống gần đây bất thường, nặng nhất trước.
+    pub vitals: Vec<PayloadEntry>,
+}
+
+/// Một dòng của payload.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadEntry {
+    pub label: String,
+    pub detail: Option<String>,
+    pub severity: Severity,
+}
+
+/// Payload cho `snapshot`, hoặc `None` khi không có mục trọng yếu hay chỉ số sống nào
+/// từ mức `High` trở lên. Mỗi danh sách giữ tối đa [`MAX_PAYLOAD_ENTRIES`] dòng.
+pub fn critical_payload(snapshot: &TimelineSnapshot) -> Option<CriticalAlertPayload> {
+    let critical = &snapshot.critical;
+    let mut items: Vec<&CriticalItem> = [
+        &critical.alerts,
+        &critical.allergies,
+        &critical.medications,
+        &critical.chronic_conditions,
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|item| item.severity.is_at_least(Severity::High))
+    .collect();
+    items.sort_by(|a, b| CriticalItemOrder::SeverityThenRecency.compare(a, b));
+
+    let mut vitals = critical.abnormal_vitals();
+    vitals.sort_by_key(|vital| vital.severity);
+
+    let highest_severity = Severity::most_severe(
+        items
+            .iter()
+            .map(|item| item.severity)
+            .chain(vitals.iter().map(|vital| vital.severity)),
+    )?;
+
+    Some(CriticalAlertPayload {
+        generated_at: snapshot.generated_at,
+        patient_name: snapshot
+            .patient
+            .as_ref()
+            .and_then(|patient| patient.name.clone()),
+        code_status: critical.code_status.clone(),
+        highest_severity,
+        items: items
+            .into_iter()
+            .take(MAX_PAYLOAD_ENTRIES)
+            .map(|item| PayloadEntry {
+                label: item.label.clone(),
+                detail: item.detail.clone(),
+                severity: item.severity,
+            })
+            .collect(),
+        vitals: vitals
+            .into_iter()
+            .take(MAX_PAYLOAD_ENTRIES)
+            .map(|vital| PayloadEntry {
+                label: vital.name.clone(),
+                detail: Some(vital.value.clone()),
+                severity: vital.severity,
+            })
+            .collect(),
+    })
+}
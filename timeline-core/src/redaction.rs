@@ -0,0 +1,162 @@
+//! Che thông tin định danh (PHI) trên snapshot cho demo, ảnh chụp màn hình và ticket hỗ trợ.
+//!
+//! Quy tắc của [`TimelineSnapshot::redact`]:
+//!
+//! 1. `patient.name` thay bằng [`REDACTED`], `patient.birth_date` bị xóa (giữ tuổi, giới).
+//! 2. Mọi chỗ xuất hiện tên bệnh nhân trong nhãn, chi tiết, tiêu đề và cảnh báo
+//!    (kể cả cảnh báo "Patient: ...") đều được thay bằng [`REDACTED`].
+//! 3. `display` của tham chiếu tới `Patient`, `Practitioner`, `RelatedPerson` được thay
+//!    bằng [`REDACTED`]; tham chiếu tới resource lâm sàng giữ nguyên.
+//! 4. Trong tiêu đề và chi tiết tài liệu (`EventCategory::Document`), từ trông giống mã
+//!    định danh được thay bằng [`REDACTED_ID`]: "MRN" theo sau là dấu phân cách hoặc chữ số
+//!    (như `MRN:12345`, `MRN12345`; "mRNA" không bị coi là mã), hoặc từ chỉ gồm chữ/số và
+//!    có từ 5 chữ số trở lên (ngày `2025-10-30` không bị coi là mã).
+//! 5. Ghi chú của sự kiện (`TimelineEvent::notes`) chỉ được thay tên bệnh nhân, như chi
+//!    tiết; số liệu lâm sàng trong ghi chú (ví dụ "Platelets 150000") giữ nguyên.
+//!
+//! Nội dung lâm sàng (giá trị, mức độ, thời điểm, xu hướng) không thay đổi.
+
+use crate::{CriticalItem, EventCategory, ResourceReference, TimelineEvent, TimelineSnapshot};
+
+/// Chuỗi thay thế cho tên người.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Chuỗi thay thế cho mã định danh (MRN, số hồ sơ).
+pub const REDACTED_ID: &str = "[ID]";
+
+/// Loại resource có `display` là tên người.
+const PERSON_RESOURCE_TYPES: [&str; 3] = ["Patient", "Practitioner", "RelatedPerson"];
+
+impl TimelineSnapshot {
+    /// Bản sao đã che thông tin định danh theo các quy tắc của module [`crate::redaction`].
+    pub fn redact(&self) -> TimelineSnapshot {
+        let mut snapshot = self.clone();
+        let name = snapshot
+            .patient
+            .as_ref()
+            .and_then(|patient| patient.name.clone())
+            .filter(|name| !name.trim().is_empty());
+        let scrub = |text: &mut String| {
+            if let Some(name) = name.as_deref() {
+                if text.contains(name) {
+                    *text = text.replace(name, REDACTED);
+                }
+            }
+        };
+
+        if let Some(patient) = snapshot.patient.as_mut() {
+            if patient.name.is_some() {
+                patient.name = Some(REDACTED.to_string());
+            }
+            patient.birth_date = None;
+        }
+
+        let critical = &mut snapshot.critical;
+        for item in critical
+            .alerts
+            .iter_mut()
+            .chain(critical.allergies.iter_mut())
+            .chain(critical.medications.iter_mut())
+            .chain(critical.discontinued_medications.iter_mut())
+            .chain(critical.chronic_conditions.iter_mut())
+        {
+            redact_item(item, &scrub);
+        }
+
+        for event in &mut snapshot.events {
+            redact_event(event, &scrub);
+        }
+        for warning in &mut snapshot.warnings {
+            scrub(warning);
+        }
+
+        snapshot
+    }
+}
+
+fn redact_item(item: &mut CriticalItem, scrub: &impl Fn(&mut String)) {
+    if let Some(rest) = item.label.strip_prefix("Patient: ") {
+        if !rest.is_empty() {
+            item.label = format!("Patient: {REDACTED}");
+        }
+    }
+    scrub(&mut item.label);
+    if let Some(detail) = item.detail.as_mut() {
+        scrub(detail);
+    }
+}
+
+fn redact_event(event: &mut TimelineEvent, scrub: &impl Fn(&mut String)) {
+    scrub(&mut event.title);
+    if let Some(detail) = event.detail.as_mut() {
+        scrub(detail);
+    }
+
+    if event.category == EventCategory::Document {
+        event.title = redact_identifiers(&event.title);
+        event.detail = event.detail.as_deref().map(redact_identifiers);
+    }
+    for note in &mut event.notes {
+        scrub(note);
+    }
+
+    if let Some(source) = event.source.as_mut() {
+        redact_reference(source, scrub);
+    }
+}
+
+fn redact_reference(reference: &mut ResourceReference, scrub: &impl Fn(&mut String)) {
+    let is_person = reference
+        .reference
+        .as_deref()
+        .and_then(|reference| reference.split_once('/'))
+        .is_some_and(|(resource_type, _)| PERSON_RESOURCE_TYPES.contains(&resource_type));
+
+    if let Some(display) = reference.display.as_mut() {
+        if is_person {
+            *display = REDACTED.to_string();
+        } else {
+            scrub(display);
+        }
+    }
+}
+
+/// Thay các từ trông giống mã định danh trong `text` bằng [`REDACTED_ID`].
+pub fn redact_identifiers(text: &str) -> String {
+    text.split(' ')
+        .map(|token| {
+            if looks_like_identifier(token) {
+                REDACTED_ID
+            } else {
+                token
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn looks_like_identifier(token: &str) -> bool {
+    let trimmed = token.trim_matches(|c: char| !c.is_alphanumeric());
+    if is_mrn(trimmed) {
+        return true;
+    }
+    trimmed.chars().all(char::is_alphanumeric)
+        && trimmed.chars().filter(char::is_ascii_digit).count() >= 5
+}
+
+/// `MRN` theo sau là dấu phân cách hoặc chữ số (`MRN:8812345`, `mrn-77`, `MRN8812`);
+/// từ thường như "mRNA" không khớp.
+fn is_mrn(token: &str) -> bool {
+    if !token
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("mrn"))
+    {
+        return false;
+    }
+    let rest = &token[3..];
+    match rest.chars().next() {
+        Some(c) if c.is_ascii_digit() => rest.chars().all(|c| c.is_ascii_digit()),
+        Some(c) => matches!(c, ':' | '#' | '-' | '_' | '/' | '='),
+        None => false,
+    }
+}
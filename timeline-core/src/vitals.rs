@@ -0,0 +1,134 @@
+//! Ngưỡng phân loại mức độ nghiêm trọng cho chỉ số sống và xét nghiệm cấp cứu.
+//!
+//! Dùng chung giữa bộ chuyển đổi FHIR và giao diện để một giá trị luôn được
+//! đánh giá giống nhau dù ở panel, timeline hay từng điểm trên biểu đồ.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Severity;
+
+/// Khoảng giá trị sinh lý có thể gặp của một chỉ số (bao gồm hai đầu).
+///
+/// Giá trị ngoài khoảng (SpO2 300%, mạch 0) được coi là lỗi thiết bị hoặc nhập
+/// liệu, không phải tình trạng bệnh nhân.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct PhysiologicRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl PhysiologicRange {
+    pub fn contains(&self, value: f64) -> bool {
+        value >= self.min && value <= self.max
+    }
+}
+
+/// Khoảng sinh lý mặc định theo nhãn chỉ số sống, đủ rộng để giữ mọi giá trị
+/// cực đoan nhưng vẫn có thể xảy ra (mạch 250, SpO2 40%).
+///
+/// Huyết áp được kiểm tra trên giá trị tâm thu. Nhiệt độ không có khoảng mặc định
+/// vì nguồn dữ liệu có thể dùng °C hoặc °F.
+pub fn default_physiologic_ranges() -> BTreeMap<String, PhysiologicRange> {
+    [
+        ("Heart rate", 1.0, 350.0),
+        ("SpO2", 1.0, 100.0),
+        ("Respiratory rate", 1.0, 100.0),
+        ("Blood pressure", 20.0, 350.0),
+    ]
+    .into_iter()
+    .map(|(name, min, max)| (name.to_string(), PhysiologicRange { min, max }))
+    .collect()
+}
+
+/// Phân loại một giá trị số theo tên chỉ số (không phân biệt hoa thường).
+///
+/// Trả về `None` khi tên không thuộc nhóm chỉ số đã biết. Huyết áp chỉ được
+/// nhận diện qua tên thành phần "systolic"/"diastolic"; giá trị gộp dạng
+/// "120/80" dùng [`classify_blood_pressure`].
+pub fn classify_vital(name: &str, value: f64) -> Option<Severity> {
+    let normalized = name.to_lowercase();
+
+    if normalized.contains("heart rate") || normalized.contains("pulse") {
+        return Some(match value {
+            v if v >= 140.0 => Severity::Critical,
+            v if v >= 120.0 => Severity::High,
+            v if v <= 40.0 => Severity::Critical,
+            v if v <= 50.0 => Severity::High,
+            _ => Severity::Moderate,
+        });
+    }
+
+    if normalized.contains("respiratory rate") {
+        return Some(match value {
+            v if v >= 35.0 => Severity::Critical,
+            v if v >= 28.0 => Severity::High,
+            v if v <= 8.0 => Severity::Critical,
+            v if v <= 10.0 => Severity::High,
+            _ => Severity::Moderate,
+        });
+    }
+
+    if normalized.contains("spo2") || normalized.contains("oxygen saturation") {
+        return Some(match value {
+            v if v < 85.0 => Severity::Critical,
+            v if v < 92.0 => Severity::High,
+            _ => Severity::Moderate,
+        });
+    }
+
+    if normalized.contains("diastolic") {
+        return Some(classify_diastolic(value));
+    }
+
+    if normalized.contains("systolic") {
+        return Some(classify_systolic(value));
+    }
+
+    if normalized.contains("glasgow") || normalized.contains("gcs") {
+        return Some(classify_gcs(value));
+    }
+
+    if normalized.contains("lactate") {
+        return Some(match value {
+            v if v >= 4.0 => Severity::Critical,
+            v if v >= 2.0 => Severity::High,
+            _ => Severity::Moderate,
+        });
+    }
+
+    None
+}
+
+/// Phân loại huyết áp theo thành phần nặng hơn giữa tâm thu và tâm trương.
+pub fn classify_blood_pressure(systolic: f64, diastolic: f64) -> Severity {
+    classify_systolic(systolic).min(classify_diastolic(diastolic))
+}
+
+/// Phân loại tổng điểm Glasgow (3–15): ≤ 8 nguy kịch, ≤ 12 cao.
+pub fn classify_gcs(total: f64) -> Severity {
+    match total {
+        v if v <= 8.0 => Severity::Critical,
+        v if v <= 12.0 => Severity::High,
+        _ => Severity::Moderate,
+    }
+}
+
+fn classify_systolic(value: f64) -> Severity {
+    match value {
+        v if v >= 200.0 => Severity::Critical,
+        v if v >= 180.0 => Severity::High,
+        v if v <= 80.0 => Severity::High,
+        _ => Severity::Moderate,
+    }
+}
+
+fn classify_diastolic(value: f64) -> Severity {
+    match value {
+        v if v >= 120.0 => Severity::Critical,
+        v if v >= 110.0 => Severity::High,
+        v if v <= 50.0 => Severity::High,
+        _ => Severity::Moderate,
+    }
+}
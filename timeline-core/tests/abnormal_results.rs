@@ -0,0 +1,86 @@
+use chrono::{TimeZone, Utc};
+use timeline_core::{
+    CriticalSummary, DiagnosticKind, DiagnosticSnapshot, EventCategory, Severity, TimelineEvent,
+    TimelineSnapshot, VitalSnapshot,
+};
+
+fn diagnostic(name: &str, value: &str, severity: Severity) -> DiagnosticSnapshot {
+    DiagnosticSnapshot {
+        name: name.to_string(),
+        value: value.to_string(),
+        severity,
+        kind: DiagnosticKind::Lab,
+        ..DiagnosticSnapshot::default()
+    }
+}
+
+fn vital(name: &str, value: &str, severity: Severity) -> VitalSnapshot {
+    VitalSnapshot {
+        name: name.to_string(),
+        value: value.to_string(),
+        recorded_at: None,
+        numeric_value: None,
+        unit: None,
+        severity,
+        watched: false,
+    }
+}
+
+fn event(id: &str, severity: Severity, hour: u32) -> TimelineEvent {
+    TimelineEvent {
+        occurred_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, hour, 0, 0).unwrap()),
+        severity,
+        ..TimelineEvent::new(id, EventCategory::Observation, id)
+    }
+}
+
+#[test]
+fn abnormal_filters_keep_high_and_critical_only() {
+    let critical = CriticalSummary {
+        recent_diagnostics: vec![
+            diagnostic("Lactate", "4.5 mmol/L", Severity::Critical),
+            diagnostic("Sodium", "139 mmol/L", Severity::Moderate),
+        ],
+        recent_vitals: vec![
+            vital("Heart rate", "128 bpm", Severity::High),
+            vital("Temperature", "37.0 C", Severity::Info),
+        ],
+        ..CriticalSummary::default()
+    };
+
+    let labs: Vec<&str> = critical
+        .abnormal_diagnostics()
+        .into_iter()
+        .map(|item| item.name.as_str())
+        .collect();
+    assert_eq!(labs, vec!["Lactate"]);
+
+    let vitals: Vec<&str> = critical
+        .abnormal_vitals()
+        .into_iter()
+        .map(|item| item.name.as_str())
+        .collect();
+    assert_eq!(vitals, vec!["Heart rate"]);
+
+    let snapshot = TimelineSnapshot::new(
+        critical,
+        vec![
+            event("obs-lactate", Severity::Critical, 9),
+            event("obs-sodium", Severity::Moderate, 8),
+            event("obs-hr", Severity::High, 7),
+        ],
+    );
+    let events: Vec<&str> = snapshot
+        .abnormal_events()
+        .into_iter()
+        .map(|event| event.id.as_str())
+        .collect();
+    assert_eq!(events, vec!["obs-hr", "obs-lactate"]);
+}
+
+#[test]
+fn is_at_least_follows_severity_order() {
+    assert!(Severity::Critical.is_at_least(Severity::High));
+    assert!(Severity::High.is_at_least(Severity::High));
+    assert!(!Severity::Moderate.is_at_least(Severity::High));
+}
@@ -0,0 +1,304 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use timeline_core::analytics::{
+    derived_bmi, deterioration_windows, events_per_day, events_per_day_by_severity,
+    recent_weighted_delta, reportable_delta, reportable_weighted_delta, unit_consistency,
+    vitals_completeness, DeteriorationConfig, TrendDeltaThresholds, EXPECTED_VITALS,
+};
+use timeline_core::{
+    CriticalSummary, EventCategory, Severity, TimelineEvent, TimelineSnapshot, VitalSnapshot,
+    VitalTrend, VitalTrendPoint,
+};
+
+fn vital(name: &str, value: &str) -> VitalSnapshot {
+    VitalSnapshot {
+        name: name.to_string(),
+        value: value.to_string(),
+        recorded_at: None,
+        numeric_value: None,
+        unit: None,
+        severity: Severity::Moderate,
+        watched: false,
+    }
+}
+
+#[test]
+fn completeness_reports_missing_vitals() {
+    let summary = CriticalSummary {
+        recent_vitals: vec![
+            vital("Heart rate", "88 bpm"),
+            vital("Blood pressure", "118/76 mmHg"),
+            vital("SpO2", "97 %"),
+        ],
+        ..CriticalSummary::default()
+    };
+
+    let report = vitals_completeness(&summary, &EXPECTED_VITALS);
+
+    assert!((report.percentage - 60.0).abs() < f64::EPSILON);
+    assert_eq!(report.present, vec!["Heart rate", "Blood pressure", "SpO2"]);
+    assert_eq!(report.missing, vec!["Respiratory rate", "Temperature"]);
+}
+
+#[test]
+fn empty_expected_set_is_complete() {
+    let report = vitals_completeness(&CriticalSummary::default(), &[]);
+    assert!((report.percentage - 100.0).abs() < f64::EPSILON);
+    assert!(report.missing.is_empty());
+}
+
+fn measurement(name: &str, value: f64, unit: &str) -> VitalSnapshot {
+    VitalSnapshot {
+        numeric_value: Some(value),
+        unit: Some(unit.to_string()),
+        ..vital(name, &format!("{value} {unit}"))
+    }
+}
+
+#[test]
+fn bmi_is_derived_from_height_and_weight() {
+    let summary = CriticalSummary {
+        recent_vitals: vec![
+            measurement("Body height", 170.0, "cm"),
+            measurement("Body weight", 70.0, "kg"),
+        ],
+        ..CriticalSummary::default()
+    };
+
+    let bmi = derived_bmi(&summary).expect("BMI should be derived");
+
+    assert_eq!(bmi.name, "BMI");
+    assert_eq!(bmi.numeric_value, Some(24.2));
+    assert_eq!(bmi.value, "24.2 kg/m2");
+}
+
+#[test]
+fn bmi_requires_non_zero_height_and_known_units() {
+    let without_height = CriticalSummary {
+        recent_vitals: vec![measurement("Body weight", 70.0, "kg")],
+        ..CriticalSummary::default()
+    };
+    assert!(derived_bmi(&without_height).is_none());
+
+    let zero_height = CriticalSummary {
+        recent_vitals: vec![
+            measurement("Body height", 0.0, "cm"),
+            measurement("Body weight", 70.0, "kg"),
+        ],
+        ..CriticalSummary::default()
+    };
+    assert!(derived_bmi(&zero_height).is_none());
+
+    let imperial = CriticalSummary {
+        recent_vitals: vec![
+            measurement("Body height", 67.0, "[in_i]"),
+            measurement("Body weight", 154.0, "[lb_av]"),
+        ],
+        ..CriticalSummary::default()
+    };
+    assert_eq!(
+        derived_bmi(&imperial).and_then(|bmi| bmi.numeric_value),
+        Some(24.1)
+    );
+}
+
+fn trend(name: &str, points: &[(u32, u32, f64)]) -> VitalTrend {
+    VitalTrend {
+        name: name.to_string(),
+        unit: None,
+        points: points
+            .iter()
+            .map(|&(hour, minute, value)| VitalTrendPoint {
+                recorded_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, hour, minute, 0).unwrap()),
+                value: Some(value),
+                label: None,
+            })
+            .collect(),
+    }
+}
+
+fn trends_snapshot(vital_trends: Vec<VitalTrend>) -> TimelineSnapshot {
+    let critical = CriticalSummary {
+        vital_trends,
+        ..CriticalSummary::default()
+    };
+    TimelineSnapshot::new(critical, Vec::new())
+}
+
+#[test]
+fn vitals_worsening_in_the_same_hour_form_one_window() {
+    let snapshot = trends_snapshot(vec![
+        trend(
+            "Heart rate",
+            &[(9, 0, 82.0), (10, 10, 128.0), (10, 40, 135.0)],
+        ),
+        trend("SpO2", &[(9, 0, 97.0), (10, 45, 89.0)]),
+        trend("Respiratory rate", &[(9, 0, 16.0), (14, 0, 30.0)]),
+    ]);
+
+    let windows = deterioration_windows(&snapshot, &DeteriorationConfig::default());
+
+    assert_eq!(windows.len(), 1);
+    let window = &windows[0];
+    assert_eq!(
+        window.start,
+        Utc.with_ymd_and_hms(2025, 10, 30, 10, 10, 0).unwrap()
+    );
+    assert_eq!(
+        window.end,
+        Utc.with_ymd_and_hms(2025, 10, 30, 10, 45, 0).unwrap()
+    );
+    assert_eq!(window.vitals, vec!["Heart rate", "SpO2"]);
+}
+
+#[test]
+fn deterioration_respects_configured_vitals_and_threshold() {
+    let snapshot = trends_snapshot(vec![
+        trend("Heart rate", &[(9, 0, 82.0), (10, 10, 128.0)]),
+        trend("SpO2", &[(9, 0, 97.0), (10, 45, 89.0)]),
+    ]);
+
+    let heart_only = DeteriorationConfig {
+        vitals: vec!["heart rate".to_string()],
+        ..DeteriorationConfig::default()
+    };
+    assert!(deterioration_windows(&snapshot, &heart_only).is_empty());
+
+    let critical_only = DeteriorationConfig {
+        abnormal_at: Severity::Critical,
+        ..DeteriorationConfig::default()
+    };
+    assert!(deterioration_windows(&snapshot, &critical_only).is_empty());
+}
+
+fn at(day: u32, hour: u32) -> Option<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(2025, 10, day, hour, 0, 0).single()
+}
+
+fn dated_event(id: &str, occurred_at: Option<DateTime<Utc>>, severity: Severity) -> TimelineEvent {
+    TimelineEvent {
+        occurred_at,
+        severity,
+        ..TimelineEvent::new(id, EventCategory::Observation, id)
+    }
+}
+
+#[test]
+fn histogram_counts_events_per_day() {
+    let snapshot = TimelineSnapshot::new(
+        CriticalSummary::default(),
+        vec![
+            dated_event("a", at(29, 8), Severity::Critical),
+            dated_event("b", at(29, 23), Severity::Moderate),
+            dated_event("c", at(30, 0), Severity::Critical),
+            dated_event("d", at(30, 6), Severity::High),
+            dated_event("e", at(30, 12), Severity::High),
+            dated_event("undated", None, Severity::Critical),
+        ],
+    );
+
+    let oct = |day| NaiveDate::from_ymd_opt(2025, 10, day).unwrap();
+    let histogram = events_per_day(&snapshot);
+    assert_eq!(histogram.len(), 2);
+    assert_eq!(histogram[&oct(29)], 2);
+    assert_eq!(histogram[&oct(30)], 3);
+
+    let by_severity = events_per_day_by_severity(&snapshot);
+    assert_eq!(by_severity[&oct(29)].critical, 1);
+    assert_eq!(by_severity[&oct(29)].moderate, 1);
+    assert_eq!(by_severity[&oct(30)].total, 3);
+    assert_eq!(by_severity[&oct(30)].high, 2);
+}
+
+fn unit_trend(name: &str, unit: &str, values: &[f64]) -> VitalTrend {
+    let points: Vec<(u32, u32, f64)> = values
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| (8 + index as u32, 0, value))
+        .collect();
+    VitalTrend {
+        unit: Some(unit.to_string()),
+        ..trend(name, &points)
+    }
+}
+
+#[test]
+fn delta_gate_uses_per_metric_and_per_unit_thresholds() {
+    let thresholds = TrendDeltaThresholds::default();
+
+    let ph = unit_trend("pH", "[pH]", &[7.38, 7.31, 7.18]);
+    let ph_delta = reportable_delta(&ph, &thresholds).expect("pH drop of 0.2 is reported");
+    assert!((ph_delta + 0.2).abs() < 1e-9);
+
+    let glucose = unit_trend("Glucose", "mg/dL", &[142.0, 142.5]);
+    assert_eq!(reportable_delta(&glucose, &thresholds), None);
+
+    let heart_rate = unit_trend("Heart rate", "/min", &[110.0, 118.0]);
+    assert_eq!(reportable_delta(&heart_rate, &thresholds), Some(8.0));
+}
+
+#[test]
+fn delta_gate_thresholds_are_configurable() {
+    let glucose = unit_trend("Glucose", "mg/dL", &[142.0, 142.5]);
+    let strict = TrendDeltaThresholds {
+        by_unit: Default::default(),
+        ..TrendDeltaThresholds::default()
+    };
+    assert_eq!(reportable_delta(&glucose, &strict), Some(0.5));
+
+    let lenient_ph: TrendDeltaThresholds =
+        serde_json::from_str(r#"{ "by_metric": { "PH": 0.5 } }"#).expect("valid thresholds");
+    assert_eq!(lenient_ph.default, 0.5);
+    let ph = unit_trend("pH", "[pH]", &[7.38, 7.18]);
+    assert_eq!(reportable_delta(&ph, &lenient_ph), None);
+
+    let single_point = unit_trend("pH", "[pH]", &[7.1]);
+    assert_eq!(reportable_delta(&single_point, &strict), None);
+}
+
+#[test]
+fn metric_recorded_in_two_units_is_flagged() {
+    let summary = CriticalSummary {
+        recent_vitals: vec![
+            VitalSnapshot {
+                unit: Some("mmHg".to_string()),
+                ..vital("Blood pressure", "118/76 mmHg")
+            },
+            VitalSnapshot {
+                unit: Some("/min".to_string()),
+                ..vital("Heart rate", "88 /min")
+            },
+            vital("SpO2", "97 %"),
+        ],
+        vital_trends: vec![
+            unit_trend("blood pressure", "kPa", &[15.7, 16.0]),
+            unit_trend("Heart rate", "/min", &[88.0, 92.0]),
+        ],
+        ..CriticalSummary::default()
+    };
+
+    let reports = unit_consistency(&summary);
+
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[0].metric, "Blood pressure");
+    assert_eq!(reports[0].units, vec!["kPa", "mmHg"]);
+    assert!(reports[0].inconsistent);
+    assert_eq!(reports[1].metric, "Heart rate");
+    assert_eq!(reports[1].units, vec!["/min"]);
+    assert!(!reports[1].inconsistent);
+}
+
+#[test]
+fn weighted_mode_reports_the_fall_after_a_spike() {
+    let thresholds = TrendDeltaThresholds::default();
+    let spike = unit_trend("Heart rate", "/min", &[80.0, 80.0, 140.0, 80.0]);
+
+    assert_eq!(reportable_delta(&spike, &thresholds), None);
+    assert_eq!(recent_weighted_delta(&spike, 0.5), Some(-30.0));
+    let weighted = reportable_weighted_delta(&spike, &thresholds).expect("recent fall is reported");
+    assert!(weighted < 0.0);
+
+    let steady = unit_trend("Heart rate", "/min", &[80.0, 80.2, 80.1]);
+    assert_eq!(reportable_weighted_delta(&steady, &thresholds), None);
+    let single_point = unit_trend("Heart rate", "/min", &[80.0]);
+    assert_eq!(recent_weighted_delta(&single_point, 0.5), None);
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use timeline_core::format_day_label;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 10, 30, 9, 30, 0).unwrap()
+}
+
+fn label_for(days_ago: i64) -> String {
+    format_day_label(Some(now() - Duration::days(days_ago)), now())
+}
+
+#[test]
+fn same_and_adjacent_days_use_words() {
+    assert_eq!(label_for(0), "Today");
+    assert_eq!(label_for(1), "Yesterday");
+    assert_eq!(label_for(-1), "Tomorrow");
+}
+
+#[test]
+fn calendar_days_not_elapsed_hours_decide_the_label() {
+    let late_yesterday = Utc.with_ymd_and_hms(2025, 10, 29, 23, 59, 0).unwrap();
+    let early_today = Utc.with_ymd_and_hms(2025, 10, 30, 0, 0, 0).unwrap();
+    assert_eq!(format_day_label(Some(late_yesterday), now()), "Yesterday");
+    assert_eq!(format_day_label(Some(early_today), now()), "Today");
+}
+
+#[test]
+fn within_six_days_counts_days() {
+    assert_eq!(label_for(2), "2 days ago");
+    assert_eq!(label_for(6), "6 days ago");
+    assert_eq!(label_for(-2), "in 2 days");
+    assert_eq!(label_for(-6), "in 6 days");
+}
+
+#[test]
+fn seven_days_or_more_falls_back_to_date() {
+    assert_eq!(label_for(7), "10/23/2025");
+    assert_eq!(label_for(-7), "11/06/2025");
+    assert_eq!(label_for(400), "09/25/2024");
+}
+
+#[test]
+fn missing_timestamp_is_unknown() {
+    assert_eq!(format_day_label(None, now()), "Unknown time");
+}
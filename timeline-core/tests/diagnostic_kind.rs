@@ -0,0 +1,47 @@
+use timeline_core::DiagnosticKind;
+
+#[test]
+fn laboratory_categories_map_to_lab() {
+    assert_eq!(
+        DiagnosticKind::from_category_text("laboratory"),
+        Some(DiagnosticKind::Lab)
+    );
+    assert_eq!(
+        DiagnosticKind::from_category_text("LAB"),
+        Some(DiagnosticKind::Lab)
+    );
+    assert_eq!(
+        DiagnosticKind::from_category_text("Laboratory results"),
+        Some(DiagnosticKind::Lab)
+    );
+}
+
+#[test]
+fn words_merely_containing_lab_are_not_lab() {
+    assert_eq!(DiagnosticKind::from_category_text("label"), None);
+    assert_eq!(DiagnosticKind::from_category_text("collaboration"), None);
+    assert_eq!(DiagnosticKind::from_category_text("Syllabus review"), None);
+}
+
+#[test]
+fn imaging_categories_map_to_imaging() {
+    assert_eq!(
+        DiagnosticKind::from_category_text("imaging"),
+        Some(DiagnosticKind::Imaging)
+    );
+    assert_eq!(
+        DiagnosticKind::from_category_text("Radiology"),
+        Some(DiagnosticKind::Imaging)
+    );
+}
+
+#[test]
+fn vital_signs_are_not_diagnostics() {
+    assert_eq!(DiagnosticKind::from_category_text("vital-signs"), None);
+}
+
+#[test]
+fn unknown_category_is_none() {
+    assert_eq!(DiagnosticKind::from_category_text("social-history"), None);
+    assert_eq!(DiagnosticKind::from_category_text(""), None);
+}
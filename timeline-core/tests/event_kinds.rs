@@ -0,0 +1,43 @@
+use timeline_core::{EventCategory, ObservationCategory, TimelineEvent};
+
+fn event(
+    title: &str,
+    category: EventCategory,
+    observation_category: Option<ObservationCategory>,
+) -> TimelineEvent {
+    TimelineEvent {
+        observation_category,
+        ..TimelineEvent::new("evt-1", category, title)
+    }
+}
+
+#[test]
+fn blood_pressure_event_is_vital() {
+    let bp = event("Blood pressure", EventCategory::Observation, None);
+    assert!(bp.is_vital());
+    assert!(!bp.is_lab());
+    assert!(!bp.is_imaging());
+}
+
+#[test]
+fn ct_event_is_imaging() {
+    let ct = event("CT head without contrast", EventCategory::Observation, None);
+    assert!(ct.is_imaging());
+    assert!(!ct.is_vital());
+
+    // "ct" only matches as a whole word.
+    assert!(!event("Lactate", EventCategory::Observation, None).is_imaging());
+}
+
+#[test]
+fn source_category_wins_over_title_keywords() {
+    let ferritin = event(
+        "Ferritin",
+        EventCategory::Observation,
+        Some(ObservationCategory::Laboratory),
+    );
+    assert!(ferritin.is_lab());
+
+    let procedure = event("CT-guided biopsy", EventCategory::Procedure, None);
+    assert!(!procedure.is_imaging(), "only observations are bucketed");
+}
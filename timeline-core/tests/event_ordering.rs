@@ -0,0 +1,37 @@
+use chrono::{TimeZone, Utc};
+use timeline_core::{CriticalSummary, EventCategory, Severity, TimelineEvent, TimelineSnapshot};
+
+fn event(id: &str, severity: Severity) -> TimelineEvent {
+    TimelineEvent {
+        occurred_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, 9, 0, 0).unwrap()),
+        severity,
+        ..TimelineEvent::new(id, EventCategory::Observation, id)
+    }
+}
+
+fn ordered_ids(events: Vec<TimelineEvent>) -> Vec<String> {
+    TimelineSnapshot::new(CriticalSummary::default(), events)
+        .events
+        .into_iter()
+        .map(|event| event.id)
+        .collect()
+}
+
+#[test]
+fn same_instant_events_order_by_severity_then_id() {
+    let expected = vec!["obs-potassium", "obs-chloride", "obs-sodium"];
+
+    let forward = ordered_ids(vec![
+        event("obs-sodium", Severity::Info),
+        event("obs-chloride", Severity::Info),
+        event("obs-potassium", Severity::High),
+    ]);
+    let reversed = ordered_ids(vec![
+        event("obs-potassium", Severity::High),
+        event("obs-sodium", Severity::Info),
+        event("obs-chloride", Severity::Info),
+    ]);
+
+    assert_eq!(forward, expected);
+    assert_eq!(reversed, expected);
+}
@@ -0,0 +1,58 @@
+use chrono::{TimeZone, Utc};
+use timeline_core::export::{trends_to_csv, TRENDS_CSV_HEADER};
+use timeline_core::{CriticalSummary, VitalTrend, VitalTrendPoint};
+
+fn point(hour: u32, value: Option<f64>) -> VitalTrendPoint {
+    VitalTrendPoint {
+        recorded_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, hour, 0, 0).unwrap()),
+        value,
+        label: None,
+    }
+}
+
+#[test]
+fn trends_csv_has_one_row_per_valued_point() {
+    let summary = CriticalSummary {
+        vital_trends: vec![
+            VitalTrend {
+                name: "Heart rate".to_string(),
+                unit: Some("/min".to_string()),
+                points: vec![point(8, Some(88.0)), point(9, None), point(10, Some(112.5))],
+            },
+            VitalTrend {
+                name: "Blood pressure, systolic".to_string(),
+                unit: None,
+                points: vec![
+                    point(8, Some(95.0)),
+                    VitalTrendPoint {
+                        recorded_at: None,
+                        value: Some(90.0),
+                        label: None,
+                    },
+                ],
+            },
+        ],
+        ..CriticalSummary::default()
+    };
+
+    let csv = trends_to_csv(&summary);
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], TRENDS_CSV_HEADER);
+    assert_eq!(lines[0], "metric,timestamp,value,unit");
+    assert_eq!(lines.len() - 1, 3);
+    assert_eq!(lines[1], "Heart rate,2025-10-30T08:00:00+00:00,88,/min");
+    assert_eq!(lines[2], "Heart rate,2025-10-30T10:00:00+00:00,112.5,/min");
+    assert_eq!(
+        lines[3],
+        "\"Blood pressure, systolic\",2025-10-30T08:00:00+00:00,95,"
+    );
+}
+
+#[test]
+fn empty_summary_exports_only_the_header() {
+    assert_eq!(
+        trends_to_csv(&CriticalSummary::default()),
+        format!("{TRENDS_CSV_HEADER}\n")
+    );
+}
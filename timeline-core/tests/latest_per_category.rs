@@ -0,0 +1,45 @@
+use chrono::{TimeZone, Utc};
+use timeline_core::{CriticalSummary, EventCategory, TimelineEvent, TimelineSnapshot};
+
+fn event(id: &str, category: EventCategory, hour: Option<u32>) -> TimelineEvent {
+    TimelineEvent {
+        occurred_at: hour.map(|hour| Utc.with_ymd_and_hms(2025, 10, 30, hour, 0, 0).unwrap()),
+        ..TimelineEvent::new(id, category, id)
+    }
+}
+
+#[test]
+fn latest_per_category_picks_newest_dated_event() {
+    let snapshot = TimelineSnapshot::new(
+        CriticalSummary::default(),
+        vec![
+            event("med-late", EventCategory::Medication, Some(11)),
+            event("obs-early", EventCategory::Observation, Some(7)),
+            event("med-undated", EventCategory::Medication, None),
+            event("obs-late", EventCategory::Observation, Some(10)),
+            event("med-early", EventCategory::Medication, Some(6)),
+            event("note-undated", EventCategory::Note, None),
+        ],
+    );
+
+    let latest = snapshot.latest_per_category();
+
+    assert_eq!(latest.len(), 3);
+    assert_eq!(latest[&EventCategory::Medication].id, "med-late");
+    assert_eq!(latest[&EventCategory::Observation].id, "obs-late");
+    assert_eq!(latest[&EventCategory::Note].id, "note-undated");
+}
+
+#[test]
+fn undated_event_does_not_win_when_events_are_unsorted() {
+    let mut snapshot = TimelineSnapshot::new(CriticalSummary::default(), Vec::new());
+    snapshot.events = vec![
+        event("med-dated", EventCategory::Medication, Some(9)),
+        event("med-undated", EventCategory::Medication, None),
+    ];
+
+    assert_eq!(
+        snapshot.latest_per_category()[&EventCategory::Medication].id,
+        "med-dated"
+    );
+}
@@ -0,0 +1,70 @@
+use timeline_core::notify::critical_payload;
+use timeline_core::{CriticalItem, CriticalSummary, Severity, TimelineSnapshot, VitalSnapshot};
+
+fn item(label: &str, severity: Severity) -> CriticalItem {
+    CriticalItem {
+        label: label.to_string(),
+        detail: None,
+        severity,
+        recorded_at: None,
+        reactions: Vec::new(),
+        watched: false,
+    }
+}
+
+fn vital(name: &str, value: &str, severity: Severity) -> VitalSnapshot {
+    VitalSnapshot {
+        name: name.to_string(),
+        value: value.to_string(),
+        recorded_at: None,
+        numeric_value: None,
+        unit: None,
+        severity,
+        watched: false,
+    }
+}
+
+#[test]
+fn critical_alert_yields_a_payload() {
+    let critical = CriticalSummary {
+        alerts: vec![item("Sepsis alert", Severity::Critical)],
+        medications: vec![
+            item("Medication: Heparin infusion", Severity::High),
+            item("Medication: Paracetamol", Severity::Info),
+        ],
+        recent_vitals: vec![
+            vital("SpO2", "86 %", Severity::Critical),
+            vital("Temperature", "37.1 Cel", Severity::Moderate),
+        ],
+        code_status: Some("Full code".to_string()),
+        ..CriticalSummary::default()
+    };
+    let snapshot = TimelineSnapshot::new(critical, Vec::new());
+
+    let payload = critical_payload(&snapshot).expect("payload for a critical alert");
+
+    assert_eq!(payload.highest_severity, Severity::Critical);
+    let labels: Vec<&str> = payload
+        .items
+        .iter()
+        .map(|entry| entry.label.as_str())
+        .collect();
+    assert_eq!(labels, vec!["Sepsis alert", "Medication: Heparin infusion"]);
+    assert_eq!(payload.vitals.len(), 1);
+    assert_eq!(payload.vitals[0].detail.as_deref(), Some("86 %"));
+    assert_eq!(payload.code_status.as_deref(), Some("Full code"));
+    assert!(serde_json::to_string(&payload).is_ok());
+}
+
+#[test]
+fn benign_snapshot_yields_none() {
+    let critical = CriticalSummary {
+        medications: vec![item("Medication: Paracetamol", Severity::Info)],
+        chronic_conditions: vec![item("Chronic condition: Asthma", Severity::Moderate)],
+        recent_vitals: vec![vital("Heart rate", "78 bpm", Severity::Moderate)],
+        ..CriticalSummary::default()
+    };
+    let snapshot = TimelineSnapshot::new(critical, Vec::new());
+
+    assert_eq!(critical_payload(&snapshot), None);
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, TimeZone, Utc};
+use timeline_core::{
+    CriticalGroup, CriticalItem, CriticalSummary, EventCategory, PriorityEntry, Severity,
+    TimelineEvent, TimelineSnapshot,
+};
+
+fn at(hour: u32) -> Option<DateTime<Utc>> {
+    Some(Utc.with_ymd_and_hms(2025, 10, 30, hour, 0, 0).unwrap())
+}
+
+fn item(label: &str, severity: Severity, recorded_at: Option<DateTime<Utc>>) -> CriticalItem {
+    CriticalItem {
+        label: label.to_string(),
+        detail: None,
+        severity,
+        recorded_at,
+        reactions: Vec::new(),
+        watched: false,
+    }
+}
+
+fn event(id: &str, severity: Severity, occurred_at: Option<DateTime<Utc>>) -> TimelineEvent {
+    TimelineEvent {
+        occurred_at,
+        severity,
+        ..TimelineEvent::new(id, EventCategory::Observation, id)
+    }
+}
+
+fn label(entry: &PriorityEntry) -> String {
+    match entry {
+        PriorityEntry::Event(event) => format!("event:{}", event.id),
+        PriorityEntry::Critical { item, .. } => format!("item:{}", item.label),
+    }
+}
+
+#[test]
+fn critical_alert_precedes_high_event_and_info_items_come_last() {
+    let critical = CriticalSummary {
+        alerts: vec![item("Sepsis alert", Severity::Critical, at(7))],
+        medications: vec![item("Paracetamol", Severity::Info, at(10))],
+        ..CriticalSummary::default()
+    };
+    let snapshot = TimelineSnapshot::new(
+        critical,
+        vec![
+            event("note", Severity::Info, at(11)),
+            event("lactate", Severity::High, at(8)),
+            event("hr", Severity::High, at(9)),
+            event("undated", Severity::Info, None),
+        ],
+    );
+
+    let order: Vec<String> = snapshot.priority_order().iter().map(label).collect();
+    assert_eq!(
+        order,
+        vec![
+            "item:Sepsis alert",
+            "event:hr",
+            "event:lactate",
+            "event:note",
+            "item:Paracetamol",
+            "event:undated",
+        ]
+    );
+
+    let first = snapshot.priority_order()[0];
+    assert!(matches!(
+        first,
+        PriorityEntry::Critical {
+            group: CriticalGroup::Alert,
+            ..
+        }
+    ));
+    assert_eq!(first.severity(), Severity::Critical);
+}
@@ -0,0 +1,138 @@
+use chrono::{TimeZone, Utc};
+use timeline_core::redaction::{redact_identifiers, REDACTED};
+use timeline_core::{
+    CriticalItem, CriticalSummary, EventCategory, PatientDemographics, ResourceReference, Severity,
+    TimelineEvent, TimelineSnapshot, VitalSnapshot,
+};
+
+fn event(id: &str, category: EventCategory, title: &str, reference: &str) -> TimelineEvent {
+    TimelineEvent {
+        occurred_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, 8, 0, 0).unwrap()),
+        severity: Severity::High,
+        source: Some(ResourceReference {
+            system: Some("FHIR".to_string()),
+            reference: Some(reference.to_string()),
+            display: Some("Dr. Tran Thi B".to_string()),
+        }),
+        ..TimelineEvent::new(id, category, title)
+    }
+}
+
+fn snapshot() -> TimelineSnapshot {
+    let critical = CriticalSummary {
+        alerts: vec![CriticalItem {
+            label: "Patient: Nguyen Van A".to_string(),
+            detail: Some("Age 67 | Male".to_string()),
+            severity: Severity::Info,
+            recorded_at: None,
+            reactions: Vec::new(),
+            watched: false,
+        }],
+        recent_vitals: vec![VitalSnapshot {
+            name: "Heart rate".to_string(),
+            value: "132 bpm".to_string(),
+            recorded_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, 8, 0, 0).unwrap()),
+            numeric_value: Some(132.0),
+            unit: Some("bpm".to_string()),
+            severity: Severity::High,
+            watched: false,
+        }],
+        ..CriticalSummary::default()
+    };
+    let mut snapshot = TimelineSnapshot::new(
+        critical,
+        vec![
+            event(
+                "doc-discharge",
+                EventCategory::Document,
+                "Discharge summary Nguyen Van A MRN:8812345",
+                "Practitioner/pr-1",
+            ),
+            event(
+                "obs-hr",
+                EventCategory::Observation,
+                "Heart rate",
+                "Observation/obs-hr",
+            ),
+        ],
+    );
+    snapshot.patient = Some(PatientDemographics {
+        name: Some("Nguyen Van A".to_string()),
+        age: Some(67),
+        gender: Some("male".to_string()),
+        birth_date: chrono::NaiveDate::from_ymd_opt(1958, 3, 14),
+        deceased: false,
+        deceased_at: None,
+    });
+    snapshot
+}
+
+#[test]
+fn redact_replaces_names_and_keeps_clinical_content() {
+    let original = snapshot();
+    let redacted = original.redact();
+
+    assert_eq!(
+        redacted.critical.alerts[0].label,
+        format!("Patient: {REDACTED}")
+    );
+    let patient = redacted.patient.as_ref().unwrap();
+    assert_eq!(patient.name.as_deref(), Some(REDACTED));
+    assert_eq!(patient.birth_date, None);
+    assert_eq!(patient.age, Some(67));
+
+    assert_eq!(
+        redacted.critical.recent_vitals,
+        original.critical.recent_vitals
+    );
+    let severities = |snapshot: &TimelineSnapshot| {
+        snapshot
+            .events
+            .iter()
+            .map(|event| event.severity)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(severities(&redacted), severities(&original));
+
+    let document = &redacted.events[0];
+    assert_eq!(document.title, "Discharge summary [REDACTED] [ID]");
+    assert_eq!(
+        document.source.as_ref().unwrap().display.as_deref(),
+        Some(REDACTED)
+    );
+    let observation = &redacted.events[1];
+    assert_eq!(
+        observation.source.as_ref().unwrap().display.as_deref(),
+        Some("Dr. Tran Thi B"),
+        "clinical references keep their display"
+    );
+}
+
+#[test]
+fn identifier_rules_spare_dates_and_short_numbers() {
+    assert_eq!(
+        redact_identifiers("Report 2025-10-30 room 12 acct 00123456"),
+        "Report 2025-10-30 room 12 acct [ID]"
+    );
+}
+
+#[test]
+fn mrn_prefix_needs_a_separator_or_digits() {
+    assert_eq!(
+        redact_identifiers("mRNA vaccine given, MRN:8812 and mrn-77 and MRN4411"),
+        "mRNA vaccine given, [ID] and [ID] and [ID]"
+    );
+}
+
+#[test]
+fn notes_lose_the_patient_name_but_keep_clinical_numbers() {
+    let mut original = snapshot();
+    original.events[1].notes = vec!["Nguyen Van A agitated; Platelets 150000".to_string()];
+
+    let redacted = original.redact();
+
+    assert_eq!(
+        redacted.events[1].notes,
+        vec![format!("{REDACTED} agitated; Platelets 150000")]
+    );
+}
@@ -0,0 +1,22 @@
+use timeline_core::{empty_snapshot, CriticalSummary, TimelineSnapshot, SNAPSHOT_SCHEMA_VERSION};
+
+#[test]
+fn new_snapshots_carry_current_schema_version() {
+    let snapshot = TimelineSnapshot::new(CriticalSummary::default(), Vec::new());
+    assert_eq!(snapshot.schema_version, SNAPSHOT_SCHEMA_VERSION);
+    assert_eq!(empty_snapshot().schema_version, SNAPSHOT_SCHEMA_VERSION);
+
+    let json = serde_json::to_value(&snapshot).expect("serialize snapshot");
+    assert_eq!(json["schema_version"], SNAPSHOT_SCHEMA_VERSION);
+}
+
+#[test]
+fn legacy_payload_defaults_to_version_zero() {
+    let mut legacy = serde_json::to_value(empty_snapshot()).expect("serialize snapshot");
+    legacy
+        .as_object_mut()
+        .expect("snapshot object")
+        .remove("schema_version");
+    let snapshot: TimelineSnapshot = serde_json::from_value(legacy).expect("legacy snapshot");
+    assert_eq!(snapshot.schema_version, 0);
+}
@@ -0,0 +1,39 @@
+use timeline_core::Severity;
+
+#[test]
+fn most_and_least_severe_over_mixed_iterator() {
+    let mixed = [
+        Severity::Low,
+        Severity::High,
+        Severity::Info,
+        Severity::Moderate,
+    ];
+
+    assert_eq!(Severity::most_severe(mixed), Some(Severity::High));
+    assert_eq!(Severity::least_severe(mixed), Some(Severity::Info));
+}
+
+#[test]
+fn critical_wins_regardless_of_position() {
+    let severities = vec![Severity::Moderate, Severity::Moderate, Severity::Critical];
+
+    assert_eq!(
+        Severity::most_severe(severities.iter().copied()),
+        Some(Severity::Critical)
+    );
+    assert_eq!(Severity::least_severe(severities), Some(Severity::Moderate));
+}
+
+#[test]
+fn empty_iterator_has_no_severity() {
+    assert_eq!(Severity::most_severe(Vec::new()), None);
+    assert_eq!(Severity::least_severe(std::iter::empty()), None);
+}
+
+#[test]
+fn escalate_steps_one_level_and_caps_at_critical() {
+    assert_eq!(Severity::Info.escalate(), Severity::Low);
+    assert_eq!(Severity::Moderate.escalate(), Severity::High);
+    assert_eq!(Severity::High.escalate(), Severity::Critical);
+    assert_eq!(Severity::Critical.escalate(), Severity::Critical);
+}
@@ -0,0 +1,38 @@
+use timeline_core::Severity;
+
+const ALL: [Severity; 5] = [
+    Severity::Critical,
+    Severity::High,
+    Severity::Moderate,
+    Severity::Low,
+    Severity::Info,
+];
+
+#[test]
+fn level_strings_are_stable() {
+    let levels: Vec<&str> = ALL.iter().map(|severity| severity.level_str()).collect();
+    assert_eq!(levels, vec!["critical", "high", "moderate", "low", "info"]);
+}
+
+#[test]
+fn level_strings_match_serde_names() {
+    for severity in ALL {
+        let serialized = serde_json::to_value(severity).expect("serialize severity");
+        assert_eq!(serialized, severity.level_str());
+    }
+}
+
+#[test]
+fn visual_tokens_are_distinct_per_variant() {
+    for (index, severity) in ALL.iter().enumerate() {
+        assert_eq!(
+            severity.color_token(),
+            format!("--timeline-severity-{}", severity.level_str())
+        );
+        assert!(severity.color_hex().starts_with('#'));
+        for other in &ALL[index + 1..] {
+            assert_ne!(severity.color_hex(), other.color_hex());
+            assert_ne!(severity.icon(), other.icon());
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use chrono::{TimeZone, Utc};
+use timeline_core::{
+    CriticalItem, CriticalSummary, EventCategory, Severity, TimelineEvent, TimelineSnapshot,
+};
+
+fn event(id: &str, hour: u32) -> TimelineEvent {
+    TimelineEvent {
+        occurred_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, hour, 0, 0).unwrap()),
+        ..TimelineEvent::new(id, EventCategory::Note, id)
+    }
+}
+
+fn stale_snapshot() -> TimelineSnapshot {
+    let mut snapshot = TimelineSnapshot::new(CriticalSummary::default(), vec![event("note-1", 8)]);
+    snapshot.generated_at = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    snapshot
+}
+
+#[test]
+fn with_critical_replaces_summary_and_refreshes_timestamp() {
+    let edited = CriticalSummary::builder()
+        .allergy(CriticalItem {
+            label: "Penicillin".to_string(),
+            detail: Some("anaphylaxis".to_string()),
+            severity: Severity::Critical,
+            recorded_at: None,
+            reactions: Vec::new(),
+            watched: false,
+        })
+        .build();
+
+    let snapshot = stale_snapshot().with_critical(edited.clone());
+
+    assert_eq!(snapshot.critical, edited);
+    assert_eq!(snapshot.events.len(), 1, "events are left untouched");
+    assert!(snapshot.generated_at > Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap());
+}
+
+#[test]
+fn with_events_resorts_and_refreshes_timestamp() {
+    let snapshot =
+        stale_snapshot().with_events(vec![event("note-late", 11), event("note-early", 7)]);
+
+    let ids: Vec<&str> = snapshot
+        .events
+        .iter()
+        .map(|event| event.id.as_str())
+        .collect();
+    assert_eq!(ids, ["note-early", "note-late"]);
+    assert!(snapshot.generated_at > Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap());
+}
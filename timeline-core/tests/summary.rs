@@ -0,0 +1,91 @@
+use timeline_core::{
+    CriticalItem, CriticalSummary, EventCategory, Severity, SeverityCounts, TimelineEvent,
+    TimelineSnapshot,
+};
+
+fn event(id: &str, severity: Severity) -> TimelineEvent {
+    TimelineEvent {
+        severity,
+        ..TimelineEvent::new(id, EventCategory::Observation, id)
+    }
+}
+
+fn allergy(label: &str) -> CriticalItem {
+    CriticalItem {
+        label: label.to_string(),
+        detail: None,
+        severity: Severity::High,
+        recorded_at: None,
+        reactions: Vec::new(),
+        watched: false,
+    }
+}
+
+#[test]
+fn summary_reflects_counts() {
+    let critical = CriticalSummary::builder()
+        .allergy(allergy("Allergy: Penicillin"))
+        .allergy(allergy("Allergy: Latex"))
+        .code_status("DNR")
+        .build();
+    let snapshot = TimelineSnapshot::new(
+        critical,
+        vec![
+            event("obs-lactate", Severity::Critical),
+            event("obs-map", Severity::Critical),
+            event("obs-hr", Severity::High),
+        ],
+    );
+
+    assert_eq!(
+        snapshot.severity_counts(),
+        SeverityCounts {
+            total: 3,
+            critical: 2,
+            high: 1,
+            ..SeverityCounts::default()
+        }
+    );
+    assert_eq!(
+        snapshot.summary_line(),
+        "3 events, 2 critical, code status: DNR, 2 allergies"
+    );
+
+    let report = snapshot.summary_report();
+    assert!(report.contains("Timeline events: 3 (critical 2, high 1, moderate 0, low 0, info 0)"));
+    assert!(report.contains("Code status: DNR"));
+    assert!(report.contains("Allergies: 2"));
+}
+
+#[test]
+fn summary_handles_empty_snapshot() {
+    let snapshot = TimelineSnapshot::new(CriticalSummary::default(), Vec::new());
+
+    assert_eq!(
+        snapshot.summary_line(),
+        "0 events, 0 critical, code status: not recorded, 0 allergies"
+    );
+    assert_eq!(snapshot.summary_report().lines().count(), 7);
+}
+
+#[test]
+fn category_summary_matches_event_list() {
+    let mut note = event("note-1", Severity::Info);
+    note.category = EventCategory::Note;
+    let snapshot = TimelineSnapshot::new(
+        CriticalSummary::default(),
+        vec![
+            event("obs-hr", Severity::High),
+            note,
+            event("obs-rr", Severity::Moderate),
+        ],
+    );
+
+    assert_eq!(snapshot.category_summary.len(), 2);
+    assert_eq!(snapshot.category_summary[&EventCategory::Observation], 2);
+    assert_eq!(snapshot.category_summary[&EventCategory::Note], 1);
+    assert_eq!(snapshot.category_counts(), snapshot.category_summary);
+
+    let json = serde_json::to_value(&snapshot).expect("snapshot serializes");
+    assert_eq!(json["category_summary"]["Observation"], 2);
+}
@@ -0,0 +1,55 @@
+use chrono::{Duration, TimeZone, Utc};
+use timeline_core::{RollupAggregation, VitalTrend, VitalTrendPoint};
+
+fn point(hour: u32, minute: u32, value: f64) -> VitalTrendPoint {
+    VitalTrendPoint {
+        recorded_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, hour, minute, 0).unwrap()),
+        value: Some(value),
+        label: None,
+    }
+}
+
+fn temperature_trend() -> VitalTrend {
+    VitalTrend {
+        name: "Temperature".to_string(),
+        unit: Some("C".to_string()),
+        points: vec![
+            point(10, 5, 37.0),
+            point(10, 20, 38.0),
+            point(10, 55, 39.0),
+            point(12, 10, 37.5),
+            VitalTrendPoint {
+                recorded_at: None,
+                value: Some(40.0),
+                label: None,
+            },
+        ],
+    }
+}
+
+#[test]
+fn hourly_rollup_averages_points_in_each_window() {
+    let rolled = temperature_trend().rollup(Duration::hours(1), RollupAggregation::Mean);
+
+    assert_eq!(rolled.name, "Temperature");
+    assert_eq!(rolled.unit.as_deref(), Some("C"));
+    assert_eq!(rolled.points.len(), 2);
+
+    let first = &rolled.points[0];
+    assert_eq!(
+        first.recorded_at,
+        Some(Utc.with_ymd_and_hms(2025, 10, 30, 10, 0, 0).unwrap())
+    );
+    assert!((first.value.unwrap() - 38.0).abs() < 1e-9);
+    assert!((rolled.points[1].value.unwrap() - 37.5).abs() < 1e-9);
+}
+
+#[test]
+fn rollup_supports_min_and_max() {
+    let trend = temperature_trend();
+    let min = trend.rollup(Duration::hours(1), RollupAggregation::Min);
+    let max = trend.rollup(Duration::hours(1), RollupAggregation::Max);
+
+    assert_eq!(min.points[0].value, Some(37.0));
+    assert_eq!(max.points[0].value, Some(39.0));
+}
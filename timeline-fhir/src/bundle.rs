@@ -0,0 +1,257 @@
+//! Export a `TimelineSnapshot` back to FHIR as a normalized `collection` Bundle.
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use timeline_core::{
+    DatePrecision, EventCategory, ObservationCategory, PatientDemographics, TimelineEvent,
+    TimelineSnapshot,
+};
+
+const PATIENT_ID: &str = "patient";
+
+/// Build a `collection` Bundle with a `Patient` followed by one minimal
+/// resource per timeline event.
+///
+/// The `Patient` carries the snapshot demographics, or only an id when there
+/// are none, and every resource references it. Resources keep the type and id
+/// of their source reference (falling back to the event category and id; types
+/// without a mapping below become `Basic`), the event title as `code.text`, the
+/// timestamp at its original precision, the detail as a note (as `valueString`
+/// for observations) and any source notes after it. Severity, tags and anything
+/// the converter did not keep are lost. Entry `fullUrl`s are `urn:uuid:` URIs
+/// derived from `{resourceType}/{id}`, so repeated exports agree.
+pub fn snapshot_to_bundle(snapshot: &TimelineSnapshot) -> Value {
+    let subject = json!({ "reference": full_url("Patient", PATIENT_ID) });
+    let patient = snapshot.patient.as_ref().map_or_else(
+        || patient_resource(&PatientDemographics::default()),
+        patient_resource,
+    );
+    let mut entries = vec![entry(patient)];
+    entries.extend(
+        snapshot
+            .events
+            .iter()
+            .map(|event| entry(event_resource(event, &subject))),
+    );
+
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "timestamp": snapshot.generated_at.to_rfc3339(),
+        "entry": entries
+    })
+}
+
+fn entry(resource: Value) -> Value {
+    let full_url = full_url(
+        resource["resourceType"].as_str().unwrap_or_default(),
+        resource["id"].as_str().unwrap_or_default(),
+    );
+    json!({ "fullUrl": full_url, "resource": resource })
+}
+
+/// `urn:uuid:` URI named by `{resource_type}/{id}`.
+///
+/// The 128 bits are two FNV-1a hashes of the name (stable across builds and
+/// platforms, unlike `DefaultHasher`), marked as a version 8 UUID.
+fn full_url(resource_type: &str, id: &str) -> String {
+    let name = format!("{resource_type}/{id}");
+    let fnv1a = |offset: u64| {
+        name.bytes().fold(offset, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+    };
+    let high = (fnv1a(0xcbf2_9ce4_8422_2325) & !0xf000) | 0x8000;
+    let low = (fnv1a(0x8422_2325_cbf2_9ce4) & !(0b11 << 62)) | (0b10 << 62);
+    format!(
+        "urn:uuid:{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    )
+}
+
+fn patient_resource(patient: &PatientDemographics) -> Value {
+    let mut resource = json!({ "resourceType": "Patient", "id": PATIENT_ID });
+    if let Some(name) = patient.name.as_deref() {
+        resource["name"] = json!([{ "text": name }]);
+    }
+    if let Some(gender) = patient.gender.as_deref() {
+        resource["gender"] = json!(gender.to_lowercase());
+    }
+    if let Some(birth_date) = patient.birth_date {
+        resource["birthDate"] = json!(birth_date.to_string());
+    }
+    if let Some(deceased_at) = patient.deceased_at {
+        resource["deceasedDateTime"] = json!(deceased_at.to_rfc3339());
+    } else if patient.deceased {
+        resource["deceasedBoolean"] = json!(true);
+    }
+    resource
+}
+
+fn event_resource(event: &TimelineEvent, subject: &Value) -> Value {
+    let (resource_type, id) = source_type_and_id(event);
+    let code = json!({
+        "text": event
+            .source
+            .as_ref()
+            .and_then(|source| source.display.as_deref())
+            .unwrap_or(&event.title)
+    });
+    let mut resource = json!({ "resourceType": resource_type, "id": id });
+
+    let date_field = match resource_type {
+        "Observation" => {
+            resource["status"] = json!("final");
+            resource["code"] = code;
+            if let Some(category) = event.observation_category {
+                resource["category"] = json!([{
+                    "coding": [{
+                        "system": "http://terminology.hl7.org/CodeSystem/observation-category",
+                        "code": observation_category_code(category)
+                    }]
+                }]);
+            }
+            "effective"
+        }
+        "MedicationStatement" | "MedicationRequest" => {
+            resource["status"] = json!("unknown");
+            resource["medicationCodeableConcept"] = code;
+            if resource_type == "MedicationRequest" {
+                resource["intent"] = json!("order");
+                "authoredOn"
+            } else {
+                "effective"
+            }
+        }
+        "Procedure" => {
+            resource["status"] = json!("completed");
+            resource["code"] = code;
+            "performed"
+        }
+        "Encounter" => {
+            resource["status"] = json!("finished");
+            resource["type"] = json!([code]);
+            "period"
+        }
+        "DocumentReference" | "Composition" => {
+            resource["status"] = json!(if resource_type == "Composition" {
+                "final"
+            } else {
+                "current"
+            });
+            resource["type"] = code;
+            resource["description"] = json!(event.title);
+            "date"
+        }
+        "AllergyIntolerance" | "Condition" => {
+            resource["code"] = code;
+            "recordedDate"
+        }
+        _ => {
+            // Other types (e.g. Coverage) need elements an event cannot supply.
+            resource["resourceType"] = json!("Basic");
+            resource["code"] = code;
+            "created"
+        }
+    };
+    set_date(&mut resource, date_field, event);
+
+    let subject_field = if resource_type == "AllergyIntolerance" {
+        "patient"
+    } else {
+        "subject"
+    };
+    resource[subject_field] = subject.clone();
+    if let Some(system) = event
+        .source
+        .as_ref()
+        .and_then(|source| source.system.as_deref())
+    {
+        resource["meta"] = json!({ "source": system });
+    }
+    let mut notes: Vec<Value> = Vec::new();
+    if let Some(detail) = event.detail.as_deref() {
+        // Observation details are the rendered value, which is what re-ingestion needs.
+        if resource_type == "Observation" {
+            resource["valueString"] = json!(detail);
+        } else {
+            notes.push(json!({ "text": detail }));
+        }
+    }
+    notes.extend(event.notes.iter().map(|note| json!({ "text": note })));
+    if !notes.is_empty() {
+        resource["note"] = Value::Array(notes);
+    }
+    resource
+}
+
+/// Type and id from the event's `{type}/{id}` source reference, or derived from
+/// the event itself.
+fn source_type_and_id(event: &TimelineEvent) -> (&str, &str) {
+    event
+        .source
+        .as_ref()
+        .and_then(|source| source.reference.as_deref())
+        .and_then(|reference| reference.split_once('/'))
+        .unwrap_or_else(|| (category_resource_type(event.category), event.id.as_str()))
+}
+
+fn category_resource_type(category: EventCategory) -> &'static str {
+    match category {
+        EventCategory::Encounter => "Encounter",
+        EventCategory::Procedure => "Procedure",
+        EventCategory::Condition => "Condition",
+        EventCategory::Medication => "MedicationStatement",
+        EventCategory::Observation => "Observation",
+        EventCategory::Document | EventCategory::Note => "DocumentReference",
+        EventCategory::Other => "Basic",
+    }
+}
+
+fn observation_category_code(category: ObservationCategory) -> &'static str {
+    match category {
+        ObservationCategory::VitalSigns => "vital-signs",
+        ObservationCategory::Laboratory => "laboratory",
+        ObservationCategory::Imaging => "imaging",
+        ObservationCategory::Survey => "survey",
+    }
+}
+
+/// Write the event time to `field`: a `{field}Period` when the event has an end,
+/// `{field}DateTime` for choice types, or `field` itself for plain dates.
+fn set_date(resource: &mut Value, field: &str, event: &TimelineEvent) {
+    let Some(start) = event.occurred_at else {
+        return;
+    };
+    let start_text = format_date(start, event.occurred_at_precision);
+    match field {
+        "effective" | "performed" => match event.period_end {
+            Some(end) => {
+                resource[format!("{field}Period")] =
+                    json!({ "start": start_text, "end": end.to_rfc3339() });
+            }
+            None => resource[format!("{field}DateTime")] = json!(start_text),
+        },
+        "period" => {
+            resource["period"] = match event.period_end {
+                Some(end) => json!({ "start": start_text, "end": end.to_rfc3339() }),
+                None => json!({ "start": start_text }),
+            };
+        }
+        _ => resource[field] = json!(start_text),
+    }
+}
+
+/// FHIR date or dateTime at `precision`, so partial dates stay partial.
+fn format_date(timestamp: DateTime<Utc>, precision: DatePrecision) -> String {
+    match precision {
+        DatePrecision::Year => timestamp.format("%Y").to_string(),
+        DatePrecision::Month => timestamp.format("%Y-%m").to_string(),
+        DatePrecision::Day => timestamp.format("%Y-%m-%d").to_string(),
+        DatePrecision::DateTime => timestamp.to_rfc3339(),
+    }
+}
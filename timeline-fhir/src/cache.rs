@@ -0,0 +1,102 @@
+//! In-memory LRU cache of snapshots for services that re-summarize unchanged
+//! bundles (enabled with the `cache` feature).
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+
+use timeline_core::{TimelineConfig, TimelineError, TimelineSnapshot};
+
+use crate::summarize_bundle_str;
+
+/// Least-recently-used cache of snapshots keyed by bundle bytes and config.
+///
+/// Entries keep the bundle text and serialized config next to the hash, so a
+/// hash collision can never return another bundle's snapshot.
+#[derive(Debug)]
+pub struct SnapshotCache {
+    capacity: usize,
+    /// Most recently used first.
+    entries: VecDeque<CacheEntry>,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    hash: u64,
+    bundle: String,
+    config: String,
+    snapshot: TimelineSnapshot,
+}
+
+impl SnapshotCache {
+    /// Cache holding at most `capacity` snapshots (at least one).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Calls answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Calls that had to summarize the bundle.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Like [`summarize_bundle_str`], but returns a clone of the cached snapshot
+/// when the same bundle text was summarized with an equal config. Errors are
+/// not cached.
+pub fn summarize_bundle_cached(
+    cache: &mut SnapshotCache,
+    bundle_json: &str,
+    config: &TimelineConfig,
+) -> Result<TimelineSnapshot, TimelineError> {
+    let config_key =
+        serde_json::to_string(config).map_err(|err| TimelineError::Parse(err.to_string()))?;
+    let mut hasher = DefaultHasher::new();
+    bundle_json.hash(&mut hasher);
+    config_key.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    let position = cache.entries.iter().position(|entry| {
+        entry.hash == hash && entry.bundle == bundle_json && entry.config == config_key
+    });
+    if let Some(entry) = position.and_then(|index| cache.entries.remove(index)) {
+        cache.hits += 1;
+        let snapshot = entry.snapshot.clone();
+        cache.entries.push_front(entry);
+        return Ok(snapshot);
+    }
+
+    cache.misses += 1;
+    let snapshot = summarize_bundle_str(bundle_json, config)?;
+    cache.entries.push_front(CacheEntry {
+        hash,
+        bundle: bundle_json.to_string(),
+        config: config_key,
+        snapshot: snapshot.clone(),
+    });
+    cache.entries.truncate(cache.capacity);
+    Ok(snapshot)
+}
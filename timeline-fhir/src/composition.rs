@@ -0,0 +1,178 @@
+//! Export a `TimelineSnapshot` back to FHIR as a clinical summary `Composition`.
+
+use serde_json::{json, Value};
+use timeline_core::{CriticalItem, ObservationCategory, TimelineEvent, TimelineSnapshot};
+
+const LOINC: &str = "http://loinc.org";
+
+/// Build a `Composition` (clinical summary document) from `snapshot`.
+///
+/// Sections cover allergies, medications, problems and vitals; each lists the
+/// snapshot items as narrative and references the source resources of the
+/// matching timeline events. The subject is a display-only placeholder because
+/// snapshots do not keep the Patient reference.
+pub fn to_composition(snapshot: &TimelineSnapshot) -> Value {
+    let critical = &snapshot.critical;
+    let subject = snapshot
+        .patient
+        .as_ref()
+        .and_then(|patient| patient.name.clone())
+        .unwrap_or_else(|| "Unknown patient".to_string());
+
+    let vital_lines: Vec<String> = critical
+        .recent_vitals
+        .iter()
+        .map(|vital| format!("{}: {}", vital.name, vital.value))
+        .collect();
+    let vital_names: Vec<String> = critical
+        .recent_vitals
+        .iter()
+        .map(|vital| vital.name.to_lowercase())
+        .collect();
+
+    let sections = vec![
+        section(
+            "Allergies",
+            "48765-2",
+            "Allergies and adverse reactions Document",
+            &item_lines(&critical.allergies),
+            event_references(&snapshot.events, |event| {
+                source_type(event) == Some("AllergyIntolerance")
+            }),
+        ),
+        section(
+            "Medications",
+            "10160-0",
+            "History of Medication use Narrative",
+            &item_lines(&critical.medications),
+            event_references(&snapshot.events, |event| {
+                matches!(
+                    source_type(event),
+                    Some("MedicationStatement" | "MedicationRequest")
+                )
+            }),
+        ),
+        section(
+            "Problems",
+            "11450-4",
+            "Problem list - Reported",
+            &item_lines(&critical.chronic_conditions),
+            event_references(&snapshot.events, |event| {
+                source_type(event) == Some("Condition")
+            }),
+        ),
+        section(
+            "Vitals",
+            "8716-3",
+            "Vital signs",
+            &vital_lines,
+            event_references(&snapshot.events, |event| {
+                source_type(event) == Some("Observation")
+                    && (event.observation_category == Some(ObservationCategory::VitalSigns)
+                        || vital_names
+                            .iter()
+                            .any(|name| event.title.to_lowercase().contains(name.as_str())))
+            }),
+        ),
+    ];
+
+    json!({
+        "resourceType": "Composition",
+        "status": "final",
+        "type": {
+            "coding": [{
+                "system": LOINC,
+                "code": "60591-5",
+                "display": "Patient summary Document"
+            }]
+        },
+        "subject": { "display": subject },
+        "date": snapshot.generated_at.to_rfc3339(),
+        "author": [{ "display": "seds_timeline" }],
+        "title": "Emergency timeline summary",
+        "section": sections
+    })
+}
+
+fn section(title: &str, code: &str, display: &str, lines: &[String], entries: Vec<Value>) -> Value {
+    let mut section = json!({
+        "title": title,
+        "code": {
+            "coding": [{ "system": LOINC, "code": code, "display": display }]
+        },
+        "text": {
+            "status": "generated",
+            "div": narrative(lines)
+        }
+    });
+
+    // Items whose events were filtered out still fill the narrative, so the
+    // section is only empty when it has neither.
+    if lines.is_empty() && entries.is_empty() {
+        section["emptyReason"] = json!({
+            "coding": [{
+                "system": "http://terminology.hl7.org/CodeSystem/list-empty-reason",
+                "code": "unavailable"
+            }]
+        });
+    } else if !entries.is_empty() {
+        section["entry"] = Value::Array(entries);
+    }
+    section
+}
+
+fn item_lines(items: &[CriticalItem]) -> Vec<String> {
+    items
+        .iter()
+        .map(|item| match item.detail.as_deref() {
+            Some(detail) => format!("{} ({detail})", item.label),
+            None => item.label.clone(),
+        })
+        .collect()
+}
+
+fn narrative(lines: &[String]) -> String {
+    let body = if lines.is_empty() {
+        "<p>No information available.</p>".to_string()
+    } else {
+        let items: String = lines
+            .iter()
+            .map(|line| format!("<li>{}</li>", escape_xhtml(line)))
+            .collect();
+        format!("<ul>{items}</ul>")
+    };
+    format!("<div xmlns=\"http://www.w3.org/1999/xhtml\">{body}</div>")
+}
+
+fn escape_xhtml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn source_type(event: &TimelineEvent) -> Option<&str> {
+    let reference = event.source.as_ref()?.reference.as_deref()?;
+    reference
+        .split_once('/')
+        .map(|(resource_type, _)| resource_type)
+}
+
+fn event_references(
+    events: &[TimelineEvent],
+    predicate: impl Fn(&TimelineEvent) -> bool,
+) -> Vec<Value> {
+    events
+        .iter()
+        .filter(|event| predicate(event))
+        .filter_map(|event| {
+            let source = event.source.as_ref()?;
+            let reference = source.reference.as_deref()?;
+            let mut entry = json!({ "reference": reference });
+            if let Some(display) = source.display.as_deref() {
+                entry["display"] = json!(display);
+            }
+            Some(entry)
+        })
+        .collect()
+}
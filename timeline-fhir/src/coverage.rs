@@ -0,0 +1,59 @@
+//! Per-resource-type coverage of a conversion, for onboarding new data sources.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use timeline_core::TimelineConfig;
+
+use crate::{summarize_bundle_inner, HandlerRegistry};
+
+/// How many resources of one type a bundle held and how many of them
+/// contributed to the snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResourceCoverage {
+    pub present: usize,
+    /// Resources that added an event, a critical item, patient demographics or
+    /// a `List` ordering.
+    pub contributing: usize,
+}
+
+/// Coverage by `resourceType`, in alphabetical order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CoverageReport {
+    pub by_type: BTreeMap<String, ResourceCoverage>,
+}
+
+impl CoverageReport {
+    pub(crate) fn record(&mut self, resource_type: &str, contributed: bool) {
+        let coverage = self.by_type.entry(resource_type.to_string()).or_default();
+        coverage.present += 1;
+        if contributed {
+            coverage.contributing += 1;
+        }
+    }
+
+    /// Resource types none of whose resources contributed anything.
+    pub fn unused_types(&self) -> Vec<&str> {
+        self.by_type
+            .iter()
+            .filter(|(_, coverage)| coverage.contributing == 0)
+            .map(|(resource_type, _)| resource_type.as_str())
+            .collect()
+    }
+}
+
+/// Convert `bundle` with the default configuration and report, per resource
+/// type, how many resources were present and how many contributed. Resources
+/// dropped by recency windows count as present only; input that is not a
+/// Bundle yields an empty report.
+pub fn coverage_report(bundle: &Value) -> CoverageReport {
+    let mut report = CoverageReport::default();
+    let _ = summarize_bundle_inner(
+        bundle,
+        &TimelineConfig::default(),
+        None,
+        &HandlerRegistry::default(),
+        Some(&mut report),
+    );
+    report
+}
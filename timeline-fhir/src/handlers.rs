@@ -0,0 +1,161 @@
+//! Pluggable conversion of resource types the converter does not know.
+//!
+//! Built-in resource types are always converted by the built-in handlers;
+//! handlers registered in a [`HandlerRegistry`] are consulted for every other
+//! type, before the `capture_unknown_resources` fallback.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use timeline_core::{CriticalItem, TimelineConfig, TimelineEvent};
+
+use crate::AggregateData;
+
+/// Converts one resource into timeline contributions.
+pub trait ResourceHandler {
+    fn handle(&self, resource: &Value, ctx: &mut ConversionContext);
+}
+
+impl<F: Fn(&Value, &mut ConversionContext)> ResourceHandler for F {
+    fn handle(&self, resource: &Value, ctx: &mut ConversionContext) {
+        self(resource, ctx)
+    }
+}
+
+/// What a handler may contribute to the snapshot under construction.
+pub struct ConversionContext<'a, 'cfg> {
+    pub(crate) aggregate: &'a mut AggregateData<'cfg>,
+    pub(crate) config: &'cfg TimelineConfig,
+}
+
+impl ConversionContext<'_, '_> {
+    pub fn config(&self) -> &TimelineConfig {
+        self.config
+    }
+
+    /// Instant recency windows are measured against (latest plausible timestamp
+    /// of the bundle, or `anchor_override`).
+    pub fn anchor(&self) -> Option<DateTime<Utc>> {
+        self.aggregate.anchor
+    }
+
+    /// Add a timeline event; tag rules, the event sink and the future-event
+    /// cutoff apply as for built-in events.
+    pub fn push_event(&mut self, event: TimelineEvent) {
+        self.aggregate.push_event(event);
+    }
+
+    /// Add an item to the clinical alerts card.
+    pub fn push_alert(&mut self, item: CriticalItem) {
+        self.aggregate.alerts.push(item);
+    }
+
+    /// Add a snapshot warning.
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.aggregate.warnings.push(message.into());
+    }
+}
+
+/// Handlers by `resourceType`.
+#[derive(Default)]
+pub struct HandlerRegistry {
+    custom: Vec<(String, Box<dyn ResourceHandler>)>,
+}
+
+impl HandlerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert resources of `resource_type` with `handler`. Built-in types keep
+    /// their built-in handler; a later registration for the same type replaces
+    /// an earlier one.
+    pub fn register(
+        &mut self,
+        resource_type: impl Into<String>,
+        handler: impl ResourceHandler + 'static,
+    ) -> &mut Self {
+        let resource_type = resource_type.into();
+        self.custom
+            .retain(|(registered, _)| *registered != resource_type);
+        self.custom.push((resource_type, Box::new(handler)));
+        self
+    }
+
+    /// `true` when resources of `resource_type` are converted (built in or registered).
+    pub fn handles(&self, resource_type: &str) -> bool {
+        self.find(resource_type).is_some()
+    }
+
+    pub(crate) fn find(&self, resource_type: &str) -> Option<&dyn ResourceHandler> {
+        BUILTIN_HANDLERS
+            .iter()
+            .find(|(builtin, _)| *builtin == resource_type)
+            .map(|(_, handler)| handler as &dyn ResourceHandler)
+            .or_else(|| {
+                self.custom
+                    .iter()
+                    .find(|(registered, _)| registered == resource_type)
+                    .map(|(_, handler)| handler.as_ref())
+            })
+    }
+}
+
+struct Builtin(fn(&Value, &mut ConversionContext));
+
+impl ResourceHandler for Builtin {
+    fn handle(&self, resource: &Value, ctx: &mut ConversionContext) {
+        (self.0)(resource, ctx)
+    }
+}
+
+/// Default registrations, consulted before any registered handler.
+const BUILTIN_HANDLERS: &[(&str, Builtin)] = &[
+    (
+        "Patient",
+        Builtin(|resource, ctx| ctx.aggregate.handle_patient(resource)),
+    ),
+    (
+        "AllergyIntolerance",
+        Builtin(|resource, ctx| ctx.aggregate.handle_allergy(resource)),
+    ),
+    (
+        "MedicationStatement",
+        Builtin(|resource, ctx| ctx.aggregate.handle_medication(resource, ctx.config)),
+    ),
+    (
+        "MedicationRequest",
+        Builtin(|resource, ctx| ctx.aggregate.handle_medication(resource, ctx.config)),
+    ),
+    (
+        "Condition",
+        Builtin(|resource, ctx| ctx.aggregate.handle_condition(resource, ctx.config)),
+    ),
+    (
+        "Observation",
+        Builtin(|resource, ctx| ctx.aggregate.handle_observation(resource, ctx.config)),
+    ),
+    (
+        "Procedure",
+        Builtin(|resource, ctx| ctx.aggregate.handle_procedure(resource, ctx.config)),
+    ),
+    (
+        "Encounter",
+        Builtin(|resource, ctx| ctx.aggregate.handle_encounter(resource)),
+    ),
+    (
+        "DocumentReference",
+        Builtin(|resource, ctx| ctx.aggregate.handle_document(resource)),
+    ),
+    (
+        "Composition",
+        Builtin(|resource, ctx| ctx.aggregate.handle_document(resource)),
+    ),
+    (
+        "List",
+        Builtin(|resource, ctx| ctx.aggregate.handle_list(resource)),
+    ),
+    (
+        "Coverage",
+        Builtin(|resource, ctx| ctx.aggregate.handle_coverage(resource)),
+    ),
+];
@@ -1,22 +1,45 @@
 //! FHIR JSON to `TimelineSnapshot` converter with extended analytics.
 
-use std::collections::{hash_map::Entry, HashMap};
-
-use chrono::{DateTime, Datelike, NaiveDate, Utc};
+mod bundle;
+#[cfg(feature = "cache")]
+mod cache;
+mod composition;
+mod coverage;
+mod handlers;
+
+pub use bundle::snapshot_to_bundle;
+#[cfg(feature = "cache")]
+pub use cache::{summarize_bundle_cached, SnapshotCache};
+pub use composition::to_composition;
+pub use coverage::{coverage_report, CoverageReport, ResourceCoverage};
+pub use handlers::{ConversionContext, HandlerRegistry, ResourceHandler};
+
+use std::cmp::Reverse;
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 use serde_json::Value;
+use timeline_core::vitals::{classify_blood_pressure, classify_gcs, classify_vital};
 use timeline_core::{
-    CriticalItem, CriticalSummary, DiagnosticKind, DiagnosticSnapshot, EventCategory,
-    ResourceReference, Severity, TimelineConfig, TimelineError, TimelineEvent, TimelineSnapshot,
-    VitalSnapshot, VitalTrend, VitalTrendPoint,
+    AllergyReaction, BloodPressurePoint, BloodPressureTrend, CriticalItem, CriticalItemOrder,
+    CriticalSummary, DatePrecision, DiagnosticKind, DiagnosticSnapshot, EventCategory,
+    ObservationCategory, PatientDemographics, PeriodTiming, ResourceReference, Severity, TagRule,
+    TimelineConfig, TimelineError, TimelineEvent, TimelineSnapshot, VitalSnapshot, VitalTrend,
+    VitalTrendPoint,
 };
 
+/// Warning added when clinical resources arrive without any `Patient` entry, so
+/// consumers can tell "no demographics in the source" from a dropped resource.
+pub const MISSING_PATIENT_WARNING: &str =
+    "No Patient resource in bundle; demographics are unavailable.";
+
 /// Summarize timeline data from a JSON string.
 pub fn summarize_bundle_str(
     bundle_json: &str,
     config: &TimelineConfig,
 ) -> Result<TimelineSnapshot, TimelineError> {
-    let value: Value =
-        serde_json::from_str(bundle_json).map_err(|err| TimelineError::Parse(err.to_string()))?;
+    let value: Value = serde_json::from_str(bundle_json)
+        .map_err(|err| TimelineError::InvalidJson(err.to_string()))?;
     summarize_bundle_value(&value, config)
 }
 
@@ -24,16 +47,181 @@ pub fn summarize_bundle_str(
 pub fn summarize_bundle_value(
     bundle: &Value,
     config: &TimelineConfig,
+) -> Result<TimelineSnapshot, TimelineError> {
+    summarize_bundle_inner(bundle, config, None, &HandlerRegistry::default(), None)
+}
+
+/// Site-specific transform applied to a finished snapshot.
+pub type PostProcess = Box<dyn Fn(&mut TimelineSnapshot)>;
+
+/// Like [`summarize_bundle_value`], then hands the finished snapshot to
+/// `post_process` (when given) for site-specific changes such as renaming or
+/// extra alerts.
+pub fn summarize_bundle_value_with(
+    bundle: &Value,
+    config: &TimelineConfig,
+    post_process: Option<PostProcess>,
+) -> Result<TimelineSnapshot, TimelineError> {
+    let mut snapshot = summarize_bundle_value(bundle, config)?;
+    if let Some(post_process) = post_process {
+        post_process(&mut snapshot);
+    }
+    Ok(snapshot)
+}
+
+/// Like [`summarize_bundle_value`], but converts resource types without a
+/// built-in handler with the handlers in `handlers`.
+pub fn summarize_bundle_with_handlers(
+    bundle: &Value,
+    config: &TimelineConfig,
+    handlers: &HandlerRegistry,
+) -> Result<TimelineSnapshot, TimelineError> {
+    summarize_bundle_inner(bundle, config, None, handlers, None)
+}
+
+/// Like [`summarize_bundle_value`], but also hands each event to `sink` as soon
+/// as it is created (in bundle order), e.g. to stream events into storage.
+pub fn summarize_bundle_with_sink<F: FnMut(&TimelineEvent)>(
+    bundle: &Value,
+    config: &TimelineConfig,
+    mut sink: F,
+) -> Result<TimelineSnapshot, TimelineError> {
+    summarize_bundle_inner(
+        bundle,
+        config,
+        Some(&mut sink),
+        &HandlerRegistry::default(),
+        None,
+    )
+}
+
+/// Best-effort variant of [`summarize_bundle_str`] for exports that may be
+/// partially corrupt.
+///
+/// Accepts a Bundle, a JSON array of resources (or bundle entries), or
+/// line-delimited JSON (ndjson). For ndjson every line is parsed on its own and
+/// unparseable lines are skipped with a warning; array items that are not
+/// resources are skipped likewise. A single malformed Bundle still fails, as
+/// there is no way to recover its entries.
+pub fn summarize_bundle_lenient(
+    input: &str,
+    config: &TimelineConfig,
+) -> Result<TimelineSnapshot, TimelineError> {
+    let mut warnings = Vec::new();
+    let resources = match serde_json::from_str::<Value>(input) {
+        Ok(value) if value.get("resourceType").and_then(Value::as_str) == Some("Bundle") => {
+            return summarize_bundle_value(&value, config);
+        }
+        Ok(Value::Array(items)) => items
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let resource = lenient_resource(item);
+                if resource.is_none() {
+                    warnings.push(format!("Skipped item {index}: not a FHIR resource"));
+                }
+                resource
+            })
+            .collect(),
+        Ok(value) => vec![lenient_resource(value).ok_or(TimelineError::MissingData)?],
+        Err(_) => input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(|(index, line)| {
+                let line_number = index + 1;
+                match serde_json::from_str::<Value>(line) {
+                    Ok(value) => {
+                        let resource = lenient_resource(value);
+                        if resource.is_none() {
+                            warnings
+                                .push(format!("Skipped line {line_number}: not a FHIR resource"));
+                        }
+                        resource
+                    }
+                    Err(err) => {
+                        warnings.push(format!("Skipped line {line_number}: {err}"));
+                        None
+                    }
+                }
+            })
+            .collect(),
+    };
+
+    if resources.is_empty() {
+        return Err(TimelineError::Parse(
+            "No parseable FHIR resources in input".to_string(),
+        ));
+    }
+
+    let entries: Vec<Value> = resources
+        .into_iter()
+        .map(|resource| serde_json::json!({ "resource": resource }))
+        .collect();
+    let bundle = serde_json::json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": entries
+    });
+
+    let mut snapshot = summarize_bundle_value(&bundle, config)?;
+    warnings.append(&mut snapshot.warnings);
+    snapshot.warnings = warnings;
+    Ok(snapshot)
+}
+
+/// Major versions of FHIR (R4, R4B, R5) whose Bundle layout the converter reads.
+const SUPPORTED_FHIR_VERSIONS: [&str; 2] = ["4.", "5."];
+
+/// FHIR version the bundle declares in `meta.fhirVersion` (or a top-level
+/// `fhirVersion`, as some exporters write it); most bundles declare none.
+fn declared_fhir_version(bundle: &Value) -> Option<&str> {
+    bundle
+        .get("meta")
+        .and_then(|meta| meta.get("fhirVersion"))
+        .or_else(|| bundle.get("fhirVersion"))
+        .and_then(Value::as_str)
+}
+
+/// The resource inside a bundle entry, or `value` itself when it already is one.
+fn lenient_resource(value: Value) -> Option<Value> {
+    let resource = match value.get("resource") {
+        Some(resource) => resource.clone(),
+        None => value,
+    };
+    resource
+        .get("resourceType")
+        .and_then(Value::as_str)
+        .is_some()
+        .then_some(resource)
+}
+
+fn summarize_bundle_inner<'a>(
+    bundle: &Value,
+    config: &'a TimelineConfig,
+    sink: Option<&'a mut dyn FnMut(&TimelineEvent)>,
+    handlers: &HandlerRegistry,
+    mut coverage: Option<&mut CoverageReport>,
 ) -> Result<TimelineSnapshot, TimelineError> {
     let bundle_type = bundle
         .get("resourceType")
         .and_then(Value::as_str)
-        .ok_or_else(|| TimelineError::MissingData)?;
+        .ok_or(TimelineError::MissingData)?;
 
     if bundle_type != "Bundle" {
-        return Err(TimelineError::Parse(format!(
-            "Expected resourceType Bundle, received {bundle_type}"
-        )));
+        return Err(TimelineError::NotABundle {
+            found: bundle_type.to_string(),
+        });
+    }
+    if let Some(version) = declared_fhir_version(bundle) {
+        if !SUPPORTED_FHIR_VERSIONS
+            .iter()
+            .any(|prefix| version.starts_with(prefix))
+        {
+            return Err(TimelineError::UnsupportedVersion {
+                version: version.to_string(),
+            });
+        }
     }
 
     let entries = bundle
@@ -41,50 +229,117 @@ pub fn summarize_bundle_value(
         .and_then(Value::as_array)
         .ok_or(TimelineError::MissingData)?;
 
-    let anchor = compute_anchor(entries);
+    let (anchor, warnings) = match config.anchor_override {
+        Some(anchor) => (Some(anchor), Vec::new()),
+        None => {
+            let latest_plausible =
+                Utc::now() + Duration::days(i64::from(config.future_tolerance_days));
+            compute_anchor(entries, latest_plausible, config.observation_period_timing)
+        }
+    };
     let mut aggregate = AggregateData::with_anchor(anchor);
+    aggregate.sink = sink;
+    aggregate.source_system = meta_source(bundle);
+    aggregate.tag_rules = &config.tag_rules;
+    if !config.include_future_events {
+        aggregate.future_cutoff = Some(config.anchor_override.unwrap_or_else(Utc::now));
+    }
+    aggregate.warnings = warnings;
+    aggregate.panel_members = collect_panel_members(entries);
+    aggregate.full_urls = collect_full_urls(entries)
+        .into_iter()
+        .map(|(full_url, reference)| (full_url.to_string(), reference))
+        .collect();
+    let derived_observations = if config.suppress_derived_observations {
+        collect_derived_observations(entries)
+    } else {
+        HashSet::new()
+    };
 
     for entry in entries {
         let Some(resource) = entry.get("resource") else {
             continue;
         };
 
-        match resource
+        let resource_type = resource
             .get("resourceType")
             .and_then(Value::as_str)
-            .unwrap_or_default()
+            .unwrap_or_default();
+        let contributions_before = aggregate.contribution_count();
+
+        if !is_context_resource(resource_type, resource)
+            && !config.includes(resource_timestamp(
+                resource,
+                config.observation_period_timing,
+            ))
+        {
+            record_coverage(coverage.as_deref_mut(), resource_type, false);
+            continue;
+        }
+
+        if resource_type == "Observation"
+            && observation_key(resource).is_some_and(|key| derived_observations.contains(&key))
         {
-            "Patient" => aggregate.handle_patient(resource),
-            "AllergyIntolerance" => aggregate.handle_allergy(resource),
-            "MedicationStatement" => aggregate.handle_medication(resource),
-            "MedicationRequest" => aggregate.handle_medication(resource),
-            "Condition" => aggregate.handle_condition(resource, config),
-            "Observation" => aggregate.handle_observation(resource, config),
-            "Procedure" => aggregate.handle_procedure(resource),
-            "Encounter" => aggregate.handle_encounter(resource),
-            "DocumentReference" | "Composition" => aggregate.handle_document(resource),
-            _ => {}
+            record_coverage(coverage.as_deref_mut(), resource_type, false);
+            continue;
+        }
+
+        match handlers.find(resource_type) {
+            Some(handler) => {
+                let mut ctx = ConversionContext {
+                    aggregate: &mut aggregate,
+                    config,
+                };
+                handler.handle(resource, &mut ctx);
+            }
+            None if config.capture_unknown_resources => aggregate.handle_unknown(resource, config),
+            None => {}
         }
+        let contributed = aggregate.contribution_count() > contributions_before;
+        record_coverage(coverage.as_deref_mut(), resource_type, contributed);
     }
 
     Ok(aggregate.finalize(config))
 }
 
+fn record_coverage(coverage: Option<&mut CoverageReport>, resource_type: &str, contributed: bool) {
+    if let Some(coverage) = coverage {
+        coverage.record(resource_type, contributed);
+    }
+}
+
 #[derive(Default)]
-struct AggregateData {
+struct AggregateData<'a> {
     anchor: Option<DateTime<Utc>>,
     alerts: Vec<CriticalItem>,
     allergies: Vec<CriticalItem>,
-    medications: Vec<CriticalItem>,
-    chronic_conditions: Vec<CriticalItem>,
+    medications: PanelItems,
+    discontinued_medications: PanelItems,
+    chronic_conditions: PanelItems,
     code_status: Option<CodeStatusRecord>,
     vitals: HashMap<String, VitalSnapshot>,
     vital_trends: HashMap<String, TrendAccumulator>,
+    blood_pressure: TrendAccumulator<BloodPressurePoint>,
     diagnostics: HashMap<String, DiagnosticSnapshot>,
     events: Vec<TimelineEvent>,
+    panel_members: HashMap<String, String>,
+    /// Entry `fullUrl` → `{resourceType}/{id}`, for resolving `List` entries.
+    full_urls: HashMap<String, String>,
+    /// Position of each resource in a curated `List`: (list index, entry index).
+    list_ranks: HashMap<String, (usize, usize)>,
+    /// Bundle `meta.source`, used for events whose resource does not declare one.
+    source_system: Option<String>,
+    patient: Option<PatientDemographics>,
+    warnings: Vec<String>,
+    sink: Option<&'a mut dyn FnMut(&TimelineEvent)>,
+    tag_rules: &'a [TagRule],
+    /// Events after this instant are dropped (`include_future_events: false`).
+    future_cutoff: Option<DateTime<Utc>>,
 }
 
-impl AggregateData {
+impl AggregateData<'_> {
+    /// Recency gating is measured against `anchor`: `TimelineConfig::anchor_override`
+    /// when set, otherwise the latest plausible timestamp of the bundle.
     fn with_anchor(anchor: Option<DateTime<Utc>>) -> Self {
         Self {
             anchor,
@@ -92,38 +347,87 @@ impl AggregateData {
         }
     }
 
-    fn handle_patient(&mut self, resource: &Value) {
-        if let Some(name) = extract_patient_name(resource) {
-            let mut detail_parts = Vec::new();
+    /// Running total of everything a resource can add, so coverage can tell
+    /// whether a handler contributed.
+    fn contribution_count(&self) -> usize {
+        self.events.len()
+            + self.alerts.len()
+            + self.allergies.len()
+            + self.medications.items.len()
+            + self.discontinued_medications.items.len()
+            + self.chronic_conditions.items.len()
+            + self.list_ranks.len()
+            + usize::from(self.patient.is_some())
+    }
 
-            if let Some(age) = extract_patient_age(resource) {
-                detail_parts.push(format!("Age {age}"));
-            }
+    fn handle_patient(&mut self, resource: &Value) {
+        let deceased_at = extract_datetime(resource, &["deceasedDateTime"]);
+        let demographics = PatientDemographics {
+            name: extract_patient_name(resource),
+            age: extract_patient_age(resource),
+            gender: resource
+                .get("gender")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            birth_date: resource
+                .get("birthDate")
+                .and_then(Value::as_str)
+                .and_then(parse_date),
+            deceased: deceased_at.is_some()
+                || resource
+                    .get("deceasedBoolean")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+            deceased_at,
+        };
 
-            if let Some(gender) = resource.get("gender").and_then(Value::as_str) {
-                detail_parts.push(match gender {
-                    "male" => "Male".to_string(),
-                    "female" => "Female".to_string(),
-                    other => format!("Gender: {other}"),
-                });
-            }
+        match self.patient.as_mut() {
+            Some(patient) => patient.merge(demographics),
+            None => self.patient = Some(demographics),
+        }
+    }
 
-            let detail = if detail_parts.is_empty() {
-                None
-            } else {
-                Some(detail_parts.join(" | "))
-            };
+    /// Record the curated order of a problem or medication `List`. Lists that
+    /// were entered in error, or that describe changes rather than a current
+    /// list (`mode` = `changes`), are ignored, as are deleted entries.
+    fn handle_list(&mut self, resource: &Value) {
+        let status = resource.get("status").and_then(Value::as_str);
+        let mode = resource.get("mode").and_then(Value::as_str);
+        if status == Some("entered-in-error") || mode == Some("changes") {
+            return;
+        }
+        let Some(entries) = resource.get("entry").and_then(Value::as_array) else {
+            return;
+        };
 
-            self.alerts.push(CriticalItem {
-                label: format!("Patient: {name}"),
-                detail,
-                severity: Severity::Info,
-            });
+        let list_index = self
+            .list_ranks
+            .values()
+            .map(|(list, _)| list + 1)
+            .max()
+            .unwrap_or(0);
+        let references = entries
+            .iter()
+            .filter(|entry| entry.get("deleted").and_then(Value::as_bool) != Some(true))
+            .filter_map(|entry| entry.pointer("/item/reference").and_then(Value::as_str));
+        for (position, reference) in references.enumerate() {
+            let reference = self
+                .full_urls
+                .get(reference)
+                .cloned()
+                .unwrap_or_else(|| reference.to_string());
+            self.list_ranks
+                .entry(reference)
+                .or_insert((list_index, position));
         }
     }
 
     fn handle_allergy(&mut self, resource: &Value) {
-        let Some(label) = resource.get("code").and_then(extract_codeable_text) else {
+        let Some(label) = allergy_label(resource) else {
+            self.warnings.push(format!(
+                "Skipped AllergyIntolerance/{} without a code or reaction substance",
+                resource_id(resource, "allergy")
+            ));
             return;
         };
 
@@ -140,7 +444,7 @@ impl AggregateData {
                     "Category: {}.",
                     category
                         .into_iter()
-                        .map(|s| capitalize_first(s))
+                        .map(capitalize_first)
                         .collect::<Vec<_>>()
                         .join(", ")
                 ));
@@ -155,7 +459,8 @@ impl AggregateData {
             phrases.push(format!("Criticality {}.", criticality.to_uppercase()));
         }
 
-        let recorded_at = extract_datetime(resource, &["recordedDate", "onsetDateTime"]);
+        let (recorded_at, precision) =
+            extract_datetime_with_precision(resource, &["recordedDate", "onsetDateTime"]);
 
         let detail = if phrases.is_empty() {
             None
@@ -167,11 +472,14 @@ impl AggregateData {
             label: format!("Allergy: {label}"),
             detail: detail.clone(),
             severity,
+            recorded_at,
+            reactions: allergy_reactions(resource),
+            watched: false,
         };
 
         self.allergies.push(item);
 
-        self.events.push(TimelineEvent {
+        self.push_event(TimelineEvent {
             id: resource_id(resource, "allergy"),
             category: EventCategory::Condition,
             title: format!("Allergy documented: {label}"),
@@ -179,10 +487,16 @@ impl AggregateData {
             occurred_at: recorded_at,
             severity,
             source: make_reference(resource),
+            panel: None,
+            observation_category: None,
+            tags: Vec::new(),
+            period_end: None,
+            occurred_at_precision: precision,
+            notes: Vec::new(),
         });
     }
 
-    fn handle_medication(&mut self, resource: &Value) {
+    fn handle_medication(&mut self, resource: &Value, config: &TimelineConfig) {
         let medication = resource
             .get("medicationCodeableConcept")
             .and_then(extract_codeable_text)
@@ -201,10 +515,16 @@ impl AggregateData {
             .and_then(Value::as_str)
             .unwrap_or("unknown");
 
+        let prn = prn_indication(resource);
+        let discontinued = matches!(status, "stopped" | "not-taken");
         let severity = match status {
+            // As-needed orders are not standing therapy, so they may rank below scheduled ones.
+            "active" | "intended" if prn.is_some() && config.lower_prn_medication_severity => {
+                Severity::Moderate
+            }
             "active" | "intended" => Severity::High,
             "on-hold" => Severity::Moderate,
-            "completed" => Severity::Low,
+            "completed" | "stopped" | "not-taken" => Severity::Low,
             _ => Severity::Moderate,
         };
 
@@ -214,12 +534,20 @@ impl AggregateData {
             "intended" => Some("Planned therapy.".to_string()),
             "completed" => Some("Course completed.".to_string()),
             "on-hold" => Some("Therapy on hold.".to_string()),
+            "stopped" => Some("Discontinued.".to_string()),
+            "not-taken" => Some("Not taken; discontinued.".to_string()),
             other => Some(format!("Status {other}.")),
         };
         if let Some(phrase) = status_phrase {
             phrases.push(phrase);
         }
 
+        match prn {
+            Some(Some(reason)) => phrases.push(format!("PRN for {reason}.")),
+            Some(None) => phrases.push("PRN.".to_string()),
+            None => {}
+        }
+
         if let Some(reason) = resource
             .get("reasonCode")
             .and_then(Value::as_array)
@@ -233,7 +561,7 @@ impl AggregateData {
             phrases.extend(dose_phrases);
         }
 
-        let recorded_at = extract_datetime(
+        let (recorded_at, recorded_precision) = extract_datetime_with_precision(
             resource,
             &[
                 "effectiveDateTime",
@@ -253,17 +581,37 @@ impl AggregateData {
             label: format!("Medication: {medication}"),
             detail: detail.clone(),
             severity,
+            recorded_at,
+            reactions: Vec::new(),
+            watched: false,
         };
-        self.medications.push(item);
+        if discontinued && config.separate_discontinued_medications {
+            self.discontinued_medications
+                .push(item, resource_key(resource));
+        } else {
+            self.medications.push(item, resource_key(resource));
+        }
+
+        let (occurred_at, period_end, precision) =
+            match extract_period_bounds(resource, "effectivePeriod") {
+                Some((start, end)) => (Some(start), end, DatePrecision::DateTime),
+                None => (recorded_at, None, recorded_precision),
+            };
 
-        self.events.push(TimelineEvent {
+        self.push_event(TimelineEvent {
             id: resource_id(resource, "medication"),
             category: EventCategory::Medication,
-            title: format!("{medication}"),
+            title: medication.clone(),
             detail,
-            occurred_at: recorded_at,
+            occurred_at,
             severity,
             source: make_reference(resource),
+            panel: None,
+            observation_category: None,
+            tags: Vec::new(),
+            period_end,
+            occurred_at_precision: precision,
+            notes: Vec::new(),
         });
     }
 
@@ -272,18 +620,38 @@ impl AggregateData {
             return;
         };
 
-        let recorded_at = extract_datetime(
+        let (recorded_at, precision) = extract_datetime_with_precision(
             resource,
             &["recordedDate", "onsetDateTime", "onsetDate", "assertedDate"],
         );
 
-        if !is_recent_event(self.anchor, recorded_at, config.clinical_event_days) {
+        let watched = config.is_watched(&condition_name);
+        if !watched && !is_recent_event(self.anchor, recorded_at, config.clinical_event_days) {
             return;
         }
 
-        let severity = map_condition_severity(&condition_name);
-
-        let mut phrases = Vec::new();
+        let stages = condition_stages(resource);
+        let base_severity = configured_code_severity(resource, config)
+            .unwrap_or_else(|| map_condition_severity(&condition_name));
+        let severity = Severity::most_severe(
+            std::iter::once(base_severity).chain(
+                stages
+                    .iter()
+                    .filter_map(|stage| config.stage_severity(stage)),
+            ),
+        )
+        .unwrap_or(base_severity);
+
+        let mut phrases: Vec<String> = stages
+            .iter()
+            .map(|stage| {
+                if stage.to_lowercase().starts_with("stage") {
+                    format!("{stage}.")
+                } else {
+                    format!("Stage {stage}.")
+                }
+            })
+            .collect();
         if let Some(status) = extract_status_code(resource.get("clinicalStatus")) {
             phrases.push(format!("Status {status}."));
         }
@@ -299,11 +667,15 @@ impl AggregateData {
                 Some(phrases.join(" "))
             },
             severity,
+            recorded_at,
+            reactions: Vec::new(),
+            watched,
         };
 
-        self.chronic_conditions.push(item.clone());
+        self.chronic_conditions
+            .push(item.clone(), resource_key(resource));
 
-        self.events.push(TimelineEvent {
+        self.push_event(TimelineEvent {
             id: resource_id(resource, "condition"),
             category: EventCategory::Condition,
             title: condition_name,
@@ -311,10 +683,16 @@ impl AggregateData {
             occurred_at: recorded_at,
             severity,
             source: make_reference(resource),
+            panel: None,
+            observation_category: None,
+            tags: Vec::new(),
+            period_end: None,
+            occurred_at_precision: precision,
+            notes: Vec::new(),
         });
     }
 
-    fn handle_observation(&mut self, resource: &Value, _config: &TimelineConfig) {
+    fn handle_observation(&mut self, resource: &Value, config: &TimelineConfig) {
         let name = resource
             .get("code")
             .and_then(extract_codeable_text)
@@ -322,7 +700,8 @@ impl AggregateData {
 
         if observation_is_code_status(resource) {
             if let Some(value) = observation_value_text(resource) {
-                let recorded_at = extract_observation_timestamp(resource);
+                let recorded_at =
+                    extract_observation_timestamp(resource, config.observation_period_timing);
                 let severity = Severity::Critical;
                 self.code_status = match &self.code_status {
                     Some(existing) if is_more_recent(existing.recorded_at, recorded_at) => {
@@ -331,7 +710,7 @@ impl AggregateData {
                     _ => Some(CodeStatusRecord { value, recorded_at }),
                 };
 
-                self.events.push(TimelineEvent {
+                self.push_event(TimelineEvent {
                     id: resource_id(resource, "code-status"),
                     category: EventCategory::Observation,
                     title: "Code status updated".to_string(),
@@ -339,6 +718,12 @@ impl AggregateData {
                     occurred_at: recorded_at,
                     severity,
                     source: make_reference(resource),
+                    panel: None,
+                    observation_category: None,
+                    tags: Vec::new(),
+                    period_end: None,
+                    occurred_at_precision: DatePrecision::DateTime,
+                    notes: Vec::new(),
                 });
             }
             return;
@@ -349,8 +734,26 @@ impl AggregateData {
             None => return,
         };
 
-        let recorded_at = extract_observation_timestamp(resource);
-        let severity = classify_observation(&name, resource, &detail);
+        let recorded_at = extract_observation_timestamp(resource, config.observation_period_timing);
+        let vital_label = infer_vital_label(&name);
+        if let Some(vital_label) = vital_label {
+            let (numeric_value, _) = observation_numeric_metadata(&name, resource, &detail);
+            if let (Some(value), Some(range)) =
+                (numeric_value, config.physiologic_range(vital_label))
+            {
+                if !range.contains(value) {
+                    self.warnings.push(format!(
+                        "Dropped implausible {vital_label} value {detail} ({}); expected {}–{}",
+                        resource_id(resource, "observation"),
+                        range.min,
+                        range.max
+                    ));
+                    return;
+                }
+            }
+        }
+        let severity = configured_code_severity(resource, config)
+            .unwrap_or_else(|| classify_observation(&name, resource, &detail, config));
 
         let event = TimelineEvent {
             id: resource_id(resource, "observation"),
@@ -360,9 +763,15 @@ impl AggregateData {
             occurred_at: recorded_at,
             severity,
             source: make_reference(resource),
+            panel: self.panel_title_for(resource),
+            observation_category: observation_category(resource),
+            tags: Vec::new(),
+            period_end: None,
+            occurred_at_precision: DatePrecision::DateTime,
+            notes: annotation_texts(resource),
         };
 
-        if let Some(vital_label) = infer_vital_label(&name) {
+        if let Some(vital_label) = vital_label {
             let (numeric_value, unit) = observation_numeric_metadata(&name, resource, &detail);
             let snapshot = VitalSnapshot {
                 name: vital_label.to_string(),
@@ -370,14 +779,29 @@ impl AggregateData {
                 recorded_at,
                 numeric_value,
                 unit: unit.clone(),
+                severity,
+                watched: config.is_watched(vital_label) || config.is_watched(&name),
             };
             self.upsert_vital(snapshot);
+            if vital_label == "Blood pressure" {
+                if let Some((systolic, diastolic)) = parse_blood_pressure_from_detail(&detail) {
+                    self.blood_pressure.push(
+                        BloodPressurePoint {
+                            recorded_at,
+                            systolic,
+                            diastolic,
+                        },
+                        unit.clone(),
+                    );
+                }
+            }
             self.record_vital_trend(
                 vital_label,
                 recorded_at,
                 numeric_value,
                 detail.clone(),
                 unit,
+                Duration::seconds(i64::from(config.vital_duplicate_window_seconds)),
             );
         } else if let Some(kind) = guess_diagnostic_kind(&name, resource) {
             let (_, unit) = observation_numeric_metadata(&name, resource, &detail);
@@ -388,22 +812,24 @@ impl AggregateData {
                 severity,
                 kind,
                 unit,
+                watched: config.is_watched(&name),
             };
             self.upsert_diagnostic(snapshot);
         }
 
-        self.events.push(event);
+        self.push_event(event);
     }
 
-    fn handle_procedure(&mut self, resource: &Value) {
+    fn handle_procedure(&mut self, resource: &Value, config: &TimelineConfig) {
         let name = resource
             .get("code")
             .and_then(extract_codeable_text)
             .unwrap_or_else(|| "Procedure".to_string());
-        let recorded_at = extract_datetime(resource, &["performedDateTime", "performedPeriod"]);
-        let severity = Severity::Moderate;
+        let (recorded_at, precision) =
+            extract_datetime_with_precision(resource, &["performedDateTime", "performedPeriod"]);
+        let severity = map_procedure_severity(&name, resource, config);
 
-        self.events.push(TimelineEvent {
+        self.push_event(TimelineEvent {
             id: resource_id(resource, "procedure"),
             category: EventCategory::Procedure,
             title: name,
@@ -411,6 +837,12 @@ impl AggregateData {
             occurred_at: recorded_at,
             severity,
             source: make_reference(resource),
+            panel: None,
+            observation_category: None,
+            tags: Vec::new(),
+            period_end: None,
+            occurred_at_precision: precision,
+            notes: Vec::new(),
         });
     }
 
@@ -427,20 +859,43 @@ impl AggregateData {
                     .unwrap_or_else(|| "Encounter".to_string())
             });
 
-        let recorded_at = extract_datetime(resource, &["period"]);
+        let (occurred_at, period_end) = match extract_period_bounds(resource, "period") {
+            Some((start, end)) => (Some(start), end),
+            None => (extract_datetime(resource, &["period"]), None),
+        };
 
-        self.events.push(TimelineEvent {
+        let reason = resource
+            .get("reasonCode")
+            .and_then(Value::as_array)
+            .and_then(|arr| arr.first())
+            .and_then(extract_codeable_text);
+        let service = resource.get("serviceType").and_then(|value| match value {
+            Value::Array(items) => items.first().and_then(|item| {
+                item.get("concept")
+                    .and_then(extract_codeable_text)
+                    .or_else(|| extract_codeable_text(item))
+            }),
+            other => extract_codeable_text(other),
+        });
+        let detail_parts: Vec<String> = [reason, service, encounter_location_path(resource)]
+            .into_iter()
+            .flatten()
+            .collect();
+
+        self.push_event(TimelineEvent {
             id: resource_id(resource, "encounter"),
             category: EventCategory::Encounter,
             title: format!("Encounter: {label}"),
-            detail: resource
-                .get("reasonCode")
-                .and_then(Value::as_array)
-                .and_then(|arr| arr.first())
-                .and_then(extract_codeable_text),
-            occurred_at: recorded_at,
+            detail: (!detail_parts.is_empty()).then(|| detail_parts.join(" | ")),
+            occurred_at,
             severity: Severity::Info,
             source: make_reference(resource),
+            panel: None,
+            observation_category: None,
+            tags: Vec::new(),
+            period_end,
+            occurred_at_precision: DatePrecision::DateTime,
+            notes: Vec::new(),
         });
     }
 
@@ -456,9 +911,10 @@ impl AggregateData {
             })
             .unwrap_or_else(|| "Clinical document".to_string());
 
-        let recorded_at = extract_datetime(resource, &["date", "created"]);
+        let (recorded_at, precision) =
+            extract_datetime_with_precision(resource, &["date", "created"]);
 
-        self.events.push(TimelineEvent {
+        self.push_event(TimelineEvent {
             id: resource_id(resource, "document"),
             category: EventCategory::Document,
             title,
@@ -476,9 +932,121 @@ impl AggregateData {
             occurred_at: recorded_at,
             severity: Severity::Low,
             source: make_reference(resource),
+            panel: None,
+            observation_category: None,
+            tags: Vec::new(),
+            period_end: None,
+            occurred_at_precision: precision,
+            notes: Vec::new(),
+        });
+    }
+
+    /// Insurance context: every coverage becomes an `Other` event naming the
+    /// payor and status, and a coverage that is not `active` raises a Moderate
+    /// alert so financial clearance is not missed.
+    fn handle_coverage(&mut self, resource: &Value) {
+        let payor = coverage_payor(resource).unwrap_or_else(|| "Unknown payor".to_string());
+        let status = resource
+            .get("status")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let (occurred_at, period_end) = match extract_period_bounds(resource, "period") {
+            Some((start, end)) => (Some(start), end),
+            None => (None, None),
+        };
+        let plan = resource
+            .get("type")
+            .and_then(extract_codeable_text)
+            .map(|plan| format!("Plan: {plan}."));
+
+        if status != "active" {
+            self.alerts.push(CriticalItem {
+                label: format!("Coverage: {payor}"),
+                detail: Some(format!("Coverage status {status}.")),
+                severity: Severity::Moderate,
+                recorded_at: occurred_at,
+                reactions: Vec::new(),
+                watched: false,
+            });
+        }
+
+        self.push_event(TimelineEvent {
+            id: resource_id(resource, "coverage"),
+            category: EventCategory::Other,
+            title: format!("Coverage: {payor} ({status})"),
+            detail: plan,
+            occurred_at,
+            severity: Severity::Low,
+            source: make_reference(resource),
+            panel: None,
+            observation_category: None,
+            tags: Vec::new(),
+            period_end,
+            occurred_at_precision: DatePrecision::DateTime,
+            notes: Vec::new(),
+        });
+    }
+
+    /// Generic fallback for resource types without a dedicated handler: anything
+    /// carrying a `code` and a timestamp becomes a low-severity `Other` event.
+    fn handle_unknown(&mut self, resource: &Value, config: &TimelineConfig) {
+        let Some(title) = resource.get("code").and_then(extract_codeable_text) else {
+            return;
+        };
+        let Some(recorded_at) = resource_timestamp(resource, config.observation_period_timing)
+        else {
+            return;
+        };
+        let resource_type = resource
+            .get("resourceType")
+            .and_then(Value::as_str)
+            .unwrap_or("Resource");
+
+        self.push_event(TimelineEvent {
+            id: resource_id(resource, "resource"),
+            category: EventCategory::Other,
+            title,
+            detail: Some(format!("{resource_type} resource")),
+            occurred_at: Some(recorded_at),
+            severity: Severity::Low,
+            source: make_reference(resource),
+            panel: None,
+            observation_category: None,
+            tags: Vec::new(),
+            period_end: None,
+            occurred_at_precision: DatePrecision::DateTime,
+            notes: Vec::new(),
         });
     }
 
+    fn push_event(&mut self, mut event: TimelineEvent) {
+        if let (Some(cutoff), Some(occurred_at)) = (self.future_cutoff, event.occurred_at) {
+            if occurred_at > cutoff {
+                return;
+            }
+        }
+        if let Some(source) = event.source.as_mut() {
+            source.system.get_or_insert_with(|| {
+                self.source_system
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_SOURCE_SYSTEM.to_string())
+            });
+        }
+        for rule in self.tag_rules {
+            if rule.matches(&event) && !event.tags.contains(&rule.tag) {
+                event.tags.push(rule.tag.clone());
+            }
+        }
+        if let Some(sink) = self.sink.as_mut() {
+            sink(&event);
+        }
+        self.events.push(event);
+    }
+
+    fn panel_title_for(&self, resource: &Value) -> Option<String> {
+        self.panel_members.get(&observation_key(resource)?).cloned()
+    }
+
     fn upsert_vital(&mut self, snapshot: VitalSnapshot) {
         let key = snapshot.name.clone();
         match self.vitals.entry(key) {
@@ -523,11 +1091,22 @@ impl AggregateData {
         numeric_value: Option<f64>,
         display: String,
         unit: Option<String>,
+        duplicate_window: Duration,
     ) {
-        let entry = self
-            .vital_trends
-            .entry(label.to_string())
-            .or_insert_with(TrendAccumulator::default);
+        let entry = self.vital_trends.entry(label.to_string()).or_default();
+
+        // Monitors sometimes send one reading twice; keep the first copy.
+        if let (Some(at), Some(value)) = (recorded_at, numeric_value) {
+            let is_duplicate = entry.points.iter().any(|point| {
+                point.value == Some(value)
+                    && point
+                        .recorded_at
+                        .is_some_and(|other| (at - other).abs() <= duplicate_window)
+            });
+            if is_duplicate {
+                return;
+            }
+        }
 
         entry.push(
             VitalTrendPoint {
@@ -540,25 +1119,27 @@ impl AggregateData {
     }
 
     fn finalize(mut self, config: &TimelineConfig) -> TimelineSnapshot {
-        self.alerts.sort_by_key(|item| item.severity);
-        self.allergies.sort_by_key(|item| item.severity);
-        self.medications.sort_by_key(|item| item.severity);
-        self.chronic_conditions.sort_by_key(|item| item.severity);
-
-        let mut vital_values: Vec<VitalSnapshot> = self
-            .vitals
-            .into_values()
-            .filter(|vital| {
-                is_recent_vital(self.anchor, vital.recorded_at, config.vital_recent_hours)
-            })
-            .collect();
-        vital_values.sort_by(|a, b| b.recorded_at.cmp(&a.recorded_at));
+        let order = config.critical_item_order;
+        let medications = self.medications.into_ordered(&self.list_ranks, order);
+        let discontinued_medications = self
+            .discontinued_medications
+            .into_ordered(&self.list_ranks, order);
+        let chronic_conditions = self
+            .chronic_conditions
+            .into_ordered(&self.list_ranks, order);
+
+        let (mut vital_values, older_vitals): (Vec<VitalSnapshot>, Vec<VitalSnapshot>) =
+            self.vitals.into_values().partition(|vital| {
+                vital.watched
+                    || is_recent_vital(self.anchor, vital.recorded_at, config.vital_recent_hours)
+            });
+        vital_values.sort_by_key(|vital| Reverse(vital.recorded_at));
 
         let mut trends: Vec<VitalTrend> = self
             .vital_trends
             .into_iter()
             .map(|(name, mut acc)| {
-                acc.points.sort_by(|a, b| a.recorded_at.cmp(&b.recorded_at));
+                acc.points.sort_by_key(|a| a.recorded_at);
                 VitalTrend {
                     name,
                     unit: acc.unit,
@@ -572,21 +1153,106 @@ impl AggregateData {
             b_latest.cmp(&a_latest)
         });
 
-        let mut diagnostics: Vec<DiagnosticSnapshot> = self.diagnostics.into_values().collect();
-        diagnostics.sort_by(|a, b| b.recorded_at.cmp(&a.recorded_at));
+        if let Some(threshold) = config.sustained_abnormal_readings.filter(|n| *n > 0) {
+            for trend in &trends {
+                let Some(escalation) = sustained_abnormal(trend, threshold as usize) else {
+                    continue;
+                };
+                if let Some(vital) = vital_values.iter_mut().find(|v| v.name == trend.name) {
+                    vital.severity = vital.severity.escalate();
+                }
+                self.alerts.push(escalation);
+            }
+        }
+        if config.emit_patient_alert {
+            self.alerts
+                .extend(self.patient.as_ref().and_then(patient_alert));
+        }
+        for items in [&mut self.alerts, &mut self.allergies] {
+            items.sort_by(|a, b| order.compare(a, b));
+        }
+
+        let mut diagnostics: Vec<DiagnosticSnapshot> = self
+            .diagnostics
+            .into_values()
+            .filter(|item| {
+                item.watched
+                    || is_recent_event(self.anchor, item.recorded_at, config.clinical_event_days)
+            })
+            .collect();
+        diagnostics.sort_by_key(|item| Reverse(item.recorded_at));
+
+        let blood_pressure_trend = (!self.blood_pressure.points.is_empty())
+            .then(|| BloodPressureTrend::new(self.blood_pressure.unit, self.blood_pressure.points));
 
         let critical = CriticalSummary {
             allergies: self.allergies,
-            medications: self.medications,
-            chronic_conditions: self.chronic_conditions,
+            medications,
+            discontinued_medications,
+            chronic_conditions,
             code_status: self.code_status.map(|cs| cs.value),
             alerts: self.alerts,
             recent_vitals: vital_values,
             vital_trends: trends,
             recent_diagnostics: diagnostics,
+            blood_pressure_trend,
+            older_vitals: older_vitals.len(),
+            vital_window_hours: Some(config.vital_recent_hours),
         };
 
-        TimelineSnapshot::new(critical, self.events)
+        let has_clinical_data = !self.events.is_empty()
+            || !critical.allergies.is_empty()
+            || !critical.medications.is_empty()
+            || !critical.discontinued_medications.is_empty();
+        if self.patient.is_none() && has_clinical_data {
+            self.warnings.push(MISSING_PATIENT_WARNING.to_string());
+        }
+
+        let generated_at = config.generated_at.unwrap_or_else(Utc::now);
+        let mut snapshot =
+            TimelineSnapshot::new_with_generated_at(critical, self.events, generated_at);
+        snapshot.patient = self.patient;
+        snapshot.warnings = self.warnings;
+        snapshot
+    }
+}
+
+/// Critical panel items with the reference of their source resource, so a
+/// curated `List` can decide their order in `finalize`.
+#[derive(Default)]
+struct PanelItems {
+    items: Vec<CriticalItem>,
+    references: Vec<Option<String>>,
+}
+
+impl PanelItems {
+    fn push(&mut self, item: CriticalItem, reference: Option<String>) {
+        self.items.push(item);
+        self.references.push(reference);
+    }
+
+    /// Items a `List` references come first, in list order; the rest follow,
+    /// sorted by `order`, so nothing the list omits disappears from the panel.
+    fn into_ordered(
+        self,
+        list_ranks: &HashMap<String, (usize, usize)>,
+        order: CriticalItemOrder,
+    ) -> Vec<CriticalItem> {
+        let mut listed: Vec<((usize, usize), CriticalItem)> = Vec::new();
+        let mut unlisted: Vec<CriticalItem> = Vec::new();
+        for (reference, item) in self.references.into_iter().zip(self.items) {
+            match reference.and_then(|reference| list_ranks.get(&reference).copied()) {
+                Some(rank) => listed.push((rank, item)),
+                None => unlisted.push(item),
+            }
+        }
+        listed.sort_by_key(|(rank, _)| *rank);
+        unlisted.sort_by(|a, b| order.compare(a, b));
+        listed
+            .into_iter()
+            .map(|(_, item)| item)
+            .chain(unlisted)
+            .collect()
     }
 }
 
@@ -596,14 +1262,22 @@ struct CodeStatusRecord {
     recorded_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Default)]
-struct TrendAccumulator {
+struct TrendAccumulator<P = VitalTrendPoint> {
     unit: Option<String>,
-    points: Vec<VitalTrendPoint>,
+    points: Vec<P>,
 }
 
-impl TrendAccumulator {
-    fn push(&mut self, point: VitalTrendPoint, unit: Option<String>) {
+impl<P> Default for TrendAccumulator<P> {
+    fn default() -> Self {
+        Self {
+            unit: None,
+            points: Vec::new(),
+        }
+    }
+}
+
+impl<P> TrendAccumulator<P> {
+    fn push(&mut self, point: P, unit: Option<String>) {
         if self.unit.is_none() && unit.is_some() {
             self.unit = unit.clone();
         }
@@ -614,18 +1288,152 @@ impl TrendAccumulator {
     }
 }
 
-fn compute_anchor(entries: &[Value]) -> Option<DateTime<Utc>> {
+/// Map each `hasMember` target (as `Observation/{id}`) to the title of its panel.
+/// References may point at the entry `fullUrl`, so those are resolved to the
+/// member's resource id first.
+fn collect_panel_members(entries: &[Value]) -> HashMap<String, String> {
+    let full_urls = collect_full_urls(entries);
+
+    let mut members = HashMap::new();
+    for resource in entries.iter().filter_map(|entry| entry.get("resource")) {
+        if resource.get("resourceType").and_then(Value::as_str) != Some("Observation") {
+            continue;
+        }
+        let Some(references) = resource.get("hasMember").and_then(Value::as_array) else {
+            continue;
+        };
+        let title = resource
+            .get("code")
+            .and_then(extract_codeable_text)
+            .unwrap_or_else(|| "Panel".to_string());
+
+        for reference in references
+            .iter()
+            .filter_map(|member| member.get("reference").and_then(Value::as_str))
+        {
+            let key = full_urls
+                .get(reference)
+                .cloned()
+                .unwrap_or_else(|| reference.to_string());
+            members.entry(key).or_insert_with(|| title.clone());
+        }
+    }
+    members
+}
+
+/// Map each entry `fullUrl` to its `{resourceType}/{id}` reference.
+fn collect_full_urls(entries: &[Value]) -> HashMap<&str, String> {
     entries
+        .iter()
+        .filter_map(|entry| {
+            let full_url = entry.get("fullUrl").and_then(Value::as_str)?;
+            let resource = entry.get("resource")?;
+            let resource_type = resource.get("resourceType").and_then(Value::as_str)?;
+            let id = resource.get("id").and_then(Value::as_str)?;
+            Some((full_url, format!("{resource_type}/{id}")))
+        })
+        .collect()
+}
+
+/// `{resourceType}/{id}` of `resource`, as used by references within the bundle.
+fn resource_key(resource: &Value) -> Option<String> {
+    let resource_type = resource.get("resourceType").and_then(Value::as_str)?;
+    let id = resource.get("id").and_then(Value::as_str)?;
+    Some(format!("{resource_type}/{id}"))
+}
+
+fn observation_key(resource: &Value) -> Option<String> {
+    let id = resource.get("id").and_then(Value::as_str)?;
+    Some(format!("Observation/{id}"))
+}
+
+/// Observations (as `Observation/{id}`) whose `derivedFrom` points at another
+/// Observation present in the bundle, such as a MAP computed from a blood
+/// pressure reading. Derivations from absent sources are kept.
+fn collect_derived_observations(entries: &[Value]) -> HashSet<String> {
+    let full_urls = collect_full_urls(entries);
+    let observations: Vec<&Value> = entries
         .iter()
         .filter_map(|entry| entry.get("resource"))
-        .filter_map(resource_timestamp)
-        .max()
+        .filter(|resource| {
+            resource.get("resourceType").and_then(Value::as_str) == Some("Observation")
+        })
+        .collect();
+    let present: HashSet<String> = observations
+        .iter()
+        .filter_map(|resource| observation_key(resource))
+        .collect();
+
+    observations
+        .iter()
+        .filter(|resource| {
+            resource
+                .get("derivedFrom")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|source| source.get("reference").and_then(Value::as_str))
+                .map(|reference| full_urls.get(reference).map_or(reference, String::as_str))
+                .any(|reference| present.contains(reference))
+        })
+        .filter_map(|resource| observation_key(resource))
+        .collect()
 }
 
-fn resource_timestamp(resource: &Value) -> Option<DateTime<Utc>> {
+/// Latest resource timestamp in the bundle. Timestamps after `latest_plausible`
+/// are treated as data-entry errors: they are skipped and reported as warnings
+/// so a single bad date cannot collapse every recency window.
+fn compute_anchor(
+    entries: &[Value],
+    latest_plausible: DateTime<Utc>,
+    period_timing: PeriodTiming,
+) -> (Option<DateTime<Utc>>, Vec<String>) {
+    let mut anchor = None;
+    let mut warnings = Vec::new();
+
+    for resource in entries.iter().filter_map(|entry| entry.get("resource")) {
+        let Some(timestamp) = resource_timestamp(resource, period_timing) else {
+            continue;
+        };
+        if timestamp > latest_plausible {
+            let resource_type = resource
+                .get("resourceType")
+                .and_then(Value::as_str)
+                .unwrap_or("Resource");
+            let id = resource_id(resource, "resource");
+            warnings.push(format!(
+                "Ignored far-future timestamp {} on {resource_type}/{id} when computing the anchor",
+                timestamp.to_rfc3339()
+            ));
+            continue;
+        }
+        anchor = anchor.max(Some(timestamp));
+    }
+
+    (anchor, warnings)
+}
+
+/// Resources kept regardless of the `from`/`to` range because they feed the
+/// critical panel rather than the timeline window: demographics, allergies,
+/// medications, curated lists, active conditions and code status.
+fn is_context_resource(resource_type: &str, resource: &Value) -> bool {
+    match resource_type {
+        "Patient" | "AllergyIntolerance" | "MedicationStatement" | "MedicationRequest" | "List" => {
+            true
+        }
+        "Condition" => extract_status_code(resource.get("clinicalStatus"))
+            .is_some_and(|status| status.eq_ignore_ascii_case("active")),
+        "Observation" => observation_is_code_status(resource),
+        _ => false,
+    }
+}
+
+/// Timestamp used for the anchor and `from`/`to` filtering; observation periods
+/// follow `period_timing` like the events built from them.
+fn resource_timestamp(resource: &Value, period_timing: PeriodTiming) -> Option<DateTime<Utc>> {
     let resource_type = resource.get("resourceType").and_then(Value::as_str)?;
     match resource_type {
-        "Observation" => extract_observation_timestamp(resource),
+        "Observation" => extract_observation_timestamp(resource, period_timing),
         "Condition" => extract_datetime(
             resource,
             &["recordedDate", "onsetDateTime", "onsetDate", "assertedDate"],
@@ -648,11 +1456,60 @@ fn resource_timestamp(resource: &Value) -> Option<DateTime<Utc>> {
         }
         "Procedure" => extract_datetime(resource, &["performedDateTime", "performedPeriod"]),
         "Encounter" => extract_datetime(resource, &["period"]),
+        "Coverage" => extract_datetime(resource, &["period"]),
         "DocumentReference" | "Composition" => extract_datetime(resource, &["date", "created"]),
-        _ => extract_datetime(resource, &["effectiveDateTime", "issued", "date"]),
+        _ => extract_datetime(
+            resource,
+            &["effectiveDateTime", "issued", "date", "created"],
+        ),
     }
 }
 
+/// Payor of a `Coverage`: the first `payor` reference display (R4), else the
+/// R5 `insurer` or first `paymentBy` party.
+fn coverage_payor(resource: &Value) -> Option<String> {
+    let display = |reference: &Value| {
+        reference
+            .get("display")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|display| !display.is_empty())
+            .map(str::to_string)
+    };
+    resource
+        .get("payor")
+        .and_then(Value::as_array)
+        .and_then(|payors| payors.iter().find_map(display))
+        .or_else(|| resource.get("insurer").and_then(display))
+        .or_else(|| {
+            resource
+                .get("paymentBy")
+                .and_then(Value::as_array)
+                .and_then(|parties| {
+                    parties
+                        .iter()
+                        .find_map(|party| party.get("party").and_then(display))
+                })
+        })
+}
+
+/// Non-empty `note[].text` annotations, in source order.
+fn annotation_texts(resource: &Value) -> Vec<String> {
+    resource
+        .get("note")
+        .and_then(Value::as_array)
+        .map(|notes| {
+            notes
+                .iter()
+                .filter_map(|note| note.get("text").and_then(Value::as_str))
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn extract_patient_name(resource: &Value) -> Option<String> {
     let names = resource.get("name")?.as_array()?;
     let name = names.first()?;
@@ -671,6 +1528,40 @@ fn extract_patient_name(resource: &Value) -> Option<String> {
     }
 }
 
+/// "Patient: …" alert for the merged demographics; `None` without a name.
+fn patient_alert(patient: &PatientDemographics) -> Option<CriticalItem> {
+    let name = patient.name.as_ref()?;
+    let mut detail_parts = Vec::new();
+
+    if let Some(age) = patient.age {
+        detail_parts.push(format!("Age {age}"));
+    }
+
+    if let Some(gender) = patient.gender.as_deref() {
+        detail_parts.push(match gender {
+            "male" => "Male".to_string(),
+            "female" => "Female".to_string(),
+            other => format!("Gender: {other}"),
+        });
+    }
+
+    let detail = if detail_parts.is_empty() {
+        None
+    } else {
+        Some(detail_parts.join(" | "))
+    };
+
+    Some(CriticalItem {
+        label: format!("Patient: {name}"),
+        detail,
+        severity: Severity::Info,
+        recorded_at: None,
+        reactions: Vec::new(),
+        watched: false,
+    })
+}
+
+/// Age in whole years as of `Utc::now()`.
 fn extract_patient_age(resource: &Value) -> Option<i32> {
     let birth_date = resource
         .get("birthDate")
@@ -679,12 +1570,7 @@ fn extract_patient_age(resource: &Value) -> Option<i32> {
     let today = Utc::now().date_naive();
     let mut age = today.year() - birth_date.year();
 
-    let has_had_birthday = if (today.month(), today.day()) >= (birth_date.month(), birth_date.day())
-    {
-        true
-    } else {
-        false
-    };
+    let has_had_birthday = (today.month(), today.day()) >= (birth_date.month(), birth_date.day());
 
     if !has_had_birthday {
         age -= 1;
@@ -734,11 +1620,26 @@ fn resource_id(resource: &Value, fallback: &str) -> String {
         .unwrap_or_else(|| format!("{fallback}-unknown"))
 }
 
+/// `source.system` of events when neither the resource nor the bundle has `meta.source`.
+const DEFAULT_SOURCE_SYSTEM: &str = "FHIR";
+
+/// Non-empty `meta.source` (the system that produced the data) of a resource or bundle.
+fn meta_source(value: &Value) -> Option<String> {
+    value
+        .pointer("/meta/source")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|source| !source.is_empty())
+        .map(str::to_string)
+}
+
+/// Reference to `resource`; `system` is left empty unless the resource declares
+/// `meta.source`, so `push_event` can fall back to the bundle's source.
 fn make_reference(resource: &Value) -> Option<ResourceReference> {
     let resource_type = resource.get("resourceType").and_then(Value::as_str)?;
     let id = resource.get("id").and_then(Value::as_str)?.to_string();
     Some(ResourceReference {
-        system: Some("FHIR".to_string()),
+        system: meta_source(resource),
         reference: Some(format!("{resource_type}/{id}")),
         display: resource.get("code").and_then(extract_codeable_text),
     })
@@ -752,6 +1653,22 @@ fn capitalize_first(input: &str) -> String {
     }
 }
 
+/// Allergy name from `code` (text, display or raw code), falling back to the
+/// first `reaction[].substance`.
+fn allergy_label(resource: &Value) -> Option<String> {
+    resource
+        .get("code")
+        .and_then(extract_codeable_text)
+        .or_else(|| {
+            resource
+                .get("reaction")?
+                .as_array()?
+                .iter()
+                .filter_map(|reaction| reaction.get("substance"))
+                .find_map(extract_codeable_text)
+        })
+}
+
 fn summarize_reactions(resource: &Value) -> Option<String> {
     let reactions = resource.get("reaction")?.as_array()?;
     let mut parts = Vec::new();
@@ -771,6 +1688,48 @@ fn summarize_reactions(resource: &Value) -> Option<String> {
     }
 }
 
+/// One structured reaction per manifestation, keeping the severity, substance
+/// and exposure route of the reaction it belongs to.
+fn allergy_reactions(resource: &Value) -> Vec<AllergyReaction> {
+    let Some(reactions) = resource.get("reaction").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    let mut structured = Vec::new();
+    for reaction in reactions {
+        let severity = reaction
+            .get("severity")
+            .and_then(Value::as_str)
+            .map(reaction_severity);
+        let substance = reaction.get("substance").and_then(extract_codeable_text);
+        let exposure_route = reaction
+            .get("exposureRoute")
+            .and_then(extract_codeable_text);
+        let manifestations = reaction
+            .get("manifestation")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(extract_codeable_text);
+        for manifestation in manifestations {
+            structured.push(AllergyReaction {
+                manifestation,
+                severity,
+                substance: substance.clone(),
+                exposure_route: exposure_route.clone(),
+            });
+        }
+    }
+    structured
+}
+
+fn reaction_severity(code: &str) -> Severity {
+    match code {
+        "severe" => Severity::Critical,
+        "moderate" => Severity::High,
+        _ => Severity::Moderate,
+    }
+}
+
 fn map_allergy_severity(resource: &Value) -> Severity {
     if let Some(severity) = resource.get("criticality").and_then(Value::as_str) {
         return match severity {
@@ -783,12 +1742,7 @@ fn map_allergy_severity(resource: &Value) -> Severity {
     if let Some(reactions) = resource.get("reaction").and_then(Value::as_array) {
         for reaction in reactions {
             if let Some(severity) = reaction.get("severity").and_then(Value::as_str) {
-                return match severity {
-                    "severe" => Severity::Critical,
-                    "moderate" => Severity::High,
-                    "mild" => Severity::Moderate,
-                    _ => Severity::Moderate,
-                };
+                return reaction_severity(severity);
             }
         }
     }
@@ -826,6 +1780,25 @@ fn summarize_dosage(resource: &Value) -> Option<Vec<String>> {
     }
 }
 
+/// `Some` when any dosage (`dosageInstruction` on requests, `dosage` on
+/// statements) is marked as needed; the inner value is the PRN reason, if coded.
+fn prn_indication(resource: &Value) -> Option<Option<String>> {
+    ["dosageInstruction", "dosage"]
+        .iter()
+        .filter_map(|field| resource.get(*field).and_then(Value::as_array))
+        .flatten()
+        .find_map(|dosage| {
+            if let Some(reason) = dosage.get("asNeededCodeableConcept") {
+                return Some(extract_codeable_text(reason));
+            }
+            dosage
+                .get("asNeededBoolean")
+                .and_then(Value::as_bool)
+                .filter(|as_needed| *as_needed)
+                .map(|_| None)
+        })
+}
+
 fn format_quantity_value(value: &Value) -> Option<String> {
     let magnitude = value.get("value")?.as_f64()?;
     let unit = value.get("unit").and_then(Value::as_str).unwrap_or("");
@@ -848,26 +1821,42 @@ fn format_numeric(value: f64) -> String {
 }
 
 fn extract_datetime(resource: &Value, fields: &[&str]) -> Option<DateTime<Utc>> {
+    find_datetime(resource, fields).map(|(at, _)| at)
+}
+
+/// Like [`extract_datetime`], also reporting how precise the source value was
+/// (`DateTime` when nothing was found).
+fn extract_datetime_with_precision(
+    resource: &Value,
+    fields: &[&str],
+) -> (Option<DateTime<Utc>>, DatePrecision) {
+    match find_datetime(resource, fields) {
+        Some((at, precision)) => (Some(at), precision),
+        None => (None, DatePrecision::DateTime),
+    }
+}
+
+fn find_datetime(resource: &Value, fields: &[&str]) -> Option<(DateTime<Utc>, DatePrecision)> {
     for field in fields {
         let Some(value) = resource.get(*field) else {
             continue;
         };
 
         if let Some(text) = value.as_str() {
-            if let Some(dt) = parse_datetime(text) {
-                return Some(dt);
+            if let Some(dated) = parse_flexible_datetime(text) {
+                return Some(dated);
             }
         }
 
         if let Some(obj) = value.as_object() {
             if let Some(end) = obj.get("end").and_then(Value::as_str) {
-                if let Some(dt) = parse_datetime(end) {
-                    return Some(dt);
+                if let Some(dated) = parse_flexible_datetime(end) {
+                    return Some(dated);
                 }
             }
             if let Some(start) = obj.get("start").and_then(Value::as_str) {
-                if let Some(dt) = parse_datetime(start) {
-                    return Some(dt);
+                if let Some(dated) = parse_flexible_datetime(start) {
+                    return Some(dated);
                 }
             }
         }
@@ -875,10 +1864,118 @@ fn extract_datetime(resource: &Value, fields: &[&str]) -> Option<DateTime<Utc>>
     None
 }
 
+/// Transfer path through `Encounter.location` ("ED → ICU"), ordered by each
+/// location's period start when present and skipping repeated stays.
+fn encounter_location_path(resource: &Value) -> Option<String> {
+    let mut stops: Vec<(Option<DateTime<Utc>>, String)> = resource
+        .get("location")?
+        .as_array()?
+        .iter()
+        .filter_map(|entry| {
+            let display = entry
+                .get("location")
+                .and_then(|location| location.get("display"))
+                .and_then(Value::as_str)?
+                .trim();
+            (!display.is_empty()).then(|| {
+                (
+                    extract_period_bounds(entry, "period").map(|(start, _)| start),
+                    display.to_string(),
+                )
+            })
+        })
+        .collect();
+    if stops.iter().all(|(start, _)| start.is_some()) {
+        stops.sort_by_key(|(start, _)| *start);
+    }
+
+    let mut path: Vec<String> = stops.into_iter().map(|(_, display)| display).collect();
+    path.dedup();
+    (!path.is_empty()).then(|| path.join(" → "))
+}
+
+/// Start and (optional) end of a `Period` field. `None` unless the period has a
+/// parseable start, so callers can fall back to point-in-time extraction.
+fn extract_period_bounds(
+    resource: &Value,
+    field: &str,
+) -> Option<(DateTime<Utc>, Option<DateTime<Utc>>)> {
+    let period = resource.get(field)?.as_object()?;
+    let start = period
+        .get("start")
+        .and_then(Value::as_str)
+        .and_then(parse_datetime)?;
+    let end = period
+        .get("end")
+        .and_then(Value::as_str)
+        .and_then(parse_datetime);
+    Some((start, end))
+}
+
+/// Parse a FHIR `dateTime`/`instant`. Fractional seconds (up to nanoseconds)
+/// and any offset form (`Z`, `+00:00`, `-05:00`) are preserved after
+/// normalising to UTC, so rapid serial readings keep their relative order.
 fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
-    DateTime::parse_from_rfc3339(value)
-        .map(|dt| dt.with_timezone(&Utc))
-        .ok()
+    parse_flexible_datetime(value).map(|(at, _)| at)
+}
+
+/// Parse a full `dateTime`/`instant` or a partial FHIR date ("2019",
+/// "2019-03", "2019-03-14"). Partial dates are normalised to midnight UTC at
+/// the start of the period; the precision says which parts were given.
+fn parse_flexible_datetime(value: &str) -> Option<(DateTime<Utc>, DatePrecision)> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some((dt.with_timezone(&Utc), DatePrecision::DateTime));
+    }
+
+    let value = value.trim();
+    let mut parts = value.split('-');
+    let year = parts.next().filter(|part| part.len() == 4)?;
+    let month = parts.next();
+    let day = parts.next();
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let precision = match (month, day) {
+        (None, _) => DatePrecision::Year,
+        (Some(_), None) => DatePrecision::Month,
+        (Some(_), Some(_)) => DatePrecision::Day,
+    };
+    let date = NaiveDate::from_ymd_opt(
+        year.parse().ok()?,
+        month.map_or(Some(1), |month| month.parse().ok())?,
+        day.map_or(Some(1), |day| day.parse().ok())?,
+    )?;
+    Some((date.and_hms_opt(0, 0, 0)?.and_utc(), precision))
+}
+
+/// Severity from `TimelineConfig::code_severity` for the first `code.coding`
+/// entry whose system and code are listed.
+fn configured_code_severity(resource: &Value, config: &TimelineConfig) -> Option<Severity> {
+    if config.code_severity.is_empty() {
+        return None;
+    }
+    resource
+        .get("code")?
+        .get("coding")?
+        .as_array()?
+        .iter()
+        .find_map(|coding| {
+            let system = coding.get("system").and_then(Value::as_str)?;
+            let code = coding.get("code").and_then(Value::as_str)?;
+            config.severity_for_code(system, code)
+        })
+}
+
+/// `Condition.stage[].summary` texts, in resource order.
+fn condition_stages(resource: &Value) -> Vec<String> {
+    resource
+        .get("stage")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|stage| stage.get("summary").and_then(extract_codeable_text))
+        .collect()
 }
 
 fn map_condition_severity(condition: &str) -> Severity {
@@ -900,6 +1997,36 @@ fn map_condition_severity(condition: &str) -> Severity {
     }
 }
 
+/// High for configured high-acuity procedures (by name keyword or coding code),
+/// Moderate otherwise; procedures that did not happen drop to Low.
+fn map_procedure_severity(name: &str, resource: &Value, config: &TimelineConfig) -> Severity {
+    if matches!(
+        resource.get("status").and_then(Value::as_str),
+        Some("not-done" | "stopped" | "entered-in-error")
+    ) {
+        return Severity::Low;
+    }
+
+    if let Some(severity) = configured_code_severity(resource, config) {
+        return severity;
+    }
+
+    let normalized = name.to_lowercase();
+    let code = resource.get("code");
+    let is_high_acuity = config.high_acuity_procedures.iter().any(|keyword| {
+        let keyword = keyword.trim().to_lowercase();
+        !keyword.is_empty()
+            && (normalized.contains(&keyword)
+                || code.is_some_and(|code| codeable_has_code(code, &keyword)))
+    });
+
+    if is_high_acuity {
+        Severity::High
+    } else {
+        Severity::Moderate
+    }
+}
+
 fn extract_status_code(value: Option<&Value>) -> Option<String> {
     let value = value?;
     if let Some(text) = extract_codeable_text(value) {
@@ -953,16 +2080,20 @@ fn observation_numeric_metadata(
         return (value, unit);
     }
 
+    if let Some(reading) = value_string_reading(resource) {
+        return (Some(reading.value), reading.unit);
+    }
+
     let lower = name.to_lowercase();
     if lower.contains("blood pressure") {
         if let Some((systolic, _)) = parse_blood_pressure_from_detail(detail) {
-            let unit = detail.split_whitespace().skip(1).next().map(str::to_string);
-            return (Some(systolic as f64), unit);
+            let unit = detail.split_whitespace().nth(1).map(str::to_string);
+            return (Some(systolic), unit);
         }
     }
 
     let value = numeric_from_detail(detail);
-    let unit = detail.split_whitespace().skip(1).next().map(str::to_string);
+    let unit = detail.split_whitespace().nth(1).map(str::to_string);
 
     (value, unit)
 }
@@ -984,6 +2115,10 @@ fn summarize_observation_value(resource: &Value) -> Option<String> {
         }
     }
 
+    if let Some(gcs) = parse_gcs(resource) {
+        return Some(gcs.display());
+    }
+
     if let Some(components) = resource.get("component").and_then(Value::as_array) {
         if let Some(bp) = summarize_blood_pressure(components) {
             return Some(bp);
@@ -1009,20 +2144,111 @@ fn summarize_observation_value(resource: &Value) -> Option<String> {
     None
 }
 
+/// Glasgow Coma Scale reported as eye/verbal/motor components.
+struct GcsScore {
+    eye: f64,
+    verbal: f64,
+    motor: f64,
+}
+
+impl GcsScore {
+    fn total(&self) -> f64 {
+        self.eye + self.verbal + self.motor
+    }
+
+    /// Display as "GCS 7 (E2 V2 M3)".
+    fn display(&self) -> String {
+        format!(
+            "GCS {} (E{} V{} M{})",
+            format_numeric(self.total()),
+            format_numeric(self.eye),
+            format_numeric(self.verbal),
+            format_numeric(self.motor)
+        )
+    }
+}
+
+const GCS_TOTAL_LOINC: &str = "9269-2";
+const GCS_EYE_LOINC: &str = "9267-6";
+const GCS_VERBAL_LOINC: &str = "9270-0";
+const GCS_MOTOR_LOINC: &str = "9268-4";
+
+/// Recognise a GCS observation by its code and sum its three components.
+/// Returns `None` unless eye, verbal and motor scores are all present.
+fn parse_gcs(resource: &Value) -> Option<GcsScore> {
+    let code = resource.get("code")?;
+    let title = extract_codeable_text(code)?.to_lowercase();
+    let is_gcs = title.contains("glasgow")
+        || title.contains("gcs")
+        || codeable_has_code(code, GCS_TOTAL_LOINC);
+    if !is_gcs {
+        return None;
+    }
+
+    let (mut eye, mut verbal, mut motor) = (None, None, None);
+    for component in resource.get("component")?.as_array()? {
+        let Some(code) = component.get("code") else {
+            continue;
+        };
+        let label = extract_codeable_text(code)
+            .unwrap_or_default()
+            .to_lowercase();
+        let value = component
+            .get("valueQuantity")
+            .and_then(|quantity| quantity.get("value"))
+            .or_else(|| component.get("valueInteger"))
+            .and_then(Value::as_f64);
+
+        if label.contains("eye") || codeable_has_code(code, GCS_EYE_LOINC) {
+            eye = eye.or(value);
+        } else if label.contains("verbal") || codeable_has_code(code, GCS_VERBAL_LOINC) {
+            verbal = verbal.or(value);
+        } else if label.contains("motor") || codeable_has_code(code, GCS_MOTOR_LOINC) {
+            motor = motor.or(value);
+        }
+    }
+
+    Some(GcsScore {
+        eye: eye?,
+        verbal: verbal?,
+        motor: motor?,
+    })
+}
+
+fn codeable_has_code(value: &Value, code: &str) -> bool {
+    value
+        .get("coding")
+        .and_then(Value::as_array)
+        .is_some_and(|codings| {
+            codings
+                .iter()
+                .any(|coding| coding.get("code").and_then(Value::as_str) == Some(code))
+        })
+}
+
+const BP_SYSTOLIC_LOINC: &str = "8480-6";
+const BP_DIASTOLIC_LOINC: &str = "8462-4";
+
+/// Rebuild "120/80 mmHg" from systolic and diastolic components, recognised by
+/// their label text or, for feeds that send bare codes, their LOINC code.
 fn summarize_blood_pressure(components: &[Value]) -> Option<String> {
     let mut systolic: Option<String> = None;
     let mut diastolic: Option<String> = None;
     let mut unit: Option<String> = None;
 
     for component in components {
-        let label = component
-            .get("code")
+        let code = component.get("code");
+        let label = code
             .and_then(extract_codeable_text)
             .unwrap_or_else(|| "".to_string())
             .to_lowercase();
+        let is_systolic = label.contains("systolic")
+            || code.is_some_and(|code| codeable_has_code(code, BP_SYSTOLIC_LOINC));
+        let is_diastolic = label.contains("diastolic")
+            || code.is_some_and(|code| codeable_has_code(code, BP_DIASTOLIC_LOINC));
 
         if let Some(quantity) = component.get("valueQuantity") {
-            if systolic.is_none() && label.contains("systolic") {
+            if systolic.is_none() && is_systolic {
                 if let Some(value) = format_quantity_value(quantity) {
                     unit = quantity
                         .get("unit")
@@ -1032,7 +2258,7 @@ fn summarize_blood_pressure(components: &[Value]) -> Option<String> {
                 }
             }
 
-            if diastolic.is_none() && label.contains("diastolic") {
+            if diastolic.is_none() && is_diastolic {
                 if let Some(value) = format_quantity_value(quantity) {
                     unit = quantity
                         .get("unit")
@@ -1053,101 +2279,183 @@ fn summarize_blood_pressure(components: &[Value]) -> Option<String> {
     }
 }
 
-fn extract_observation_timestamp(resource: &Value) -> Option<DateTime<Utc>> {
-    extract_datetime(
-        resource,
-        &[
-            "effectiveDateTime",
-            "effectiveInstant",
-            "effectivePeriod",
-            "issued",
-        ],
-    )
+/// `effectiveDateTime`/`effectiveInstant`, then the `period_timing` instant of
+/// `effectivePeriod`, then `issued`.
+fn extract_observation_timestamp(
+    resource: &Value,
+    period_timing: PeriodTiming,
+) -> Option<DateTime<Utc>> {
+    extract_datetime(resource, &["effectiveDateTime", "effectiveInstant"])
+        .or_else(|| {
+            let period = resource.get("effectivePeriod")?;
+            let bound = |key: &str| {
+                period
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .and_then(parse_datetime)
+            };
+            period_timing.instant(bound("start"), bound("end"))
+        })
+        .or_else(|| extract_datetime(resource, &["issued"]))
 }
 
-fn classify_observation(name: &str, resource: &Value, detail: &str) -> Severity {
+/// Severity from interpretation, reference-range text, GCS, blood pressure or
+/// known vital thresholds; anything else gets
+/// `config.unclassified_observation_severity`.
+fn classify_observation(
+    name: &str,
+    resource: &Value,
+    detail: &str,
+    config: &TimelineConfig,
+) -> Severity {
     let normalized = name.to_lowercase();
 
     if let Some(severity) = severity_from_interpretation(resource) {
         return severity;
     }
 
-    if normalized.contains("heart rate") || normalized.contains("pulse") {
-        if let Some(value) = parse_value_quantity(resource) {
-            return match value {
-                v if v >= 140.0 => Severity::Critical,
-                v if v >= 120.0 => Severity::High,
-                v if v <= 40.0 => Severity::Critical,
-                v if v <= 50.0 => Severity::High,
-                _ => Severity::Moderate,
-            };
-        }
-    }
-
-    if normalized.contains("respiratory rate") {
-        if let Some(value) = parse_value_quantity(resource) {
-            return match value {
-                v if v >= 35.0 => Severity::Critical,
-                v if v >= 28.0 => Severity::High,
-                v if v <= 8.0 => Severity::Critical,
-                v if v <= 10.0 => Severity::High,
-                _ => Severity::Moderate,
-            };
-        }
+    if let Some(severity) = severity_from_reference_text(resource) {
+        return severity;
     }
 
-    if normalized.contains("spo2") || normalized.contains("oxygen saturation") {
-        if let Some(value) = parse_value_quantity(resource) {
-            return match value {
-                v if v < 85.0 => Severity::Critical,
-                v if v < 92.0 => Severity::High,
-                _ => Severity::Moderate,
-            };
-        }
+    if let Some(gcs) = parse_gcs(resource) {
+        return classify_gcs(gcs.total());
     }
 
     if normalized.contains("blood pressure") {
         if let Some((sys, dia)) = parse_blood_pressure_from_detail(detail) {
-            if sys >= 200 || dia >= 120 {
-                return Severity::Critical;
-            }
-            if sys >= 180 || dia >= 110 {
-                return Severity::High;
-            }
-            if sys <= 80 || dia <= 50 {
-                return Severity::High;
-            }
-            return Severity::Moderate;
+            return classify_blood_pressure(sys.round(), dia.round());
         }
     }
 
-    if normalized.contains("lactate") {
-        if let Some(value) = parse_value_quantity(resource) {
-            return match value {
-                v if v >= 4.0 => Severity::Critical,
-                v if v >= 2.0 => Severity::High,
-                _ => Severity::Moderate,
-            };
-        }
+    let reading = value_string_reading(resource);
+    let flag = reading.as_ref().and_then(|reading| reading.flag);
+    let measured = parse_value_quantity(resource)
+        .or(reading.map(|reading| reading.value))
+        .and_then(|value| classify_vital(&normalized, value));
+    if let Some(severity) = Severity::most_severe(measured.into_iter().chain(flag)) {
+        return severity;
     }
 
-    Severity::Info
+    config.unclassified_observation_severity
 }
 
+/// Most severe interpretation flag on the observation or any of its
+/// components, so one critical analyte escalates an otherwise normal panel.
 fn severity_from_interpretation(resource: &Value) -> Option<Severity> {
-    let interpretation = resource.get("interpretation")?.as_array()?;
-    for entry in interpretation {
-        if let Some(code) = extract_codeable_text(entry) {
-            let lower = code.to_lowercase();
-            if lower.contains("critical") || lower == "hh" || lower == "ll" {
-                return Some(Severity::Critical);
-            }
-            if lower == "h" || lower == "l" || lower.contains("abnormal") {
-                return Some(Severity::High);
+    let components = resource
+        .get("component")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten();
+    Severity::most_severe(
+        std::iter::once(resource)
+            .chain(components)
+            .filter_map(interpretation_flag),
+    )
+}
+
+fn interpretation_flag(element: &Value) -> Option<Severity> {
+    let interpretation = element.get("interpretation")?.as_array()?;
+    interpretation
+        .iter()
+        .filter_map(extract_codeable_text)
+        .find_map(|code| flag_severity(&code))
+}
+
+/// Severity of an abnormal flag such as "H", "LL" or "Critical high".
+fn flag_severity(code: &str) -> Option<Severity> {
+    let lower = code.to_lowercase();
+    if lower.contains("critical") || lower == "hh" || lower == "ll" {
+        Some(Severity::Critical)
+    } else if lower == "h" || lower == "l" || lower.contains("abnormal") {
+        Some(Severity::High)
+    } else {
+        None
+    }
+}
+
+/// Flag qualitative results whose polarity contradicts a text-only
+/// `referenceRange` (e.g. "Positive" against "Negative") as High.
+fn severity_from_reference_text(resource: &Value) -> Option<Severity> {
+    let value = qualitative_polarity(&observation_value_text(resource)?)?;
+    let mismatch = resource
+        .get("referenceRange")?
+        .as_array()?
+        .iter()
+        .filter_map(|range| range.get("text").and_then(Value::as_str))
+        .filter_map(qualitative_polarity)
+        .any(|expected| expected != value);
+    mismatch.then_some(Severity::High)
+}
+
+/// `Some(true)` for positive/detected wording, `Some(false)` for negative/not
+/// detected, `None` when the text is not a recognised qualitative result.
+fn qualitative_polarity(text: &str) -> Option<bool> {
+    let normalized = text.trim().to_lowercase();
+    if QUALITATIVE_NEGATIVE.contains(&normalized.as_str()) {
+        Some(false)
+    } else if QUALITATIVE_POSITIVE.contains(&normalized.as_str()) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+const QUALITATIVE_NEGATIVE: [&str; 7] = [
+    "negative",
+    "neg",
+    "not detected",
+    "non-reactive",
+    "nonreactive",
+    "absent",
+    "none detected",
+];
+
+const QUALITATIVE_POSITIVE: [&str; 6] = ["positive", "pos", "detected", "reactive", "present", "+"];
+
+/// Number, unit and trailing abnormal flag of a `valueString` result such as
+/// "12.3 (H)" or "6.9 mmol/L (HH)".
+struct ValueStringReading {
+    value: f64,
+    unit: Option<String>,
+    flag: Option<Severity>,
+}
+
+fn value_string_reading(resource: &Value) -> Option<ValueStringReading> {
+    if resource.get("valueQuantity").is_some() {
+        return None;
+    }
+    parse_value_string(resource.get("valueString")?.as_str()?)
+}
+
+/// Parse a number at the start of lab text. Only a parenthesised trailing flag
+/// is stripped, so "5 L" keeps litres as its unit.
+fn parse_value_string(text: &str) -> Option<ValueStringReading> {
+    let text = text.trim();
+    let number_end = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(text.len());
+    let value = text[..number_end].parse::<f64>().ok()?;
+    let rest = &text[number_end..];
+    if !(rest.is_empty() || rest.starts_with(char::is_whitespace) || rest.starts_with('(')) {
+        // "120/80", "1:256" and similar are not single numbers.
+        return None;
+    }
+    let mut rest = rest.trim();
+
+    let mut flag = None;
+    if let Some(open) = rest.rfind('(') {
+        if let Some(code) = rest[open + 1..].strip_suffix(')') {
+            flag = flag_severity(code.trim());
+            if flag.is_some() {
+                rest = rest[..open].trim();
             }
         }
     }
-    None
+
+    let unit = (!rest.is_empty()).then(|| rest.to_string());
+    Some(ValueStringReading { value, unit, flag })
 }
 
 fn parse_value_quantity(resource: &Value) -> Option<f64> {
@@ -1157,16 +2465,19 @@ fn parse_value_quantity(resource: &Value) -> Option<f64> {
     None
 }
 
-fn parse_blood_pressure_from_detail(detail: &str) -> Option<(i32, i32)> {
-    let parts: Vec<&str> = detail.split_whitespace().collect();
-    for part in parts {
-        if let Some((sys, dia)) = part.split_once('/') {
-            let sys_val = sys.parse::<i32>().ok()?;
-            let dia_val = dia.parse::<i32>().ok()?;
-            return Some((sys_val, dia_val));
-        }
-    }
-    None
+/// Parse a `systolic/diastolic` pair from free text such as "120.5/80.2 mmHg"
+/// or "BP 120/80". Returns `None` when either side is not numeric.
+fn parse_blood_pressure_from_detail(detail: &str) -> Option<(f64, f64)> {
+    let token = detail.split_whitespace().find(|part| part.contains('/'))?;
+    let (sys, dia) = token.split_once('/')?;
+    let is_numeric = |c: char| c.is_ascii_digit() || c == '.' || c == '-';
+    let sys_val = sys
+        .trim_start_matches(|c: char| !is_numeric(c))
+        .parse::<f64>()
+        .ok()?;
+    let dia_end = dia.find(|c: char| !is_numeric(c)).unwrap_or(dia.len());
+    let dia_val = dia[..dia_end].parse::<f64>().ok()?;
+    Some((sys_val, dia_val))
 }
 
 fn numeric_from_detail(detail: &str) -> Option<f64> {
@@ -1200,20 +2511,11 @@ fn infer_vital_label(name: &str) -> Option<&'static str> {
 }
 
 fn guess_diagnostic_kind(name: &str, resource: &Value) -> Option<DiagnosticKind> {
-    if observation_category_matches(resource, "vital") {
-        return None;
-    }
-
-    if observation_category_matches(resource, "laboratory")
-        || observation_category_matches(resource, "lab")
-    {
-        return Some(DiagnosticKind::Lab);
-    }
-
-    if observation_category_matches(resource, "imaging")
-        || observation_category_matches(resource, "radiology")
-    {
-        return Some(DiagnosticKind::Imaging);
+    match observation_category(resource) {
+        Some(ObservationCategory::VitalSigns) => return None,
+        Some(ObservationCategory::Laboratory) => return Some(DiagnosticKind::Lab),
+        Some(ObservationCategory::Imaging) => return Some(DiagnosticKind::Imaging),
+        Some(ObservationCategory::Survey) | None => {}
     }
 
     let normalized_tokens = tokenize(name);
@@ -1235,37 +2537,49 @@ fn guess_diagnostic_kind(name: &str, resource: &Value) -> Option<DiagnosticKind>
     None
 }
 
-fn observation_category_matches(resource: &Value, keyword: &str) -> bool {
-    let Some(categories) = resource.get("category").and_then(Value::as_array) else {
-        return false;
-    };
+/// Resolve `Observation.category` (vital-signs, laboratory, imaging, survey).
+fn observation_category(resource: &Value) -> Option<ObservationCategory> {
+    if observation_category_matches(resource, "vital") {
+        return Some(ObservationCategory::VitalSigns);
+    }
+    match category_texts(resource)
+        .into_iter()
+        .find_map(DiagnosticKind::from_category_text)
+    {
+        Some(DiagnosticKind::Lab) => Some(ObservationCategory::Laboratory),
+        Some(DiagnosticKind::Imaging) => Some(ObservationCategory::Imaging),
+        _ if observation_category_matches(resource, "survey") => Some(ObservationCategory::Survey),
+        _ => None,
+    }
+}
 
+fn observation_category_matches(resource: &Value, keyword: &str) -> bool {
     let needle = keyword.to_lowercase();
+    category_texts(resource)
+        .iter()
+        .any(|text| text.to_lowercase().contains(&needle))
+}
 
-    categories.iter().any(|entry| {
-        if let Some(text) = entry.get("text").and_then(Value::as_str) {
-            if text.to_lowercase().contains(&needle) {
-                return true;
-            }
-        }
+/// Every `text`, `coding.display` and `coding.code` of `Observation.category`.
+fn category_texts(resource: &Value) -> Vec<&str> {
+    let Some(categories) = resource.get("category").and_then(Value::as_array) else {
+        return Vec::new();
+    };
 
-        if let Some(codings) = entry.get("coding").and_then(Value::as_array) {
-            for coding in codings {
-                if let Some(code_text) = coding.get("display").and_then(Value::as_str) {
-                    if code_text.to_lowercase().contains(&needle) {
-                        return true;
-                    }
-                }
-                if let Some(code_text) = coding.get("code").and_then(Value::as_str) {
-                    if code_text.to_lowercase().contains(&needle) {
-                        return true;
-                    }
-                }
-            }
+    let mut texts = Vec::new();
+    for entry in categories {
+        texts.extend(entry.get("text").and_then(Value::as_str));
+        for coding in entry
+            .get("coding")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            texts.extend(coding.get("display").and_then(Value::as_str));
+            texts.extend(coding.get("code").and_then(Value::as_str));
         }
-
-        false
-    })
+    }
+    texts
 }
 
 fn tokenize(text: &str) -> Vec<String> {
@@ -1293,6 +2607,45 @@ const LAB_KEYWORDS: [&str; 12] = [
 
 const IMAGING_KEYWORDS: [&str; 6] = ["ct", "cta", "mri", "xray", "ultrasound", "radiograph"];
 
+/// Alert for a vital whose latest `threshold` or more trend points are all
+/// abnormal (High or worse), one level above the most severe of them.
+fn sustained_abnormal(trend: &VitalTrend, threshold: usize) -> Option<CriticalItem> {
+    let metric = if trend.name == "Blood pressure" {
+        "systolic"
+    } else {
+        trend.name.as_str()
+    };
+    let run: Vec<(&VitalTrendPoint, Severity)> = trend
+        .points
+        .iter()
+        .rev()
+        .map_while(|point| {
+            let severity = classify_vital(metric, point.value?)?;
+            severity
+                .is_at_least(Severity::High)
+                .then_some((point, severity))
+        })
+        .collect();
+    if run.len() < threshold {
+        return None;
+    }
+
+    let (latest, _) = run.first()?;
+    let severity = Severity::most_severe(run.iter().map(|(_, severity)| *severity))?.escalate();
+    let reading = latest.label.clone().unwrap_or_default();
+    Some(CriticalItem {
+        label: format!("Sustained abnormal {}", trend.name.to_lowercase()),
+        detail: Some(format!(
+            "{} consecutive abnormal readings, latest {reading}.",
+            run.len()
+        )),
+        severity,
+        recorded_at: latest.recorded_at,
+        reactions: Vec::new(),
+        watched: false,
+    })
+}
+
 fn is_recent_vital(
     anchor: Option<DateTime<Utc>>,
     recorded_at: Option<DateTime<Utc>>,
@@ -0,0 +1,30 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+#[test]
+fn allergies_without_text_fall_back_to_code_or_substance() {
+    let bundle = fs::read_to_string(fixture_path("allergy_fallback_bundle.json"))
+        .expect("Failed to read allergy bundle");
+
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let labels: Vec<&str> = snapshot
+        .critical
+        .allergies
+        .iter()
+        .map(|item| item.label.as_str())
+        .collect();
+    assert!(labels.contains(&"Allergy: 7980"));
+    assert!(labels.contains(&"Allergy: Latex"));
+    assert_eq!(labels.len(), 2);
+
+    assert_eq!(snapshot.warnings.len(), 1);
+    assert!(snapshot.warnings[0].contains("AllergyIntolerance/allergy-empty"));
+}
@@ -0,0 +1,78 @@
+use serde_json::json;
+use timeline_core::{AllergyReaction, Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+#[test]
+fn each_manifestation_becomes_a_structured_reaction() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            { "resource": { "resourceType": "Patient", "id": "pat-1" } },
+            {
+                "resource": {
+                    "resourceType": "AllergyIntolerance",
+                    "id": "allergy-penicillin",
+                    "code": { "text": "Penicillin" },
+                    "reaction": [
+                        {
+                            "substance": { "text": "Amoxicillin" },
+                            "manifestation": [
+                                { "text": "Anaphylaxis" },
+                                { "coding": [{ "display": "Urticaria" }] }
+                            ],
+                            "severity": "severe",
+                            "exposureRoute": { "text": "Oral" }
+                        }
+                    ],
+                    "recordedDate": "2025-10-30T08:00:00Z"
+                }
+            }
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let allergy = &snapshot.critical.allergies[0];
+    assert_eq!(
+        allergy.detail.as_deref(),
+        Some("Reaction: Anaphylaxis, Urticaria.")
+    );
+    let reaction = |manifestation: &str| AllergyReaction {
+        manifestation: manifestation.to_string(),
+        severity: Some(Severity::Critical),
+        substance: Some("Amoxicillin".to_string()),
+        exposure_route: Some("Oral".to_string()),
+    };
+    assert_eq!(
+        allergy.reactions,
+        vec![reaction("Anaphylaxis"), reaction("Urticaria")]
+    );
+}
+
+#[test]
+fn non_allergy_items_have_no_reactions() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "MedicationStatement",
+                    "id": "med-1",
+                    "medicationCodeableConcept": { "text": "Metformin" },
+                    "effectiveDateTime": "2025-10-30T08:00:00Z"
+                }
+            }
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+    assert!(snapshot
+        .critical
+        .medications
+        .iter()
+        .all(|item| item.reactions.is_empty()));
+}
@@ -0,0 +1,44 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+fn heart_rate_bundle() -> serde_json::Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-hr",
+                    "code": { "text": "Heart rate" },
+                    "effectiveDateTime": "2025-10-30T08:00:00Z",
+                    "valueQuantity": { "value": 88, "unit": "bpm" }
+                }
+            }
+        ]
+    })
+}
+
+#[test]
+fn anchor_override_drives_recency_gating() {
+    let stale = TimelineConfig {
+        anchor_override: Some(Utc.with_ymd_and_hms(2025, 11, 2, 8, 0, 0).unwrap()),
+        ..TimelineConfig::default()
+    };
+    let snapshot =
+        summarize_bundle_value(&heart_rate_bundle(), &stale).expect("Snapshot generation failed");
+    assert!(
+        snapshot.critical.recent_vitals.is_empty(),
+        "a vital 72 hours before the export time is not recent"
+    );
+
+    let fresh = TimelineConfig {
+        anchor_override: Some(Utc.with_ymd_and_hms(2025, 10, 30, 12, 0, 0).unwrap()),
+        ..TimelineConfig::default()
+    };
+    let snapshot =
+        summarize_bundle_value(&heart_rate_bundle(), &fresh).expect("Snapshot generation failed");
+    assert_eq!(snapshot.critical.recent_vitals.len(), 1);
+}
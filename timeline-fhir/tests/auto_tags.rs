@@ -0,0 +1,53 @@
+use serde_json::json;
+use timeline_core::{Severity, TagRule, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+#[test]
+fn auto_tag_rule_applies_to_matching_events() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-lactate",
+                    "code": { "text": "Serum lactate" },
+                    "effectiveDateTime": "2025-10-30T12:00:00Z",
+                    "valueQuantity": { "value": 4.6, "unit": "mmol/L" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-temp",
+                    "code": { "text": "Temperature" },
+                    "effectiveDateTime": "2025-10-30T12:05:00Z",
+                    "valueQuantity": { "value": 37.0, "unit": "C" }
+                }
+            }
+        ]
+    });
+    let config = TimelineConfig {
+        tag_rules: vec![TagRule {
+            tag: "relevant-to-sepsis".to_string(),
+            keywords: vec!["LACTATE".to_string()],
+            min_severity: Some(Severity::High),
+        }],
+        ..TimelineConfig::default()
+    };
+
+    let snapshot = summarize_bundle_value(&bundle, &config).expect("Snapshot generation failed");
+
+    let tags = |id: &str| {
+        snapshot
+            .events
+            .iter()
+            .find(|event| event.id == id)
+            .expect("event missing")
+            .tags
+            .clone()
+    };
+    assert_eq!(tags("obs-lactate"), vec!["relevant-to-sepsis".to_string()]);
+    assert!(tags("obs-temp").is_empty());
+}
@@ -0,0 +1,53 @@
+use serde_json::{json, Value};
+use timeline_core::{Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+fn blood_pressure_bundle(id: &str, value: &str) -> Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": id,
+                    "code": { "text": "Blood pressure" },
+                    "effectiveDateTime": "2025-10-30T12:20:00Z",
+                    "valueString": value
+                }
+            }
+        ]
+    })
+}
+
+#[test]
+fn decimal_blood_pressure_is_parsed() {
+    let snapshot = summarize_bundle_value(
+        &blood_pressure_bundle("obs-bp", "120.5/80.2 mmHg"),
+        &TimelineConfig::default(),
+    )
+    .expect("Snapshot generation failed");
+
+    let vital = snapshot
+        .critical
+        .recent_vitals
+        .iter()
+        .find(|item| item.name == "Blood pressure")
+        .expect("Blood pressure vital missing");
+    assert_eq!(vital.numeric_value, Some(120.5));
+    assert_eq!(vital.unit.as_deref(), Some("mmHg"));
+    assert_eq!(vital.severity, Severity::Moderate);
+    assert_eq!(snapshot.events[0].severity, Severity::Moderate);
+}
+
+#[test]
+fn malformed_blood_pressure_is_ignored() {
+    let snapshot = summarize_bundle_value(
+        &blood_pressure_bundle("obs-bp", "120/abc"),
+        &TimelineConfig::default(),
+    )
+    .expect("Snapshot generation failed");
+
+    assert_eq!(snapshot.events.len(), 1);
+    assert_eq!(snapshot.events[0].severity, Severity::Info);
+}
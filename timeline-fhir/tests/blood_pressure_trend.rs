@@ -0,0 +1,54 @@
+use serde_json::{json, Value};
+use timeline_core::{SeriesStats, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+fn blood_pressure(id: &str, effective: &str, value: &str) -> Value {
+    json!({
+        "resource": {
+            "resourceType": "Observation",
+            "id": id,
+            "code": { "text": "Blood pressure" },
+            "effectiveDateTime": effective,
+            "valueString": value
+        }
+    })
+}
+
+#[test]
+fn diastolic_stats_are_reported_for_blood_pressure_series() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            blood_pressure("bp-2", "2025-10-30T10:00:00Z", "150/95 mmHg"),
+            blood_pressure("bp-1", "2025-10-30T08:00:00Z", "132/84 mmHg"),
+            blood_pressure("bp-3", "2025-10-30T12:00:00Z", "124/78 mmHg")
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+    let trend = snapshot
+        .critical
+        .blood_pressure_trend
+        .expect("Blood pressure trend missing");
+
+    assert_eq!(trend.unit.as_deref(), Some("mmHg"));
+    assert_eq!(trend.points.len(), 3);
+    assert_eq!(
+        trend.systolic,
+        Some(SeriesStats {
+            min: 124.0,
+            max: 150.0,
+            latest: 124.0,
+        })
+    );
+    assert_eq!(
+        trend.diastolic,
+        Some(SeriesStats {
+            min: 78.0,
+            max: 95.0,
+            latest: 78.0,
+        })
+    );
+}
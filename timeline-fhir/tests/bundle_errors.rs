@@ -0,0 +1,49 @@
+use serde_json::json;
+use timeline_core::{TimelineConfig, TimelineError};
+use timeline_fhir::{summarize_bundle_str, summarize_bundle_value};
+
+#[test]
+fn non_bundle_resource_is_reported_as_not_a_bundle() {
+    let patient = json!({ "resourceType": "Patient", "id": "pat-1" });
+    let err = summarize_bundle_value(&patient, &TimelineConfig::default()).unwrap_err();
+    assert!(
+        matches!(&err, TimelineError::NotABundle { found } if found == "Patient"),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[test]
+fn malformed_json_is_reported_as_invalid_json() {
+    let err = summarize_bundle_str(
+        r#"{"resourceType": "Bundle", "entry": ["#,
+        &TimelineConfig::default(),
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, TimelineError::InvalidJson(_)),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[test]
+fn declared_pre_r4_version_is_unsupported() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "meta": { "fhirVersion": "3.0.2" },
+        "entry": []
+    });
+    let err = summarize_bundle_value(&bundle, &TimelineConfig::default()).unwrap_err();
+    assert!(
+        matches!(&err, TimelineError::UnsupportedVersion { version } if version == "3.0.2"),
+        "unexpected error: {err:?}"
+    );
+
+    let r4 = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "meta": { "fhirVersion": "4.0.1" },
+        "entry": []
+    });
+    assert!(summarize_bundle_value(&r4, &TimelineConfig::default()).is_ok());
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use std::collections::HashSet;
+use std::fs;
+
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::{snapshot_to_bundle, summarize_bundle_str, summarize_bundle_value};
+
+use common::fixture_path;
+
+#[test]
+fn snapshot_exports_as_collection_bundle_with_one_entry_per_event() {
+    let bundle = fs::read_to_string(fixture_path("emergency_observation_bundle.json"))
+        .expect("Failed to read bundle");
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let exported = snapshot_to_bundle(&snapshot);
+
+    assert_eq!(exported["resourceType"], "Bundle");
+    assert_eq!(exported["type"], "collection");
+    let entries = exported["entry"].as_array().expect("entries");
+    assert_eq!(entries.len(), snapshot.events.len() + 1);
+    assert_eq!(entries[0]["resource"]["resourceType"], "Patient");
+    assert_eq!(entries[0]["resource"]["name"][0]["text"], "Jane Doe");
+
+    let full_urls: HashSet<&str> = entries
+        .iter()
+        .map(|entry| entry["fullUrl"].as_str().expect("fullUrl"))
+        .collect();
+    assert_eq!(full_urls.len(), entries.len(), "fullUrls are unique");
+    for full_url in full_urls {
+        let uuid = full_url
+            .strip_prefix("urn:uuid:")
+            .unwrap_or_else(|| panic!("{full_url} is not a urn:uuid"));
+        let groups: Vec<usize> = uuid.split('-').map(str::len).collect();
+        assert_eq!(groups, vec![8, 4, 4, 4, 12], "{full_url}");
+        assert!(uuid.chars().all(|c| c == '-' || c.is_ascii_hexdigit()));
+    }
+
+    for (entry, event) in entries[1..].iter().zip(&snapshot.events) {
+        let resource = &entry["resource"];
+        let reference = format!(
+            "{}/{}",
+            resource["resourceType"].as_str().expect("resourceType"),
+            resource["id"].as_str().expect("id")
+        );
+        let source = event
+            .source
+            .as_ref()
+            .and_then(|source| source.reference.clone());
+        assert_eq!(
+            Some(reference),
+            source,
+            "entry keeps the original reference"
+        );
+    }
+}
+
+#[test]
+fn snapshot_without_demographics_still_references_a_patient() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Condition",
+                    "id": "cond-ckd",
+                    "code": { "text": "Chronic kidney disease" },
+                    "recordedDate": "2025-10-01"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Coverage",
+                    "id": "cov-medicare",
+                    "status": "active",
+                    "type": { "text": "Medicare" },
+                    "period": { "start": "2025-01-01" }
+                }
+            }
+        ]
+    });
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+    assert!(snapshot.patient.is_none());
+
+    let exported = snapshot_to_bundle(&snapshot);
+
+    let entries = exported["entry"].as_array().expect("entries");
+    assert_eq!(entries.len(), snapshot.events.len() + 1);
+    let patient = &entries[0];
+    assert_eq!(
+        patient["resource"],
+        json!({ "resourceType": "Patient", "id": "patient" })
+    );
+    for entry in &entries[1..] {
+        let resource = &entry["resource"];
+        let subject = if resource["resourceType"] == "AllergyIntolerance" {
+            &resource["patient"]
+        } else {
+            &resource["subject"]
+        };
+        assert_eq!(subject["reference"], patient["fullUrl"], "{resource}");
+    }
+
+    let coverage = entries
+        .iter()
+        .map(|entry| &entry["resource"])
+        .find(|resource| resource["id"] == "cov-medicare")
+        .expect("coverage entry");
+    assert_eq!(coverage["resourceType"], "Basic");
+    assert!(coverage["code"]["text"].is_string());
+}
+
+#[test]
+fn exported_bundle_can_be_summarized_again() {
+    let bundle = fs::read_to_string(fixture_path("emergency_observation_bundle.json"))
+        .expect("Failed to read bundle");
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let reingested =
+        summarize_bundle_value(&snapshot_to_bundle(&snapshot), &TimelineConfig::default())
+            .expect("Re-ingestion failed");
+
+    assert_eq!(reingested.events.len(), snapshot.events.len());
+    assert!(reingested.patient.is_some());
+}
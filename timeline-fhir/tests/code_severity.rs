@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use serde_json::json;
+use timeline_core::{Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+const SNOMED: &str = "http://snomed.info/sct";
+
+fn severity_of(config: &TimelineConfig, id: &str) -> Severity {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Condition",
+                    "id": "cond-pe",
+                    "code": {
+                        "text": "Lung finding",
+                        "coding": [{ "system": SNOMED, "code": "59282003" }]
+                    },
+                    "recordedDate": "2025-10-30T07:00:00Z"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Procedure",
+                    "id": "proc-dressing",
+                    "status": "completed",
+                    "code": {
+                        "text": "Wound dressing change",
+                        "coding": [{ "system": SNOMED, "code": "182531007" }]
+                    },
+                    "performedDateTime": "2025-10-30T09:00:00Z"
+                }
+            }
+        ]
+    });
+    summarize_bundle_value(&bundle, config)
+        .expect("Snapshot generation failed")
+        .events
+        .iter()
+        .find(|event| event.id == id)
+        .map(|event| event.severity)
+        .expect("event missing")
+}
+
+#[test]
+fn configured_code_overrides_inferred_severity() {
+    let default = TimelineConfig::default();
+    assert_eq!(severity_of(&default, "cond-pe"), Severity::Moderate);
+
+    let config = TimelineConfig {
+        code_severity: BTreeMap::from([(
+            SNOMED.to_string(),
+            BTreeMap::from([
+                ("59282003".to_string(), Severity::Critical),
+                ("182531007".to_string(), Severity::Low),
+            ]),
+        )]),
+        ..TimelineConfig::default()
+    };
+    assert_eq!(severity_of(&config, "cond-pe"), Severity::Critical);
+    assert_eq!(severity_of(&config, "proc-dressing"), Severity::Low);
+}
@@ -0,0 +1,6 @@
+//! Helpers shared by the integration tests.
+
+/// Absolute path of a file under `tests/data`.
+pub fn fixture_path(name: &str) -> String {
+    format!("{}/tests/data/{name}", env!("CARGO_MANIFEST_DIR"))
+}
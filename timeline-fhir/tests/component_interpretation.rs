@@ -0,0 +1,24 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+#[test]
+fn critical_component_flag_escalates_normal_panel() {
+    let bundle = fs::read_to_string(fixture_path("component_interpretation_bundle.json"))
+        .expect("Failed to read component interpretation bundle");
+
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let event = snapshot
+        .events
+        .iter()
+        .find(|event| event.id == "obs-electrolytes")
+        .expect("Electrolyte panel event missing");
+    assert_eq!(event.severity, Severity::Critical);
+}
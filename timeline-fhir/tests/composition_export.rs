@@ -0,0 +1,76 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::TimelineConfig;
+use timeline_fhir::{summarize_bundle_str, to_composition};
+
+use common::fixture_path;
+
+#[test]
+fn composition_has_required_fields_and_sections() {
+    let bundle = fs::read_to_string(fixture_path("emergency_observation_bundle.json"))
+        .expect("Failed to read bundle");
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let composition = to_composition(&snapshot);
+
+    assert_eq!(composition["resourceType"], "Composition");
+    assert_eq!(composition["status"], "final");
+    assert_eq!(composition["type"]["coding"][0]["code"], "60591-5");
+    assert_eq!(composition["subject"]["display"], "Jane Doe");
+    assert!(composition["date"].is_string());
+
+    let sections = composition["section"].as_array().expect("sections");
+    let titles: Vec<&str> = sections
+        .iter()
+        .filter_map(|section| section["title"].as_str())
+        .collect();
+    assert_eq!(
+        titles,
+        vec!["Allergies", "Medications", "Problems", "Vitals"]
+    );
+
+    for section in sections {
+        let has_entries = section["entry"].as_array().is_some_and(|e| !e.is_empty());
+        assert!(has_entries || section.get("emptyReason").is_some());
+        assert!(section["text"]["div"]
+            .as_str()
+            .is_some_and(|div| div.starts_with("<div xmlns=")));
+    }
+
+    let vitals = &sections[3]["entry"];
+    assert!(vitals
+        .as_array()
+        .expect("vital entries")
+        .iter()
+        .all(|entry| entry["reference"]
+            .as_str()
+            .is_some_and(|reference| reference.starts_with("Observation/"))));
+}
+
+#[test]
+fn section_with_items_but_no_events_is_not_marked_empty() {
+    let bundle = fs::read_to_string(fixture_path("emergency_observation_bundle.json"))
+        .expect("Failed to read bundle");
+    let mut snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+    assert!(!snapshot.critical.allergies.is_empty());
+    snapshot.events.clear();
+    snapshot.critical.chronic_conditions.clear();
+
+    let composition = to_composition(&snapshot);
+
+    let allergies = &composition["section"][0];
+    assert_eq!(allergies["title"], "Allergies");
+    assert!(allergies["text"]["div"]
+        .as_str()
+        .is_some_and(|div| div.contains("Penicillin")));
+    assert!(allergies.get("entry").is_none());
+    assert!(allergies.get("emptyReason").is_none());
+
+    let problems = &composition["section"][2];
+    assert_eq!(problems["title"], "Problems");
+    assert_eq!(problems["emptyReason"]["coding"][0]["code"], "unavailable");
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use timeline_core::{CriticalItem, Severity, TimelineConfig, TimelineSnapshot};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+fn summarize(config: &TimelineConfig) -> TimelineSnapshot {
+    let bundle = fs::read_to_string(fixture_path("ckd_stage_bundle.json"))
+        .expect("Failed to read CKD stage bundle");
+    summarize_bundle_str(&bundle, config).expect("Snapshot generation failed")
+}
+
+fn condition<'a>(snapshot: &'a TimelineSnapshot, name: &str) -> &'a CriticalItem {
+    snapshot
+        .critical
+        .chronic_conditions
+        .iter()
+        .find(|item| item.label == format!("Chronic condition: {name}"))
+        .expect("condition missing")
+}
+
+#[test]
+fn stage_five_ckd_is_escalated_and_shows_its_stage() {
+    let snapshot = summarize(&TimelineConfig::default());
+
+    let ckd = condition(&snapshot, "Chronic kidney disease");
+    assert_eq!(ckd.severity, Severity::High);
+    assert_eq!(ckd.detail.as_deref(), Some("Stage 5. Status active."));
+
+    let event = snapshot
+        .events
+        .iter()
+        .find(|event| event.id == "cond-ckd")
+        .expect("CKD event missing");
+    assert_eq!(event.severity, Severity::High);
+    assert!(event
+        .detail
+        .as_deref()
+        .unwrap_or_default()
+        .contains("Stage 5"));
+
+    let hypertension = condition(&snapshot, "Hypertension");
+    assert_eq!(hypertension.severity, Severity::Moderate);
+    assert_eq!(
+        hypertension.detail.as_deref(),
+        Some("Stage 1. Status active.")
+    );
+}
+
+#[test]
+fn stage_mapping_is_configurable() {
+    let config = TimelineConfig {
+        condition_stage_severity: BTreeMap::from([("stage 5".to_string(), Severity::Critical)]),
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize(&config);
+    assert_eq!(
+        condition(&snapshot, "Chronic kidney disease").severity,
+        Severity::Critical
+    );
+
+    let config = TimelineConfig {
+        condition_stage_severity: BTreeMap::new(),
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize(&config);
+    assert_eq!(
+        condition(&snapshot, "Chronic kidney disease").severity,
+        Severity::Moderate
+    );
+}
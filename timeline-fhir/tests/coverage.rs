@@ -0,0 +1,73 @@
+use serde_json::json;
+use timeline_fhir::{coverage_report, ResourceCoverage};
+
+#[test]
+fn unsupported_resources_show_zero_contribution() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            { "resource": { "resourceType": "Patient", "id": "pat-1" } },
+            {
+                "resource": {
+                    "resourceType": "Encounter",
+                    "id": "enc-ed",
+                    "status": "in-progress",
+                    "class": { "display": "Emergency" },
+                    "period": { "start": "2025-10-30T08:00:00Z" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Questionnaire",
+                    "id": "q-triage",
+                    "status": "active"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "QuestionnaireResponse",
+                    "id": "qr-1",
+                    "status": "completed",
+                    "authored": "2025-10-30T08:10:00Z"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "QuestionnaireResponse",
+                    "id": "qr-2",
+                    "status": "completed",
+                    "authored": "2025-10-30T08:20:00Z"
+                }
+            }
+        ]
+    });
+
+    let report = coverage_report(&bundle);
+
+    assert_eq!(
+        report.by_type["QuestionnaireResponse"],
+        ResourceCoverage {
+            present: 2,
+            contributing: 0
+        }
+    );
+    assert_eq!(
+        report.by_type["Encounter"],
+        ResourceCoverage {
+            present: 1,
+            contributing: 1
+        }
+    );
+    assert_eq!(report.by_type["Patient"].contributing, 1);
+    assert_eq!(
+        report.unused_types(),
+        vec!["Questionnaire", "QuestionnaireResponse"]
+    );
+}
+
+#[test]
+fn non_bundle_input_yields_an_empty_report() {
+    let report = coverage_report(&json!({ "resourceType": "Patient" }));
+    assert!(report.by_type.is_empty());
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{EventCategory, Severity, TimelineConfig, TimelineSnapshot};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+fn summarize() -> TimelineSnapshot {
+    let bundle = fs::read_to_string(fixture_path("coverage_bundle.json"))
+        .expect("Failed to read coverage bundle");
+    summarize_bundle_str(&bundle, &TimelineConfig::default()).expect("Snapshot generation failed")
+}
+
+#[test]
+fn cancelled_coverage_raises_a_moderate_alert() {
+    let snapshot = summarize();
+
+    let coverage_alerts: Vec<_> = snapshot
+        .critical
+        .alerts
+        .iter()
+        .filter(|item| item.label.starts_with("Coverage:"))
+        .collect();
+    assert_eq!(coverage_alerts.len(), 1);
+    assert_eq!(coverage_alerts[0].label, "Coverage: Acme Health Plan");
+    assert_eq!(coverage_alerts[0].severity, Severity::Moderate);
+    assert_eq!(
+        coverage_alerts[0].detail.as_deref(),
+        Some("Coverage status cancelled.")
+    );
+}
+
+#[test]
+fn every_coverage_becomes_a_low_severity_event() {
+    let snapshot = summarize();
+
+    let cancelled = snapshot
+        .events
+        .iter()
+        .find(|event| event.id == "cov-acme")
+        .expect("cancelled coverage event missing");
+    assert_eq!(cancelled.category, EventCategory::Other);
+    assert_eq!(cancelled.severity, Severity::Low);
+    assert_eq!(cancelled.title, "Coverage: Acme Health Plan (cancelled)");
+    assert!(cancelled.period_end.is_some());
+
+    let active = snapshot
+        .events
+        .iter()
+        .find(|event| event.id == "cov-medicare")
+        .expect("active coverage event missing");
+    assert_eq!(active.title, "Coverage: Medicare (active)");
+    assert_eq!(active.detail.as_deref(), Some("Plan: Medicare Part A."));
+    assert!(active.occurred_at.is_some());
+}
@@ -0,0 +1,53 @@
+use serde_json::{json, Value};
+use timeline_core::{CriticalItemOrder, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+fn allergy(id: &str, substance: &str, recorded: &str) -> Value {
+    json!({
+        "resource": {
+            "resourceType": "AllergyIntolerance",
+            "id": id,
+            "criticality": "high",
+            "code": { "text": substance },
+            "recordedDate": recorded
+        }
+    })
+}
+
+fn allergy_labels(order: CriticalItemOrder) -> Vec<String> {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            allergy("allergy-latex", "Latex", "2019-04-02T10:00:00Z"),
+            allergy("allergy-peanut", "Peanut", "2025-10-01T10:00:00Z")
+        ]
+    });
+    let config = TimelineConfig {
+        critical_item_order: order,
+        ..TimelineConfig::default()
+    };
+    summarize_bundle_value(&bundle, &config)
+        .expect("Snapshot generation failed")
+        .critical
+        .allergies
+        .into_iter()
+        .map(|item| item.label)
+        .collect()
+}
+
+#[test]
+fn same_severity_allergies_sort_by_recency() {
+    assert_eq!(
+        allergy_labels(CriticalItemOrder::SeverityThenRecency),
+        ["Allergy: Peanut", "Allergy: Latex"]
+    );
+}
+
+#[test]
+fn same_severity_allergies_sort_alphabetically_when_configured() {
+    assert_eq!(
+        allergy_labels(CriticalItemOrder::SeverityThenAlphabetical),
+        ["Allergy: Latex", "Allergy: Peanut"]
+    );
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{CriticalItem, TimelineConfig};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+fn labels(items: &[CriticalItem]) -> Vec<&str> {
+    items.iter().map(|item| item.label.as_str()).collect()
+}
+
+#[test]
+fn problem_list_orders_listed_conditions_before_the_rest() {
+    let bundle = fs::read_to_string(fixture_path("problem_list_bundle.json"))
+        .expect("Failed to read problem list bundle");
+
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    assert_eq!(
+        labels(&snapshot.critical.chronic_conditions),
+        vec![
+            "Chronic condition: Hypertension",
+            "Chronic condition: Chronic kidney disease stage 3",
+            "Chronic condition: Sepsis",
+            "Chronic condition: Urinary tract infection",
+        ]
+    );
+}
+
+#[test]
+fn panels_without_a_list_keep_severity_order() {
+    let bundle = fs::read_to_string(fixture_path("problem_list_bundle.json"))
+        .expect("Failed to read problem list bundle");
+
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    assert_eq!(
+        labels(&snapshot.critical.medications),
+        vec![
+            "Medication: Ceftriaxone 1 g IV",
+            "Medication: Amlodipine 5 mg",
+        ]
+    );
+}
@@ -0,0 +1,67 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use timeline_core::{DatePrecision, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+#[test]
+fn partial_dates_keep_their_precision() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            { "resource": { "resourceType": "Patient", "id": "patient-1" } },
+            {
+                "resource": {
+                    "resourceType": "Condition",
+                    "id": "cond-diabetes",
+                    "code": { "text": "Type 2 diabetes mellitus" },
+                    "onsetDateTime": "2019"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "AllergyIntolerance",
+                    "id": "allergy-penicillin",
+                    "code": { "text": "Penicillin" },
+                    "onsetDateTime": "2019-06"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Procedure",
+                    "id": "proc-cath",
+                    "code": { "text": "Cardiac catheterization" },
+                    "performedDateTime": "2019-06-14T08:30:00Z"
+                }
+            }
+        ]
+    });
+
+    let config = TimelineConfig {
+        clinical_event_days: 3650,
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize_bundle_value(&bundle, &config).expect("Snapshot generation failed");
+    let event = |id: &str| {
+        snapshot
+            .events
+            .iter()
+            .find(|event| event.id == id)
+            .expect("Event missing")
+    };
+
+    let condition = event("cond-diabetes");
+    assert_eq!(
+        condition.occurred_at,
+        Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).single()
+    );
+    assert_eq!(condition.occurred_at_precision, DatePrecision::Year);
+    assert_eq!(
+        event("allergy-penicillin").occurred_at_precision,
+        DatePrecision::Month
+    );
+    assert_eq!(
+        event("proc-cath").occurred_at_precision,
+        DatePrecision::DateTime
+    );
+}
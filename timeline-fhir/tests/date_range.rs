@@ -0,0 +1,126 @@
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+fn observation(id: &str, effective: &str) -> Value {
+    json!({
+        "resource": {
+            "resourceType": "Observation",
+            "id": id,
+            "code": { "text": "Heart rate" },
+            "effectiveDateTime": effective,
+            "valueQuantity": { "value": 88, "unit": "bpm" }
+        }
+    })
+}
+
+fn bundle() -> Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "AllergyIntolerance",
+                    "id": "allergy-1",
+                    "code": { "text": "Penicillin" },
+                    "recordedDate": "2020-01-01T00:00:00Z"
+                }
+            },
+            observation("obs-old", "2025-10-20T08:00:00Z"),
+            observation("obs-mid", "2025-10-29T08:00:00Z"),
+            observation("obs-new", "2025-10-30T12:00:00Z")
+        ]
+    })
+}
+
+#[test]
+fn events_outside_range_are_excluded() {
+    let config = TimelineConfig {
+        from: Some(Utc.with_ymd_and_hms(2025, 10, 29, 0, 0, 0).unwrap()),
+        to: Some(Utc.with_ymd_and_hms(2025, 10, 30, 0, 0, 0).unwrap()),
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize_bundle_value(&bundle(), &config).expect("Snapshot generation failed");
+
+    let observation_ids: Vec<&str> = snapshot
+        .events
+        .iter()
+        .filter(|event| event.id.starts_with("obs-"))
+        .map(|event| event.id.as_str())
+        .collect();
+    assert_eq!(observation_ids, vec!["obs-mid"]);
+
+    assert!(snapshot
+        .critical
+        .allergies
+        .iter()
+        .any(|item| item.label.contains("Penicillin")));
+}
+
+#[test]
+fn open_range_keeps_everything() {
+    let snapshot = summarize_bundle_value(&bundle(), &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let count = snapshot
+        .events
+        .iter()
+        .filter(|event| event.id.starts_with("obs-"))
+        .count();
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn critical_context_outside_range_is_kept() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Condition",
+                    "id": "cond-heart-failure",
+                    "code": { "text": "Heart failure" },
+                    "clinicalStatus": { "coding": [{ "code": "active" }] },
+                    "recordedDate": "2025-10-10T00:00:00Z"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Condition",
+                    "id": "cond-resolved-pneumonia",
+                    "code": { "text": "Pneumonia" },
+                    "clinicalStatus": { "coding": [{ "code": "resolved" }] },
+                    "recordedDate": "2025-10-12T00:00:00Z"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-code-status",
+                    "code": { "text": "Code status" },
+                    "effectiveDateTime": "2025-10-15T09:00:00Z",
+                    "valueCodeableConcept": { "text": "DNR / DNI" }
+                }
+            },
+            observation("obs-mid", "2025-10-29T08:00:00Z")
+        ]
+    });
+    let config = TimelineConfig {
+        from: Some(Utc.with_ymd_and_hms(2025, 10, 29, 0, 0, 0).unwrap()),
+        to: Some(Utc.with_ymd_and_hms(2025, 10, 30, 0, 0, 0).unwrap()),
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize_bundle_value(&bundle, &config).expect("Snapshot generation failed");
+
+    let conditions: Vec<&str> = snapshot
+        .critical
+        .chronic_conditions
+        .iter()
+        .map(|item| item.label.as_str())
+        .collect();
+    assert_eq!(conditions, vec!["Chronic condition: Heart failure"]);
+    assert_eq!(snapshot.critical.code_status.as_deref(), Some("DNR / DNI"));
+}
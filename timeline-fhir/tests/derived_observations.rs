@@ -0,0 +1,48 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{TimelineConfig, TimelineSnapshot};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+fn summarize(config: &TimelineConfig) -> TimelineSnapshot {
+    let bundle = fs::read_to_string(fixture_path("derived_map_bundle.json"))
+        .expect("Failed to read derived MAP bundle");
+    summarize_bundle_str(&bundle, config).expect("Snapshot generation failed")
+}
+
+fn event_ids(snapshot: &TimelineSnapshot) -> Vec<&str> {
+    snapshot
+        .events
+        .iter()
+        .map(|event| event.id.as_str())
+        .collect()
+}
+
+#[test]
+fn derived_observations_are_kept_by_default() {
+    let snapshot = summarize(&TimelineConfig::default());
+
+    assert!(event_ids(&snapshot).contains(&"obs-map"));
+}
+
+#[test]
+fn derived_observation_is_suppressed_when_source_is_present() {
+    let config = TimelineConfig {
+        suppress_derived_observations: true,
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize(&config);
+    let ids = event_ids(&snapshot);
+
+    assert!(ids.contains(&"obs-bp"));
+    assert!(!ids.contains(&"obs-map"));
+    assert!(ids.contains(&"obs-map-orphan"));
+    assert!(snapshot
+        .critical
+        .recent_vitals
+        .iter()
+        .all(|vital| vital.value != "90 mmHg"));
+}
@@ -0,0 +1,60 @@
+use serde_json::json;
+use timeline_core::{TimelineConfig, VitalTrend};
+use timeline_fhir::summarize_bundle_value;
+
+fn heart_rate(id: &str, time: &str, value: f64) -> serde_json::Value {
+    json!({
+        "resource": {
+            "resourceType": "Observation",
+            "id": id,
+            "status": "final",
+            "code": { "text": "Heart rate" },
+            "effectiveDateTime": time,
+            "valueQuantity": { "value": value, "unit": "bpm" }
+        }
+    })
+}
+
+fn heart_rate_trend(entries: Vec<serde_json::Value>, config: &TimelineConfig) -> VitalTrend {
+    let bundle = json!({ "resourceType": "Bundle", "type": "collection", "entry": entries });
+    let snapshot = summarize_bundle_value(&bundle, config).expect("Snapshot generation failed");
+    snapshot
+        .critical
+        .vital_trends
+        .into_iter()
+        .find(|trend| trend.name == "Heart rate")
+        .expect("Heart rate trend")
+}
+
+#[test]
+fn identical_readings_one_second_apart_become_one_point() {
+    let trend = heart_rate_trend(
+        vec![
+            heart_rate("hr-1", "2025-10-30T08:00:00Z", 112.0),
+            heart_rate("hr-1-dup", "2025-10-30T08:00:01Z", 112.0),
+        ],
+        &TimelineConfig::default(),
+    );
+    assert_eq!(trend.points.len(), 1);
+}
+
+#[test]
+fn distinct_rapid_readings_are_kept() {
+    let entries = vec![
+        heart_rate("hr-1", "2025-10-30T08:00:00Z", 112.0),
+        heart_rate("hr-2", "2025-10-30T08:00:01Z", 118.0),
+        heart_rate("hr-3", "2025-10-30T08:00:05Z", 112.0),
+    ];
+    assert_eq!(
+        heart_rate_trend(entries.clone(), &TimelineConfig::default())
+            .points
+            .len(),
+        3
+    );
+
+    let wide = TimelineConfig {
+        vital_duplicate_window_seconds: 10,
+        ..TimelineConfig::default()
+    };
+    assert_eq!(heart_rate_trend(entries, &wide).points.len(), 2);
+}
@@ -0,0 +1,27 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+#[test]
+fn encounter_detail_includes_service_and_transfer_path() {
+    let bundle = fs::read_to_string(fixture_path("encounter_transfer_bundle.json"))
+        .expect("Failed to read encounter bundle");
+
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let encounter = snapshot
+        .events
+        .iter()
+        .find(|event| event.id == "enc-sepsis")
+        .expect("encounter event missing");
+    assert_eq!(
+        encounter.detail.as_deref(),
+        Some("Septic shock | Critical care medicine | ED → ICU")
+    );
+}
@@ -0,0 +1,76 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+#[test]
+fn periods_keep_start_and_end_on_events() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "MedicationStatement",
+                    "id": "med-ceftriaxone",
+                    "status": "completed",
+                    "medicationCodeableConcept": { "text": "Ceftriaxone 2 g IV" },
+                    "effectivePeriod": {
+                        "start": "2025-10-25T08:00:00Z",
+                        "end": "2025-10-30T08:00:00Z"
+                    }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Encounter",
+                    "id": "enc-ed",
+                    "status": "in-progress",
+                    "class": { "display": "Emergency" },
+                    "period": { "start": "2025-10-30T06:30:00Z" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "MedicationStatement",
+                    "id": "med-aspirin",
+                    "status": "active",
+                    "medicationCodeableConcept": { "text": "Aspirin 81 mg" },
+                    "effectiveDateTime": "2025-10-29T08:00:00Z"
+                }
+            }
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+    let event = |id: &str| {
+        snapshot
+            .events
+            .iter()
+            .find(|event| event.id == id)
+            .expect("event missing")
+    };
+
+    let ceftriaxone = event("med-ceftriaxone");
+    assert_eq!(
+        ceftriaxone.occurred_at,
+        Some(Utc.with_ymd_and_hms(2025, 10, 25, 8, 0, 0).unwrap())
+    );
+    assert_eq!(
+        ceftriaxone.period_end,
+        Some(Utc.with_ymd_and_hms(2025, 10, 30, 8, 0, 0).unwrap())
+    );
+
+    let encounter = event("enc-ed");
+    assert_eq!(
+        encounter.occurred_at,
+        Some(Utc.with_ymd_and_hms(2025, 10, 30, 6, 30, 0).unwrap())
+    );
+    assert_eq!(
+        encounter.period_end, None,
+        "ongoing encounter has no end yet"
+    );
+
+    assert_eq!(event("med-aspirin").period_end, None);
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_with_sink;
+
+use common::fixture_path;
+
+#[test]
+fn sink_receives_each_event_once_in_creation_order() {
+    let raw = fs::read_to_string(fixture_path("emergency_observation_bundle.json"))
+        .expect("Failed to read bundle");
+    let bundle: serde_json::Value = serde_json::from_str(&raw).expect("Invalid bundle JSON");
+
+    let mut seen = Vec::new();
+    let snapshot = summarize_bundle_with_sink(&bundle, &TimelineConfig::default(), |event| {
+        seen.push(event.id.clone())
+    })
+    .expect("Snapshot generation failed");
+
+    let mut produced: Vec<String> = snapshot
+        .events
+        .iter()
+        .map(|event| event.id.clone())
+        .collect();
+    let mut streamed = seen.clone();
+    produced.sort();
+    streamed.sort();
+    assert_eq!(streamed, produced);
+
+    let bundle_order: Vec<&str> = bundle["entry"]
+        .as_array()
+        .expect("entries")
+        .iter()
+        .filter_map(|entry| entry["resource"]["id"].as_str())
+        .filter(|id| seen.iter().any(|seen_id| seen_id == id))
+        .collect();
+    assert_eq!(seen, bundle_order);
+}
@@ -0,0 +1,58 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+fn bundle_with_appointment() -> serde_json::Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            { "resource": { "resourceType": "Patient", "id": "pat-1" } },
+            {
+                "resource": {
+                    "resourceType": "Encounter",
+                    "id": "enc-ed",
+                    "status": "finished",
+                    "class": { "display": "Emergency" },
+                    "period": { "start": "2025-10-30T08:00:00Z" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Encounter",
+                    "id": "enc-follow-up",
+                    "status": "planned",
+                    "class": { "display": "Ambulatory" },
+                    "period": { "start": "2025-11-06T09:00:00Z" }
+                }
+            }
+        ]
+    })
+}
+
+fn event_ids(config: &TimelineConfig) -> Vec<String> {
+    let snapshot = summarize_bundle_value(&bundle_with_appointment(), config)
+        .expect("Snapshot generation failed");
+    snapshot.events.into_iter().map(|event| event.id).collect()
+}
+
+#[test]
+fn future_events_are_kept_by_default() {
+    let config = TimelineConfig {
+        anchor_override: Some(Utc.with_ymd_and_hms(2025, 10, 30, 12, 0, 0).unwrap()),
+        ..TimelineConfig::default()
+    };
+    assert!(config.include_future_events);
+    assert!(event_ids(&config).contains(&"enc-follow-up".to_string()));
+}
+
+#[test]
+fn future_appointment_is_excluded_when_disabled() {
+    let config = TimelineConfig {
+        anchor_override: Some(Utc.with_ymd_and_hms(2025, 10, 30, 12, 0, 0).unwrap()),
+        include_future_events: false,
+        ..TimelineConfig::default()
+    };
+    assert_eq!(event_ids(&config), vec!["enc-ed".to_string()]);
+}
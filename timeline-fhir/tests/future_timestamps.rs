@@ -0,0 +1,51 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+#[test]
+fn far_future_observation_does_not_become_anchor() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            { "resource": { "resourceType": "Patient", "id": "pat-1" } },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-hr",
+                    "code": { "text": "Heart rate" },
+                    "effectiveDateTime": "2025-10-30T12:00:00Z",
+                    "valueQuantity": { "value": 88, "unit": "bpm" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-typo",
+                    "code": { "text": "Respiratory rate" },
+                    "effectiveDateTime": "2099-10-30T12:00:00Z",
+                    "valueQuantity": { "value": 18, "unit": "breaths/min" }
+                }
+            }
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    // The heart rate is only "recent" when the anchor is its own timestamp.
+    let heart_rate = snapshot
+        .critical
+        .recent_vitals
+        .iter()
+        .find(|vital| vital.name == "Heart rate")
+        .expect("Heart rate should stay recent");
+    assert_eq!(
+        heart_rate.recorded_at,
+        Some(Utc.with_ymd_and_hms(2025, 10, 30, 12, 0, 0).unwrap())
+    );
+
+    assert_eq!(snapshot.warnings.len(), 1);
+    assert!(snapshot.warnings[0].contains("Observation/obs-typo"));
+}
@@ -0,0 +1,25 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+#[test]
+fn gcs_components_are_summed_and_classified() {
+    let bundle = fs::read_to_string(fixture_path("gcs_observation_bundle.json"))
+        .expect("Failed to read GCS bundle");
+
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let event = snapshot
+        .events
+        .iter()
+        .find(|event| event.id == "obs-gcs")
+        .expect("GCS event missing");
+    assert_eq!(event.detail.as_deref(), Some("GCS 7 (E2 V2 M3)"));
+    assert_eq!(event.severity, Severity::Critical);
+}
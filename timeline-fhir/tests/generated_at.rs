@@ -0,0 +1,20 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+#[test]
+fn configured_generation_time_is_preserved_verbatim() {
+    let generated_at = Utc.with_ymd_and_hms(2024, 2, 29, 23, 59, 59).unwrap();
+    let config = TimelineConfig {
+        generated_at: Some(generated_at),
+        ..TimelineConfig::default()
+    };
+    let bundle = json!({ "resourceType": "Bundle", "type": "collection", "entry": [] });
+
+    let snapshot = summarize_bundle_value(&bundle, &config).expect("Snapshot generation failed");
+    assert_eq!(snapshot.generated_at, generated_at);
+
+    let serialized = serde_json::to_value(&snapshot).expect("Snapshot serialization failed");
+    assert_eq!(serialized["generated_at"], "2024-02-29T23:59:59Z");
+}
@@ -1,41 +1,57 @@
+mod common;
+
 use std::fs;
 
+use chrono::{TimeZone, Utc};
 use serde_json::Value;
 use timeline_core::TimelineConfig;
 use timeline_fhir::summarize_bundle_str;
 
-fn fixture_path(name: &str) -> String {
-    format!("{}/tests/data/{name}", env!("CARGO_MANIFEST_DIR"))
-}
+use common::fixture_path;
 
 #[test]
 fn observation_bundle_matches_golden() {
     let bundle = fs::read_to_string(fixture_path("emergency_observation_bundle.json"))
         .expect("Failed to read sample bundle");
 
-    let snapshot =
-        summarize_bundle_str(&bundle, &TimelineConfig::default()).expect("Snapshot generation failed");
+    let config = TimelineConfig {
+        generated_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, 12, 0, 0).unwrap()),
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize_bundle_str(&bundle, &config).expect("Snapshot generation failed");
 
     let mut actual = serde_json::to_value(snapshot).expect("Snapshot serialization failed");
-    normalize_dynamic_fields(&mut actual);
+    normalize_age(&mut actual);
 
-    let expected =
-        fs::read_to_string(fixture_path("emergency_observation_expected_snapshot.json"))
-            .expect("Failed to read golden snapshot");
+    let expected = fs::read_to_string(fixture_path("emergency_observation_expected_snapshot.json"))
+        .expect("Failed to read golden snapshot");
 
-    let mut expected_value: Value = serde_json::from_str(&expected).expect("Invalid golden snapshot");
-    normalize_dynamic_fields(&mut expected_value);
+    let mut expected_value: Value =
+        serde_json::from_str(&expected).expect("Invalid golden snapshot");
+    normalize_age(&mut expected_value);
 
     assert_eq!(actual, expected_value);
 }
 
-fn normalize_dynamic_fields(value: &mut Value) {
-    if let Some(obj) = value.as_object_mut() {
-        if obj.contains_key("generated_at") {
-            obj.insert(
-                "generated_at".to_string(),
-                Value::String("__DYNAMIC_TIMESTAMP__".to_string()),
-            );
+/// The patient's age is computed as of today, so it is masked on both sides.
+fn normalize_age(value: &mut Value) {
+    if let Some(age) = value.pointer_mut("/patient/age") {
+        *age = Value::String("__DYNAMIC_AGE__".to_string());
+    }
+    if let Some(alerts) = value
+        .pointer_mut("/critical/alerts")
+        .and_then(Value::as_array_mut)
+    {
+        for alert in alerts.iter_mut() {
+            let is_patient = alert["label"]
+                .as_str()
+                .is_some_and(|label| label.starts_with("Patient:"));
+            let Some(detail) = alert.get_mut("detail").filter(|_| is_patient) else {
+                continue;
+            };
+            if let Some((_, rest)) = detail.as_str().and_then(|text| text.split_once(" | ")) {
+                *detail = Value::String(format!("Age __DYNAMIC_AGE__ | {rest}"));
+            }
         }
     }
 }
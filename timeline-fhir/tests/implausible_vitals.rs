@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+fn spo2(id: &str, value: f64, time: &str) -> serde_json::Value {
+    json!({
+        "resource": {
+            "resourceType": "Observation",
+            "id": id,
+            "status": "final",
+            "code": { "text": "SpO2" },
+            "effectiveDateTime": time,
+            "valueQuantity": { "value": value, "unit": "%" }
+        }
+    })
+}
+
+fn bundle() -> serde_json::Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            spo2("spo2-real", 78.0, "2025-10-30T08:00:00Z"),
+            spo2("spo2-glitch", 300.0, "2025-10-30T08:05:00Z")
+        ]
+    })
+}
+
+#[test]
+fn implausible_spo2_is_dropped_with_a_warning() {
+    let snapshot = summarize_bundle_value(&bundle(), &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let event_ids: Vec<&str> = snapshot
+        .events
+        .iter()
+        .map(|event| event.id.as_str())
+        .collect();
+    assert_eq!(event_ids, vec!["spo2-real"]);
+
+    let vital = snapshot
+        .critical
+        .recent_vitals
+        .iter()
+        .find(|vital| vital.name == "SpO2")
+        .expect("SpO2 vital");
+    assert_eq!(vital.numeric_value, Some(78.0));
+
+    let trend = snapshot
+        .critical
+        .vital_trends
+        .iter()
+        .find(|trend| trend.name == "SpO2")
+        .expect("SpO2 trend");
+    let values: Vec<Option<f64>> = trend.points.iter().map(|point| point.value).collect();
+    assert_eq!(values, vec![Some(78.0)]);
+
+    assert!(snapshot
+        .warnings
+        .iter()
+        .any(|warning| warning.contains("implausible SpO2") && warning.contains("spo2-glitch")));
+}
+
+#[test]
+fn empty_range_map_disables_the_check() {
+    let config = TimelineConfig {
+        vital_physiologic_ranges: BTreeMap::new(),
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize_bundle_value(&bundle(), &config).expect("Snapshot generation failed");
+    assert_eq!(snapshot.events.len(), 2);
+}
@@ -0,0 +1,53 @@
+use chrono::{TimeZone, Timelike, Utc};
+use serde_json::{json, Value};
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+fn heart_rate(id: &str, instant: &str, value: u32) -> Value {
+    json!({
+        "resource": {
+            "resourceType": "Observation",
+            "id": id,
+            "code": { "text": "Heart rate" },
+            "effectiveInstant": instant,
+            "valueQuantity": { "value": value, "unit": "bpm" }
+        }
+    })
+}
+
+#[test]
+fn same_second_instants_sort_by_milliseconds() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            heart_rate("hr-late", "2025-10-30T12:00:00.700+00:00", 131),
+            heart_rate("hr-early", "2025-10-30T12:00:00.500Z", 118)
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let ids: Vec<&str> = snapshot
+        .events
+        .iter()
+        .map(|event| event.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["hr-early", "hr-late"]);
+
+    let early = snapshot.events[0].occurred_at.expect("timestamp");
+    assert_eq!(early.nanosecond(), 500_000_000);
+    assert_eq!(
+        early,
+        Utc.with_ymd_and_hms(2025, 10, 30, 12, 0, 0).unwrap() + chrono::Duration::milliseconds(500)
+    );
+
+    let latest = snapshot
+        .critical
+        .recent_vitals
+        .iter()
+        .find(|vital| vital.name == "Heart rate")
+        .expect("Heart rate vital missing");
+    assert_eq!(latest.numeric_value, Some(131.0));
+}
@@ -0,0 +1,52 @@
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_lenient;
+
+#[test]
+fn corrupt_ndjson_line_is_skipped_with_warning() {
+    let input = [
+        r#"{"resourceType":"Observation","id":"obs-hr","code":{"text":"Heart rate"},"effectiveDateTime":"2025-10-30T08:00:00Z","valueQuantity":{"value":88,"unit":"bpm"}}"#,
+        r#"{"resourceType":"Observation","id":"obs-truncated","code":{"text":"Resp"#,
+        r#"{"resourceType":"Condition","id":"cond-sepsis","code":{"text":"Sepsis"},"recordedDate":"2025-10-30T07:30:00Z"}"#,
+    ]
+    .join("\n");
+
+    let snapshot = summarize_bundle_lenient(&input, &TimelineConfig::default())
+        .expect("valid lines should still produce a snapshot");
+
+    let mut ids: Vec<&str> = snapshot
+        .events
+        .iter()
+        .map(|event| event.id.as_str())
+        .collect();
+    ids.sort();
+    assert_eq!(ids, ["cond-sepsis", "obs-hr"]);
+    assert!(
+        snapshot
+            .warnings
+            .iter()
+            .any(|warning| warning.starts_with("Skipped line 2")),
+        "missing skip warning: {:?}",
+        snapshot.warnings
+    );
+}
+
+#[test]
+fn array_of_resources_is_accepted() {
+    let input = r#"[
+        {"resourceType":"Patient","id":"pat-1"},
+        {"resourceType":"Condition","id":"cond-sepsis","code":{"text":"Sepsis"},"recordedDate":"2025-10-30T07:30:00Z"},
+        42
+    ]"#;
+
+    let snapshot = summarize_bundle_lenient(input, &TimelineConfig::default())
+        .expect("array input should produce a snapshot");
+
+    assert_eq!(snapshot.events.len(), 1);
+    assert_eq!(snapshot.warnings, ["Skipped item 2: not a FHIR resource"]);
+}
+
+#[test]
+fn malformed_bundle_still_fails() {
+    let input = "{\n  \"resourceType\": \"Bundle\",\n  \"entry\": [\n";
+    assert!(summarize_bundle_lenient(input, &TimelineConfig::default()).is_err());
+}
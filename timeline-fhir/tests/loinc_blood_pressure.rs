@@ -0,0 +1,40 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{Severity, TimelineConfig, TimelineEvent, TimelineSnapshot};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+fn event<'a>(snapshot: &'a TimelineSnapshot, id: &str) -> &'a TimelineEvent {
+    snapshot
+        .events
+        .iter()
+        .find(|event| event.id == id)
+        .expect("Blood pressure event missing")
+}
+
+#[test]
+fn loinc_only_components_rebuild_blood_pressure() {
+    let bundle = fs::read_to_string(fixture_path("loinc_blood_pressure_bundle.json"))
+        .expect("Failed to read LOINC blood pressure bundle");
+
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let normal = event(&snapshot, "obs-bp-normal");
+    assert_eq!(normal.detail.as_deref(), Some("120/80 mmHg"));
+    assert_eq!(normal.severity, Severity::Moderate);
+
+    let crisis = event(&snapshot, "obs-bp-crisis");
+    assert_eq!(crisis.detail.as_deref(), Some("210/124 mmHg"));
+    assert_eq!(crisis.severity, Severity::Critical);
+
+    let trend = snapshot
+        .critical
+        .blood_pressure_trend
+        .as_ref()
+        .expect("Blood pressure trend missing");
+    assert_eq!(trend.points.len(), 2);
+}
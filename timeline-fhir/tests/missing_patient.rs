@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::{summarize_bundle_value, MISSING_PATIENT_WARNING};
+
+fn heart_rate() -> serde_json::Value {
+    json!({
+        "resource": {
+            "resourceType": "Observation",
+            "id": "obs-hr",
+            "code": { "text": "Heart rate" },
+            "effectiveDateTime": "2025-10-30T08:00:00Z",
+            "valueQuantity": { "value": 88, "unit": "beats/min" }
+        }
+    })
+}
+
+#[test]
+fn patientless_bundle_warns_about_missing_demographics() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [heart_rate()]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    assert!(snapshot.patient.is_none());
+    assert_eq!(snapshot.warnings, [MISSING_PATIENT_WARNING]);
+}
+
+#[test]
+fn multiple_patient_entries_merge_into_most_complete_record() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Patient",
+                    "id": "pat-device",
+                    "gender": "female"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Patient",
+                    "id": "pat-registration",
+                    "name": [{ "given": ["Jane"], "family": "Doe" }],
+                    "birthDate": "1975-04-18"
+                }
+            },
+            heart_rate()
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+    let patient = snapshot.patient.expect("patient demographics missing");
+
+    assert_eq!(patient.name.as_deref(), Some("Jane Doe"));
+    assert_eq!(patient.birth_date, NaiveDate::from_ymd_opt(1975, 4, 18));
+    assert_eq!(patient.gender.as_deref(), Some("female"));
+    assert!(!snapshot
+        .warnings
+        .iter()
+        .any(|warning| warning == MISSING_PATIENT_WARNING));
+}
+
+#[test]
+fn patient_alert_is_emitted_once_from_the_merged_record() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Patient",
+                    "id": "pat-device",
+                    "name": [{ "given": ["Jane"], "family": "Doe" }],
+                    "gender": "female"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Patient",
+                    "id": "pat-registration",
+                    "name": [{ "given": ["Jane"], "family": "Doe" }],
+                    "birthDate": "1975-04-18"
+                }
+            },
+            heart_rate()
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+    let patient_alerts: Vec<_> = snapshot
+        .critical
+        .alerts
+        .iter()
+        .filter(|item| item.label.starts_with("Patient:"))
+        .collect();
+
+    assert_eq!(patient_alerts.len(), 1);
+    let detail = patient_alerts[0].detail.as_deref().unwrap_or_default();
+    assert!(detail.starts_with("Age "), "{detail}");
+    assert!(detail.ends_with("| Female"), "{detail}");
+}
@@ -0,0 +1,58 @@
+use serde_json::json;
+use timeline_core::{ObservationCategory, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+#[test]
+fn laboratory_category_is_carried_onto_event() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-ferritin",
+                    "category": [
+                        {
+                            "coding": [
+                                {
+                                    "system": "http://terminology.hl7.org/CodeSystem/observation-category",
+                                    "code": "laboratory"
+                                }
+                            ]
+                        }
+                    ],
+                    "code": { "text": "Ferritin" },
+                    "effectiveDateTime": "2025-10-30T12:00:00Z",
+                    "valueQuantity": { "value": 12, "unit": "ng/mL" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-note",
+                    "code": { "text": "Pain score" },
+                    "effectiveDateTime": "2025-10-30T12:10:00Z",
+                    "valueQuantity": { "value": 4 }
+                }
+            }
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let category = |id: &str| {
+        snapshot
+            .events
+            .iter()
+            .find(|event| event.id == id)
+            .expect("event missing")
+            .observation_category
+    };
+    assert_eq!(
+        category("obs-ferritin"),
+        Some(ObservationCategory::Laboratory)
+    );
+    assert_eq!(category("obs-note"), None);
+}
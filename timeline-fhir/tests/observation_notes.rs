@@ -0,0 +1,61 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{TimelineConfig, TimelineSnapshot};
+use timeline_fhir::{snapshot_to_bundle, summarize_bundle_str};
+
+use common::fixture_path;
+
+fn summarize() -> TimelineSnapshot {
+    let bundle = fs::read_to_string(fixture_path("observation_note_bundle.json"))
+        .expect("Failed to read observation note bundle");
+    summarize_bundle_str(&bundle, &TimelineConfig::default()).expect("Snapshot generation failed")
+}
+
+#[test]
+fn observation_notes_are_kept_alongside_the_value() {
+    let snapshot = summarize();
+
+    let noted = snapshot
+        .events
+        .iter()
+        .find(|event| event.id == "obs-hr-agitated")
+        .expect("noted observation missing");
+    assert_eq!(noted.detail.as_deref(), Some("138 /min"));
+    assert_eq!(
+        noted.notes,
+        vec![
+            "Patient was agitated during measurement".to_string(),
+            "Repeat after calming: see 08:40 reading".to_string(),
+        ]
+    );
+
+    let plain = snapshot
+        .events
+        .iter()
+        .find(|event| event.id == "obs-hr-calm")
+        .expect("plain observation missing");
+    assert!(plain.notes.is_empty());
+
+    let json = serde_json::to_string(&snapshot).expect("snapshot serializes");
+    assert!(json.contains("Patient was agitated during measurement"));
+}
+
+#[test]
+fn exported_observation_carries_its_notes() {
+    let bundle = snapshot_to_bundle(&summarize());
+
+    let observation = bundle["entry"]
+        .as_array()
+        .expect("entries")
+        .iter()
+        .map(|entry| &entry["resource"])
+        .find(|resource| resource["id"] == "obs-hr-agitated")
+        .expect("exported observation missing");
+    assert_eq!(
+        observation["note"][0]["text"],
+        "Patient was agitated during measurement"
+    );
+    assert_eq!(observation["note"].as_array().map(Vec::len), Some(2));
+}
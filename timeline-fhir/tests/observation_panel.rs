@@ -0,0 +1,30 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+#[test]
+fn panel_members_are_grouped_under_panel_title() {
+    let bundle = fs::read_to_string(fixture_path("cbc_panel_bundle.json"))
+        .expect("Failed to read panel bundle");
+
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    assert_eq!(snapshot.events.len(), 2);
+    assert!(snapshot.events.iter().all(|event| event.id != "obs-cbc"));
+
+    for id in ["obs-wbc", "obs-platelets"] {
+        let event = snapshot
+            .events
+            .iter()
+            .find(|event| event.id == id)
+            .expect("Panel member event missing");
+        assert_eq!(event.panel.as_deref(), Some("CBC panel"));
+        assert!(event.detail.is_some());
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use timeline_core::{PeriodTiming, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+fn occurred_at(timing: PeriodTiming) -> Option<chrono::DateTime<Utc>> {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "spo2-continuous",
+                    "status": "final",
+                    "code": { "text": "SpO2" },
+                    "effectivePeriod": {
+                        "start": "2025-10-30T08:00:00Z",
+                        "end": "2025-10-30T12:00:00Z"
+                    },
+                    "valueQuantity": { "value": 94, "unit": "%" }
+                }
+            }
+        ]
+    });
+    let config = TimelineConfig {
+        observation_period_timing: timing,
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize_bundle_value(&bundle, &config).expect("Snapshot generation failed");
+    snapshot.events[0].occurred_at
+}
+
+#[test]
+fn period_observation_uses_the_configured_instant() {
+    let at = |hour| Some(Utc.with_ymd_and_hms(2025, 10, 30, hour, 0, 0).unwrap());
+
+    assert_eq!(
+        TimelineConfig::default().observation_period_timing,
+        PeriodTiming::Start
+    );
+    assert_eq!(occurred_at(PeriodTiming::Start), at(8));
+    assert_eq!(occurred_at(PeriodTiming::End), at(12));
+    assert_eq!(occurred_at(PeriodTiming::Midpoint), at(10));
+}
+
+#[test]
+fn open_period_falls_back_to_its_start() {
+    let start = Utc.with_ymd_and_hms(2025, 10, 30, 8, 0, 0).unwrap();
+    assert_eq!(PeriodTiming::End.instant(Some(start), None), Some(start));
+    assert_eq!(
+        PeriodTiming::Midpoint.instant(Some(start), None),
+        Some(start)
+    );
+}
@@ -0,0 +1,63 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+fn vital(id: &str, text: &str, at: &str, value: f64, unit: &str) -> serde_json::Value {
+    json!({
+        "resource": {
+            "resourceType": "Observation",
+            "id": id,
+            "category": [{
+                "coding": [{
+                    "system": "http://terminology.hl7.org/CodeSystem/observation-category",
+                    "code": "vital-signs"
+                }]
+            }],
+            "code": { "text": text },
+            "effectiveDateTime": at,
+            "valueQuantity": { "value": value, "unit": unit }
+        }
+    })
+}
+
+#[test]
+fn stale_vitals_are_counted_when_none_are_recent() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            vital("obs-hr", "Heart rate", "2025-10-28T08:00:00Z", 88.0, "beats/min"),
+            vital("obs-rr", "Respiratory rate", "2025-10-28T08:05:00Z", 16.0, "breaths/min"),
+            vital("obs-temp", "Body temperature", "2025-10-28T08:10:00Z", 37.0, "Cel"),
+            vital("obs-spo2", "Oxygen saturation", "2025-10-28T08:15:00Z", 97.0, "%")
+        ]
+    });
+    let config = TimelineConfig {
+        anchor_override: Some(Utc.with_ymd_and_hms(2025, 10, 30, 12, 0, 0).unwrap()),
+        ..TimelineConfig::default()
+    };
+
+    let snapshot = summarize_bundle_value(&bundle, &config).expect("Snapshot generation failed");
+
+    assert!(snapshot.critical.recent_vitals.is_empty());
+    assert_eq!(snapshot.critical.older_vitals, 4);
+    assert_eq!(snapshot.critical.vital_window_hours, Some(6));
+}
+
+#[test]
+fn recent_vitals_leave_older_count_at_zero() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            vital("obs-hr", "Heart rate", "2025-10-30T08:00:00Z", 88.0, "beats/min")
+        ]
+    });
+
+    let snapshot = summarize_bundle_value(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    assert_eq!(snapshot.critical.recent_vitals.len(), 1);
+    assert_eq!(snapshot.critical.older_vitals, 0);
+}
@@ -0,0 +1,89 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use serde_json::{json, Value};
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+fn patient_bundle() -> Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Patient",
+                    "id": "patient-1",
+                    "name": [{ "given": ["Jane"], "family": "Doe" }],
+                    "gender": "female",
+                    "birthDate": "1975-04-18"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-hr",
+                    "code": { "text": "Heart rate" },
+                    "effectiveDateTime": "2025-10-30T12:20:00Z",
+                    "valueQuantity": { "value": 128, "unit": "bpm" }
+                }
+            }
+        ]
+    })
+}
+
+/// Age today of the fixture patient born 1975-04-18.
+fn expected_age() -> i32 {
+    let today = Utc::now().date_naive();
+    let age = today.year() - 1975;
+    if (today.month(), today.day()) < (4, 18) {
+        age - 1
+    } else {
+        age
+    }
+}
+
+#[test]
+fn patient_alert_is_emitted_by_default() {
+    let snapshot = summarize_bundle_value(&patient_bundle(), &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let patient = snapshot
+        .critical
+        .alerts
+        .iter()
+        .find(|item| item.label == "Patient: Jane Doe")
+        .expect("Patient alert missing");
+    assert_eq!(
+        patient.detail,
+        Some(format!("Age {} | Female", expected_age()))
+    );
+}
+
+#[test]
+fn patient_alert_is_suppressed_when_disabled() {
+    let config = TimelineConfig {
+        emit_patient_alert: false,
+        ..TimelineConfig::default()
+    };
+    let snapshot =
+        summarize_bundle_value(&patient_bundle(), &config).expect("Snapshot generation failed");
+
+    assert!(snapshot
+        .critical
+        .alerts
+        .iter()
+        .all(|item| !item.label.starts_with("Patient:")));
+    assert_eq!(snapshot.events.len(), 1);
+}
+
+#[test]
+fn demographics_populate_patient_field() {
+    let snapshot = summarize_bundle_value(&patient_bundle(), &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let patient = snapshot.patient.expect("Patient demographics missing");
+    assert_eq!(patient.name.as_deref(), Some("Jane Doe"));
+    assert_eq!(patient.age, Some(expected_age()));
+    assert_eq!(patient.gender.as_deref(), Some("female"));
+    assert_eq!(patient.birth_date, NaiveDate::from_ymd_opt(1975, 4, 18));
+    assert!(!patient.deceased);
+}
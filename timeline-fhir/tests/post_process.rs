@@ -0,0 +1,57 @@
+use serde_json::json;
+use timeline_core::{CriticalItem, Severity, TimelineConfig, TimelineSnapshot};
+use timeline_fhir::summarize_bundle_value_with;
+
+fn bundle() -> serde_json::Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            { "resource": { "resourceType": "Patient", "id": "pat-1" } },
+            {
+                "resource": {
+                    "resourceType": "Encounter",
+                    "id": "enc-ed",
+                    "status": "in-progress",
+                    "class": { "display": "Emergency" },
+                    "period": { "start": "2025-10-30T08:00:00Z" }
+                }
+            }
+        ]
+    })
+}
+
+#[test]
+fn post_processor_can_inject_an_alert() {
+    let add_isolation_alert = |snapshot: &mut TimelineSnapshot| {
+        snapshot.critical.alerts.push(CriticalItem {
+            label: "Site alert: Contact isolation".to_string(),
+            detail: None,
+            severity: Severity::High,
+            recorded_at: None,
+            reactions: Vec::new(),
+            watched: false,
+        });
+    };
+
+    let snapshot = summarize_bundle_value_with(
+        &bundle(),
+        &TimelineConfig::default(),
+        Some(Box::new(add_isolation_alert)),
+    )
+    .expect("Snapshot generation failed");
+
+    assert!(snapshot
+        .critical
+        .alerts
+        .iter()
+        .any(|alert| alert.label == "Site alert: Contact isolation"));
+    assert_eq!(snapshot.events.len(), 1);
+}
+
+#[test]
+fn without_post_processor_output_is_unchanged() {
+    let snapshot = summarize_bundle_value_with(&bundle(), &TimelineConfig::default(), None)
+        .expect("Snapshot generation failed");
+    assert!(snapshot.critical.alerts.is_empty());
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{CriticalItem, Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+fn medication<'a>(items: &'a [CriticalItem], label: &str) -> &'a CriticalItem {
+    items
+        .iter()
+        .find(|item| item.label == label)
+        .unwrap_or_else(|| panic!("{label} missing"))
+}
+
+fn medications(config: &TimelineConfig) -> Vec<CriticalItem> {
+    let bundle = fs::read_to_string(fixture_path("prn_medication_bundle.json"))
+        .expect("Failed to read PRN bundle");
+
+    summarize_bundle_str(&bundle, config)
+        .expect("Snapshot generation failed")
+        .critical
+        .medications
+}
+
+#[test]
+fn prn_medications_are_annotated_and_keep_their_severity() {
+    let medications = &medications(&TimelineConfig::default());
+
+    let morphine = medication(medications, "Medication: Morphine 2 mg IV");
+    assert!(morphine
+        .detail
+        .as_deref()
+        .is_some_and(|detail| detail.contains("PRN for pain.")));
+    assert_eq!(morphine.severity, Severity::High);
+
+    let ondansetron = medication(medications, "Medication: Ondansetron 4 mg IV");
+    assert!(ondansetron
+        .detail
+        .as_deref()
+        .is_some_and(|detail| detail.contains("PRN.")));
+    assert_eq!(ondansetron.severity, Severity::High);
+
+    let ceftriaxone = medication(medications, "Medication: Ceftriaxone 1 g IV");
+    assert!(ceftriaxone
+        .detail
+        .as_deref()
+        .is_none_or(|detail| !detail.contains("PRN")));
+    assert_eq!(ceftriaxone.severity, Severity::High);
+}
+
+#[test]
+fn prn_medications_rank_below_scheduled_when_configured() {
+    let config = TimelineConfig {
+        lower_prn_medication_severity: true,
+        ..TimelineConfig::default()
+    };
+    let medications = &medications(&config);
+
+    let morphine = medication(medications, "Medication: Morphine 2 mg IV");
+    assert_eq!(morphine.severity, Severity::Moderate);
+    let ceftriaxone = medication(medications, "Medication: Ceftriaxone 1 g IV");
+    assert_eq!(ceftriaxone.severity, Severity::High);
+}
@@ -0,0 +1,52 @@
+use serde_json::{json, Value};
+use timeline_core::{Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+fn procedure(id: &str, name: &str, status: &str) -> Value {
+    json!({
+        "resource": {
+            "resourceType": "Procedure",
+            "id": id,
+            "status": status,
+            "code": { "text": name },
+            "performedDateTime": "2025-10-30T12:00:00Z"
+        }
+    })
+}
+
+fn severity_of(config: &TimelineConfig, id: &str) -> Severity {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            procedure("proc-intubation", "Endotracheal intubation", "completed"),
+            procedure("proc-dressing", "Wound dressing change", "completed"),
+            procedure("proc-cancelled", "Central line placement", "not-done")
+        ]
+    });
+    let snapshot = summarize_bundle_value(&bundle, config).expect("Snapshot generation failed");
+    snapshot
+        .events
+        .iter()
+        .find(|event| event.id == id)
+        .expect("procedure event missing")
+        .severity
+}
+
+#[test]
+fn high_acuity_procedures_classify_as_high() {
+    let config = TimelineConfig::default();
+    assert_eq!(severity_of(&config, "proc-intubation"), Severity::High);
+    assert_eq!(severity_of(&config, "proc-dressing"), Severity::Moderate);
+    assert_eq!(severity_of(&config, "proc-cancelled"), Severity::Low);
+}
+
+#[test]
+fn high_acuity_keywords_are_configurable() {
+    let config = TimelineConfig {
+        high_acuity_procedures: vec!["dressing".to_string()],
+        ..TimelineConfig::default()
+    };
+    assert_eq!(severity_of(&config, "proc-intubation"), Severity::Moderate);
+    assert_eq!(severity_of(&config, "proc-dressing"), Severity::High);
+}
@@ -0,0 +1,28 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+#[test]
+fn qualitative_result_contradicting_reference_text_is_high() {
+    let bundle = fs::read_to_string(fixture_path("qualitative_reference_bundle.json"))
+        .expect("Failed to read qualitative reference bundle");
+
+    let snapshot = summarize_bundle_str(&bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    let severity_of = |id: &str| {
+        snapshot
+            .events
+            .iter()
+            .find(|event| event.id == id)
+            .map(|event| event.severity)
+            .expect("observation event missing")
+    };
+    assert_eq!(severity_of("obs-cocaine-screen"), Severity::High);
+    assert_eq!(severity_of("obs-hcg-qual"), Severity::Info);
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use timeline_core::{EventCategory, Severity, TimelineConfig, TimelineEvent};
+use timeline_fhir::{
+    summarize_bundle_value, summarize_bundle_with_handlers, ConversionContext, HandlerRegistry,
+};
+
+const SEPSIS_SCREEN_PROFILE: &str = "http://example.org/fhir/StructureDefinition/sepsis-screen";
+
+fn bundle() -> Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            { "resource": { "resourceType": "Patient", "id": "pat-1" } },
+            {
+                "resource": {
+                    "resourceType": "RiskAssessment",
+                    "id": "screen-1",
+                    "meta": { "profile": [SEPSIS_SCREEN_PROFILE] },
+                    "occurrenceDateTime": "2025-10-30T09:00:00Z",
+                    "prediction": [{ "outcome": { "text": "Sepsis" }, "qualitativeRisk": { "text": "high" } }]
+                }
+            }
+        ]
+    })
+}
+
+fn sepsis_screen(resource: &Value, ctx: &mut ConversionContext) {
+    let is_screen = resource["meta"]["profile"]
+        .as_array()
+        .is_some_and(|profiles| {
+            profiles
+                .iter()
+                .any(|profile| profile == SEPSIS_SCREEN_PROFILE)
+        });
+    if !is_screen {
+        ctx.warn("Skipped RiskAssessment without the sepsis screen profile");
+        return;
+    }
+    let occurred_at = resource["occurrenceDateTime"]
+        .as_str()
+        .and_then(|text| text.parse::<DateTime<Utc>>().ok());
+    ctx.push_event(TimelineEvent {
+        occurred_at,
+        severity: Severity::High,
+        ..TimelineEvent::new(
+            resource["id"].as_str().unwrap_or("screen"),
+            EventCategory::Other,
+            "Sepsis screen positive",
+        )
+    });
+}
+
+#[test]
+fn registered_handler_contributes_events_for_unknown_types() {
+    let mut handlers = HandlerRegistry::new();
+    handlers.register("RiskAssessment", sepsis_screen);
+    assert!(handlers.handles("RiskAssessment"));
+
+    let snapshot = summarize_bundle_with_handlers(&bundle(), &TimelineConfig::default(), &handlers)
+        .expect("Snapshot generation failed");
+
+    assert_eq!(snapshot.events.len(), 1);
+    let event = &snapshot.events[0];
+    assert_eq!(event.id, "screen-1");
+    assert_eq!(event.title, "Sepsis screen positive");
+    assert_eq!(event.severity, Severity::High);
+
+    let without_handler = summarize_bundle_value(&bundle(), &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+    assert!(without_handler.events.is_empty());
+}
+
+#[test]
+fn built_in_types_keep_their_built_in_handler() {
+    let mut handlers = HandlerRegistry::new();
+    assert!(handlers.handles("Observation"));
+    handlers.register("Patient", |_: &Value, ctx: &mut ConversionContext| {
+        ctx.warn("custom Patient handler ran");
+    });
+
+    let snapshot = summarize_bundle_with_handlers(&bundle(), &TimelineConfig::default(), &handlers)
+        .expect("Snapshot generation failed");
+    assert!(snapshot.patient.is_some());
+    assert!(snapshot.warnings.is_empty());
+}
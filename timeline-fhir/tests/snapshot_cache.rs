@@ -0,0 +1,65 @@
+#![cfg(feature = "cache")]
+
+mod common;
+
+use std::fs;
+
+use timeline_core::TimelineConfig;
+use timeline_fhir::{summarize_bundle_cached, SnapshotCache};
+
+use common::fixture_path;
+
+fn bundle(name: &str) -> String {
+    fs::read_to_string(fixture_path(name)).expect("Failed to read bundle")
+}
+
+#[test]
+fn identical_input_hits_the_cache() {
+    let mut cache = SnapshotCache::new(4);
+    let bundle = bundle("emergency_observation_bundle.json");
+    let config = TimelineConfig::default();
+
+    let first = summarize_bundle_cached(&mut cache, &bundle, &config).expect("first call");
+    let second = summarize_bundle_cached(&mut cache, &bundle, &config).expect("second call");
+
+    assert_eq!(first, second);
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 1);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn config_change_is_a_miss() {
+    let mut cache = SnapshotCache::new(4);
+    let bundle = bundle("emergency_observation_bundle.json");
+    let wider = TimelineConfig {
+        vital_recent_hours: 48,
+        ..TimelineConfig::default()
+    };
+
+    summarize_bundle_cached(&mut cache, &bundle, &TimelineConfig::default()).expect("default");
+    summarize_bundle_cached(&mut cache, &bundle, &wider).expect("wider window");
+
+    assert_eq!(cache.misses(), 2);
+    assert_eq!(cache.hits(), 0);
+}
+
+#[test]
+fn least_recently_used_entry_is_evicted() {
+    let mut cache = SnapshotCache::new(2);
+    let config = TimelineConfig::default();
+    let emergency = bundle("emergency_observation_bundle.json");
+    let cbc = bundle("cbc_panel_bundle.json");
+    let gcs = bundle("gcs_observation_bundle.json");
+
+    summarize_bundle_cached(&mut cache, &emergency, &config).expect("emergency");
+    summarize_bundle_cached(&mut cache, &cbc, &config).expect("cbc");
+    summarize_bundle_cached(&mut cache, &emergency, &config).expect("emergency again");
+    summarize_bundle_cached(&mut cache, &gcs, &config).expect("gcs evicts cbc");
+    summarize_bundle_cached(&mut cache, &emergency, &config).expect("emergency still cached");
+    summarize_bundle_cached(&mut cache, &cbc, &config).expect("cbc was evicted");
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.hits(), 2);
+    assert_eq!(cache.misses(), 4);
+}
@@ -0,0 +1,73 @@
+use serde_json::json;
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+fn condition(id: &str) -> serde_json::Value {
+    json!({
+        "resourceType": "Condition",
+        "id": id,
+        "code": { "text": "Sepsis" },
+        "recordedDate": "2025-10-30T07:30:00Z"
+    })
+}
+
+fn systems(bundle: &serde_json::Value) -> Vec<(String, Option<String>)> {
+    let snapshot = summarize_bundle_value(bundle, &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+    snapshot
+        .events
+        .iter()
+        .map(|event| {
+            let system = event
+                .source
+                .as_ref()
+                .and_then(|source| source.system.clone());
+            (event.id.clone(), system)
+        })
+        .collect()
+}
+
+#[test]
+fn resource_meta_source_overrides_bundle_source() {
+    let mut from_ehr_b = condition("cond-b");
+    from_ehr_b["meta"] = json!({ "source": "https://ehr-b.example.org" });
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "meta": { "source": "https://ehr-a.example.org" },
+        "entry": [
+            { "resource": condition("cond-a") },
+            { "resource": from_ehr_b }
+        ]
+    });
+
+    let mut systems = systems(&bundle);
+    systems.sort();
+    assert_eq!(
+        systems,
+        vec![
+            (
+                "cond-a".to_string(),
+                Some("https://ehr-a.example.org".to_string())
+            ),
+            (
+                "cond-b".to_string(),
+                Some("https://ehr-b.example.org".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn source_system_defaults_to_fhir_without_meta_source() {
+    let bundle = json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [{ "resource": condition("cond-a") }]
+    });
+
+    assert_eq!(
+        systems(&bundle),
+        vec![("cond-a".to_string(), Some("FHIR".to_string()))]
+    );
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{CriticalItem, Severity, TimelineConfig, TimelineSnapshot};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+fn summarize(config: &TimelineConfig) -> TimelineSnapshot {
+    let bundle = fs::read_to_string(fixture_path("stopped_medication_bundle.json"))
+        .expect("Failed to read stopped medication bundle");
+    summarize_bundle_str(&bundle, config).expect("Snapshot generation failed")
+}
+
+fn labels(items: &[CriticalItem]) -> Vec<&str> {
+    items.iter().map(|item| item.label.as_str()).collect()
+}
+
+#[test]
+fn stopped_medications_are_labeled_discontinued_with_low_severity() {
+    let snapshot = summarize(&TimelineConfig::default());
+    let warfarin = snapshot
+        .critical
+        .medications
+        .iter()
+        .find(|item| item.label == "Medication: Warfarin 5 mg")
+        .expect("Warfarin missing");
+
+    assert_eq!(warfarin.severity, Severity::Low);
+    assert_eq!(warfarin.detail.as_deref(), Some("Discontinued."));
+    assert!(snapshot.critical.discontinued_medications.is_empty());
+}
+
+#[test]
+fn discontinued_medications_can_be_routed_out_of_active_list() {
+    let config = TimelineConfig {
+        separate_discontinued_medications: true,
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize(&config);
+
+    assert_eq!(
+        labels(&snapshot.critical.medications),
+        vec!["Medication: Norepinephrine infusion"]
+    );
+    assert_eq!(
+        labels(&snapshot.critical.discontinued_medications),
+        vec!["Medication: Metformin 500 mg", "Medication: Warfarin 5 mg"]
+    );
+    let metformin = &snapshot.critical.discontinued_medications[0];
+    assert_eq!(
+        metformin.detail.as_deref(),
+        Some("Not taken; discontinued.")
+    );
+    assert_eq!(metformin.severity, Severity::Low);
+
+    let timeline_titles: Vec<&str> = snapshot
+        .events
+        .iter()
+        .map(|event| event.title.as_str())
+        .collect();
+    assert!(timeline_titles.contains(&"Warfarin 5 mg"));
+}
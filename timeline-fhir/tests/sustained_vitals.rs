@@ -0,0 +1,72 @@
+use serde_json::{json, Value};
+use timeline_core::{Severity, TimelineConfig, TimelineSnapshot};
+use timeline_fhir::summarize_bundle_value;
+
+fn heart_rate(id: &str, at: &str, value: u32) -> Value {
+    json!({
+        "resource": {
+            "resourceType": "Observation",
+            "id": id,
+            "status": "final",
+            "code": { "text": "Heart rate" },
+            "effectiveDateTime": at,
+            "valueQuantity": { "value": value, "unit": "/min" }
+        }
+    })
+}
+
+fn summarize(readings: &[u32], sustained_abnormal_readings: Option<u32>) -> TimelineSnapshot {
+    let mut entry = vec![json!({ "resource": { "resourceType": "Patient", "id": "patient-1" } })];
+    entry.extend(readings.iter().enumerate().map(|(index, value)| {
+        heart_rate(
+            &format!("hr-{index}"),
+            &format!("2025-10-30T10:{:02}:00Z", index * 15),
+            *value,
+        )
+    }));
+    let bundle = json!({ "resourceType": "Bundle", "type": "collection", "entry": entry });
+    let config = TimelineConfig {
+        sustained_abnormal_readings,
+        ..TimelineConfig::default()
+    };
+    summarize_bundle_value(&bundle, &config).expect("Snapshot generation failed")
+}
+
+fn heart_rate_severity(snapshot: &TimelineSnapshot) -> Severity {
+    snapshot
+        .critical
+        .recent_vitals
+        .iter()
+        .find(|vital| vital.name == "Heart rate")
+        .map(|vital| vital.severity)
+        .expect("Heart rate vital missing")
+}
+
+#[test]
+fn three_consecutive_tachycardic_readings_escalate_to_critical() {
+    let snapshot = summarize(&[128, 132, 135], Some(3));
+
+    assert_eq!(heart_rate_severity(&snapshot), Severity::Critical);
+    let alert = snapshot
+        .critical
+        .alerts
+        .iter()
+        .find(|item| item.label == "Sustained abnormal heart rate")
+        .expect("Sustained alert missing");
+    assert_eq!(alert.severity, Severity::Critical);
+    assert_eq!(
+        alert.detail.as_deref(),
+        Some("3 consecutive abnormal readings, latest 135 /min.")
+    );
+}
+
+#[test]
+fn interrupted_or_disabled_runs_do_not_escalate() {
+    let interrupted = summarize(&[128, 88, 132, 135], Some(3));
+    assert_eq!(heart_rate_severity(&interrupted), Severity::High);
+    assert!(interrupted.critical.alerts.is_empty());
+
+    let disabled = summarize(&[128, 132, 135], None);
+    assert_eq!(heart_rate_severity(&disabled), Severity::High);
+    assert!(disabled.critical.alerts.is_empty());
+}
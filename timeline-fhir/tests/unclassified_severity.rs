@@ -0,0 +1,60 @@
+use serde_json::json;
+use timeline_core::{Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+fn bundle() -> serde_json::Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-ferritin",
+                    "code": { "text": "Ferritin" },
+                    "effectiveDateTime": "2025-10-30T08:00:00Z",
+                    "valueQuantity": { "value": 120, "unit": "ng/mL" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-lactate",
+                    "code": { "text": "Lactate" },
+                    "effectiveDateTime": "2025-10-30T08:05:00Z",
+                    "valueQuantity": { "value": 4.8, "unit": "mmol/L" },
+                    "interpretation": [{ "coding": [{ "code": "HH" }] }]
+                }
+            }
+        ]
+    })
+}
+
+fn severity_of(config: &TimelineConfig, id: &str) -> Severity {
+    let snapshot = summarize_bundle_value(&bundle(), config).expect("Snapshot generation failed");
+    snapshot
+        .events
+        .iter()
+        .find(|event| event.id == id)
+        .map(|event| event.severity)
+        .unwrap_or_else(|| panic!("{id} missing"))
+}
+
+#[test]
+fn unclassified_observation_defaults_to_info() {
+    assert_eq!(
+        severity_of(&TimelineConfig::default(), "obs-ferritin"),
+        Severity::Info
+    );
+}
+
+#[test]
+fn unclassified_observation_uses_configured_default() {
+    let config = TimelineConfig {
+        unclassified_observation_severity: Severity::Low,
+        ..TimelineConfig::default()
+    };
+
+    assert_eq!(severity_of(&config, "obs-ferritin"), Severity::Low);
+    assert_eq!(severity_of(&config, "obs-lactate"), Severity::Critical);
+}
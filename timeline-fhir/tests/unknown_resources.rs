@@ -0,0 +1,52 @@
+use serde_json::{json, Value};
+use timeline_core::{EventCategory, Severity, TimelineConfig};
+use timeline_fhir::summarize_bundle_value;
+
+fn basic_bundle() -> Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Basic",
+                    "id": "basic-fall-risk",
+                    "code": { "text": "Fall risk assessment" },
+                    "created": "2025-10-30T09:00:00Z"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Basic",
+                    "id": "basic-undated",
+                    "code": { "text": "Undated extension" }
+                }
+            }
+        ]
+    })
+}
+
+#[test]
+fn basic_resource_becomes_other_event_when_enabled() {
+    let config = TimelineConfig {
+        capture_unknown_resources: true,
+        ..TimelineConfig::default()
+    };
+    let snapshot =
+        summarize_bundle_value(&basic_bundle(), &config).expect("Snapshot generation failed");
+
+    assert_eq!(snapshot.events.len(), 1);
+    let event = &snapshot.events[0];
+    assert_eq!(event.id, "basic-fall-risk");
+    assert_eq!(event.category, EventCategory::Other);
+    assert_eq!(event.severity, Severity::Low);
+    assert_eq!(event.title, "Fall risk assessment");
+}
+
+#[test]
+fn unknown_resources_are_ignored_by_default() {
+    let snapshot = summarize_bundle_value(&basic_bundle(), &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    assert!(snapshot.events.is_empty());
+}
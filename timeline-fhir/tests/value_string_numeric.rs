@@ -0,0 +1,56 @@
+mod common;
+
+use std::fs;
+
+use timeline_core::{Severity, TimelineConfig, TimelineSnapshot};
+use timeline_fhir::summarize_bundle_str;
+
+use common::fixture_path;
+
+fn snapshot() -> TimelineSnapshot {
+    let bundle = fs::read_to_string(fixture_path("value_string_numeric_bundle.json"))
+        .expect("Failed to read valueString bundle");
+    summarize_bundle_str(&bundle, &TimelineConfig::default()).expect("Snapshot generation failed")
+}
+
+fn severity_of(snapshot: &TimelineSnapshot, id: &str) -> Severity {
+    snapshot
+        .events
+        .iter()
+        .find(|event| event.id == id)
+        .map(|event| event.severity)
+        .expect("Observation event missing")
+}
+
+#[test]
+fn numeric_value_strings_form_a_trend() {
+    let snapshot = snapshot();
+
+    let trend = snapshot
+        .critical
+        .vital_trends
+        .iter()
+        .find(|trend| trend.name == "Heart rate")
+        .expect("Heart rate trend missing");
+    let values: Vec<Option<f64>> = trend.points.iter().map(|point| point.value).collect();
+    assert_eq!(values, vec![Some(88.0), Some(104.0)]);
+    assert_eq!(trend.unit.as_deref(), Some("/min"));
+}
+
+#[test]
+fn trailing_flag_escalates_severity() {
+    let snapshot = snapshot();
+
+    assert_eq!(severity_of(&snapshot, "obs-hr-1"), Severity::Moderate);
+    assert_eq!(severity_of(&snapshot, "obs-hr-2"), Severity::High);
+    assert_eq!(severity_of(&snapshot, "obs-hgb"), Severity::High);
+
+    let hemoglobin = snapshot
+        .critical
+        .recent_diagnostics
+        .iter()
+        .find(|item| item.name == "Hemoglobin")
+        .expect("Hemoglobin result missing");
+    assert_eq!(hemoglobin.value, "12.3 (H)");
+    assert_eq!(hemoglobin.unit, None);
+}
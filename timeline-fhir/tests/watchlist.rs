@@ -0,0 +1,123 @@
+use serde_json::{json, Value};
+use timeline_core::TimelineConfig;
+use timeline_fhir::summarize_bundle_value;
+
+fn bundle() -> Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-creatinine",
+                    "category": [{ "coding": [{ "code": "laboratory" }] }],
+                    "code": { "text": "Creatinine" },
+                    "effectiveDateTime": "2025-08-01T08:00:00Z",
+                    "valueQuantity": { "value": 2.4, "unit": "mg/dL" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-sodium",
+                    "category": [{ "coding": [{ "code": "laboratory" }] }],
+                    "code": { "text": "Sodium" },
+                    "effectiveDateTime": "2025-08-01T08:00:00Z",
+                    "valueQuantity": { "value": 131, "unit": "mmol/L" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Condition",
+                    "id": "cond-ckd",
+                    "code": { "text": "Chronic kidney disease" },
+                    "recordedDate": "2024-03-01"
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-temp",
+                    "code": { "text": "Temperature" },
+                    "effectiveDateTime": "2025-10-20T08:00:00Z",
+                    "valueQuantity": { "value": 38.4, "unit": "C" }
+                }
+            },
+            {
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "obs-hr",
+                    "code": { "text": "Heart rate" },
+                    "effectiveDateTime": "2025-10-30T12:00:00Z",
+                    "valueQuantity": { "value": 92, "unit": "bpm" }
+                }
+            }
+        ]
+    })
+}
+
+#[test]
+fn watched_items_survive_recency_and_are_flagged() {
+    let config = TimelineConfig {
+        watchlist: vec![
+            "creatinine".to_string(),
+            "Temperature".to_string(),
+            "kidney".to_string(),
+        ],
+        ..TimelineConfig::default()
+    };
+    let snapshot = summarize_bundle_value(&bundle(), &config).expect("Snapshot generation failed");
+
+    let creatinine = snapshot
+        .critical
+        .recent_diagnostics
+        .iter()
+        .find(|item| item.name == "Creatinine")
+        .expect("Watched creatinine should bypass the recency window");
+    assert!(creatinine.watched);
+
+    let temperature = snapshot
+        .critical
+        .recent_vitals
+        .iter()
+        .find(|vital| vital.name == "Temperature")
+        .expect("Watched temperature should bypass the recency window");
+    assert!(temperature.watched);
+
+    let ckd = snapshot
+        .critical
+        .chronic_conditions
+        .iter()
+        .find(|item| item.label == "Chronic condition: Chronic kidney disease")
+        .expect("Watched condition should bypass the recency window");
+    assert!(ckd.watched);
+
+    assert!(snapshot
+        .critical
+        .recent_diagnostics
+        .iter()
+        .all(|item| item.name != "Sodium"));
+
+    let heart_rate = snapshot
+        .critical
+        .recent_vitals
+        .iter()
+        .find(|vital| vital.name == "Heart rate")
+        .expect("Heart rate missing");
+    assert!(!heart_rate.watched);
+}
+
+#[test]
+fn unwatched_old_items_are_dropped() {
+    let snapshot = summarize_bundle_value(&bundle(), &TimelineConfig::default())
+        .expect("Snapshot generation failed");
+
+    assert!(snapshot
+        .critical
+        .recent_vitals
+        .iter()
+        .all(|vital| vital.name != "Temperature"));
+    assert!(snapshot.critical.recent_diagnostics.is_empty());
+    assert!(snapshot.critical.chronic_conditions.is_empty());
+}
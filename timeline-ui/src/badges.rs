@@ -0,0 +1,61 @@
+//! Content of severity badges: the integrator's renderer when one is installed
+//! (e.g. an icon set), otherwise the built-in label.
+//!
+//! The renderer type is left generic so the choice is testable without a DOM;
+//! the view passes its `Callback<Severity, Html>`.
+
+use timeline_core::Severity;
+
+use crate::labels::{severity_label, severity_level};
+
+/// What a severity badge shows.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BadgeContent<T> {
+    /// Output of the installed renderer, used in place of the label span.
+    Custom(T),
+    /// Built-in label with its `data-level` value.
+    Label {
+        text: &'static str,
+        level: &'static str,
+    },
+}
+
+/// Badge for `severity`, from `renderer` when one is installed.
+pub(crate) fn badge_content<T>(
+    severity: Severity,
+    renderer: Option<impl FnOnce(Severity) -> T>,
+) -> BadgeContent<T> {
+    match renderer {
+        Some(render) => BadgeContent::Custom(render(severity)),
+        None => BadgeContent::Label {
+            text: severity_label(severity),
+            level: severity_level(severity),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_renderer_replaces_the_label() {
+        let icon = |severity: Severity| format!("icon-{}", severity_level(severity));
+        assert_eq!(
+            badge_content(Severity::Critical, Some(icon)),
+            BadgeContent::Custom("icon-critical".to_string())
+        );
+    }
+
+    #[test]
+    fn label_is_used_without_a_renderer() {
+        let none: Option<fn(Severity) -> String> = None;
+        assert_eq!(
+            badge_content(Severity::High, none),
+            BadgeContent::Label {
+                text: severity_label(Severity::High),
+                level: "high",
+            }
+        );
+    }
+}
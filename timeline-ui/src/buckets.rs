@@ -0,0 +1,189 @@
+//! Assignment of timeline events to the grid's bucket columns, to chart groups
+//! and to the "by source system" view.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use timeline_core::{EventCategory, ObservationCategory, TimelineEvent};
+
+/// Group label for events without a `source.system`.
+pub(crate) const UNKNOWN_SYSTEM: &str = "Unknown system";
+
+/// Bucket column for an event. Observations trust the source category when the
+/// converter supplied one and fall back to title keywords otherwise (see
+/// [`TimelineEvent::observation_bucket`]).
+pub(crate) fn categorize_event_for_summary(event: &TimelineEvent) -> &'static str {
+    match event.category {
+        EventCategory::Observation => match event.observation_bucket() {
+            Some(ObservationCategory::VitalSigns) => "Vitals",
+            Some(ObservationCategory::Laboratory) => "Labs",
+            Some(ObservationCategory::Imaging) => "Imaging",
+            Some(ObservationCategory::Survey) | None => "Observations",
+        },
+        EventCategory::Medication => "Medications",
+        EventCategory::Condition => "Conditions",
+        EventCategory::Procedure => "Procedures",
+        EventCategory::Encounter => "Encounters",
+        EventCategory::Document => "Documents",
+        EventCategory::Note => "Notes",
+        EventCategory::Other => "Events",
+    }
+}
+
+/// Events grouped by `source.system`, systems in alphabetical order. Events keep
+/// their input order within each group.
+pub(crate) fn group_by_source_system<'a>(
+    events: &[&'a TimelineEvent],
+) -> Vec<(String, Vec<&'a TimelineEvent>)> {
+    let mut groups: BTreeMap<&str, Vec<&'a TimelineEvent>> = BTreeMap::new();
+    for event in events {
+        let system = event
+            .source
+            .as_ref()
+            .and_then(|source| source.system.as_deref())
+            .unwrap_or(UNKNOWN_SYSTEM);
+        groups.entry(system).or_default().push(*event);
+    }
+    groups
+        .into_iter()
+        .map(|(system, events)| (system.to_string(), events))
+        .collect()
+}
+
+/// Chart group of each event, in input order: its `panel` when every numeric
+/// member of that panel reports the same unit, otherwise its own title, so
+/// series on different scales (mmol/L vs mg/dL) never share a y-axis.
+pub(crate) fn chart_group_titles(events: &[&TimelineEvent]) -> Vec<String> {
+    let mut panel_units: BTreeMap<&str, BTreeSet<Option<&str>>> = BTreeMap::new();
+    for event in events {
+        if let (Some(panel), Some(unit)) = (
+            event.panel.as_deref(),
+            event.detail.as_deref().and_then(measurement_unit),
+        ) {
+            panel_units.entry(panel).or_default().insert(unit);
+        }
+    }
+
+    events
+        .iter()
+        .map(|event| match event.panel.as_deref() {
+            Some(panel) if panel_units.get(panel).map_or(0, BTreeSet::len) <= 1 => {
+                panel.to_string()
+            }
+            _ => event.title.clone(),
+        })
+        .collect()
+}
+
+/// Unit of a numeric detail such as "5.4 mmol/L" or "120/80 mmHg" (`Some(None)`
+/// when the number has no unit); `None` when the detail is not a measurement.
+fn measurement_unit(detail: &str) -> Option<Option<&str>> {
+    let detail = detail.trim();
+    let (value, unit) = match detail.split_once(char::is_whitespace) {
+        Some((value, unit)) => (value, Some(unit.trim())),
+        None => (detail, None),
+    };
+    value
+        .split('/')
+        .all(|number| number.parse::<f64>().is_ok())
+        .then_some(unit.filter(|unit| !unit.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use timeline_core::ResourceReference;
+
+    fn observation(
+        title: &str,
+        observation_category: Option<ObservationCategory>,
+    ) -> TimelineEvent {
+        TimelineEvent {
+            observation_category,
+            ..TimelineEvent::new("obs-1", EventCategory::Observation, title)
+        }
+    }
+
+    #[test]
+    fn source_category_overrides_title_keywords() {
+        let event = observation("Ferritin", Some(ObservationCategory::Laboratory));
+        assert_eq!(categorize_event_for_summary(&event), "Labs");
+    }
+
+    #[test]
+    fn uncategorized_observations_fall_back_to_keywords() {
+        assert_eq!(
+            categorize_event_for_summary(&observation("Ferritin", None)),
+            "Observations"
+        );
+        assert_eq!(
+            categorize_event_for_summary(&observation("Serum lactate", None)),
+            "Labs"
+        );
+    }
+
+    #[test]
+    fn events_from_two_systems_form_two_groups() {
+        let from = |id: &str, system: &str| TimelineEvent {
+            id: id.to_string(),
+            source: Some(ResourceReference {
+                system: Some(system.to_string()),
+                reference: Some(format!("Observation/{id}")),
+                display: None,
+            }),
+            ..observation("Heart rate", None)
+        };
+        let a1 = from("a1", "https://ehr-a.example.org");
+        let b1 = from("b1", "https://ehr-b.example.org");
+        let a2 = from("a2", "https://ehr-a.example.org");
+
+        let groups = group_by_source_system(&[&b1, &a1, &a2]);
+
+        let summary: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|(system, events)| {
+                let ids = events.iter().map(|event| event.id.as_str()).collect();
+                (system.as_str(), ids)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("https://ehr-a.example.org", vec!["a1", "a2"]),
+                ("https://ehr-b.example.org", vec!["b1"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn events_without_source_fall_into_unknown_group() {
+        let event = observation("Heart rate", None);
+        let groups = group_by_source_system(&[&event]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].0, UNKNOWN_SYSTEM);
+    }
+
+    #[test]
+    fn panel_members_share_a_chart_only_with_one_unit() {
+        let member = |id: &str, title: &str, panel: &str, detail: &str| TimelineEvent {
+            id: id.to_string(),
+            panel: Some(panel.to_string()),
+            detail: Some(detail.to_string()),
+            ..observation(title, Some(ObservationCategory::Laboratory))
+        };
+        let cbc = [
+            member("wbc", "WBC", "CBC", "11.2 10*3/uL"),
+            member("plt", "Platelets", "CBC", "250 10*3/uL"),
+            member("comment", "Smear comment", "CBC", "Normal morphology"),
+        ];
+        let chemistry = [
+            member("glucose-si", "Glucose", "Chemistry", "5.4 mmol/L"),
+            member("glucose", "Glucose (mass)", "Chemistry", "97 mg/dL"),
+        ];
+        let events: Vec<&TimelineEvent> = cbc.iter().chain(&chemistry).collect();
+
+        assert_eq!(
+            chart_group_titles(&events),
+            vec!["CBC", "CBC", "CBC", "Glucose", "Glucose (mass)"]
+        );
+    }
+}
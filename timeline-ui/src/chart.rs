@@ -0,0 +1,515 @@
+//! Chart helpers that do not depend on the DOM.
+
+use std::borrow::Cow;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use timeline_core::{
+    vitals::classify_vital, EventCategory, Severity, TimelineEvent, VitalTrend, VitalTrendPoint,
+};
+
+use crate::labels::severity_level;
+
+/// Severity of a single plotted value. Series the vital classifier does not
+/// recognise inherit the severity of their group.
+pub(crate) fn point_severity(series_label: &str, value: f64, fallback: Severity) -> Severity {
+    // Blood-pressure trends carry the systolic reading as their numeric value.
+    let metric = if series_label.eq_ignore_ascii_case("blood pressure") {
+        "systolic"
+    } else {
+        series_label
+    };
+    classify_vital(metric, value).unwrap_or(fallback)
+}
+
+/// CSS class colouring a chart point by its own severity.
+pub(crate) fn point_severity_class(series_label: &str, value: f64, fallback: Severity) -> String {
+    format!(
+        "is-{}",
+        severity_level(point_severity(series_label, value, fallback))
+    )
+}
+
+/// Stroke weight of a chart series, heavier for more severe groups.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LineStyle {
+    pub stroke_width: &'static str,
+    pub point_radius: &'static str,
+    /// Shade the area under the line (critical primary series only).
+    pub fill_area: bool,
+}
+
+/// Line weight for a series in a chart whose group has `severity`. Secondary
+/// series (e.g. diastolic) step down one weight and never fill.
+pub(crate) fn line_style(severity: Severity, secondary: bool) -> LineStyle {
+    let weight: u8 = match severity {
+        Severity::Critical => 2,
+        Severity::High => 1,
+        _ => 0,
+    };
+    let weight = if secondary {
+        weight.saturating_sub(1)
+    } else {
+        weight
+    };
+
+    let (stroke_width, point_radius) = match weight {
+        2 => ("3.6", "4.5"),
+        1 => ("3", "3.5"),
+        _ => ("2.4", "3"),
+    };
+
+    LineStyle {
+        stroke_width,
+        point_radius,
+        fill_area: severity == Severity::Critical && !secondary,
+    }
+}
+
+/// Colour tokens shared by every other series, each backed by a
+/// `--timeline-{token}` CSS variable.
+const SERIES_PALETTE: [&str; 6] = [
+    "series-1", "series-2", "series-3", "series-4", "series-5", "series-6",
+];
+
+/// Colour token of the series called `label`. The token depends on the name
+/// alone, so a series keeps its colour in every chart of the snapshot whatever
+/// its position; systolic and diastolic have reserved tokens outside the palette.
+pub(crate) fn series_color(label: &str) -> &'static str {
+    let name = label.trim().to_lowercase();
+    match name.as_str() {
+        "systolic" => "series-systolic",
+        "diastolic" => "series-diastolic",
+        _ => {
+            // FNV-1a: stable across builds and platforms, unlike `DefaultHasher`.
+            let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+            SERIES_PALETTE[(hash % SERIES_PALETTE.len() as u64) as usize]
+        }
+    }
+}
+
+/// Drawing area of a chart, in view-box units (SVG) or pixels (canvas).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PlotArea {
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Time and value scales mapping measurements into a [`PlotArea`], shared by
+/// the SVG charts and the PNG export so both place points identically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ChartScale {
+    pub area: PlotArea,
+    pub start: DateTime<Utc>,
+    /// Seconds covered by the time axis (at least one minute).
+    pub total_seconds: f64,
+    pub axis_min: f64,
+    pub axis_max: f64,
+}
+
+impl ChartScale {
+    /// Scale covering `values` between `start` and `end`. The value axis is
+    /// padded by 10% (or one unit for narrow spans); `None` without finite values.
+    pub(crate) fn new(
+        values: impl IntoIterator<Item = f64>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        area: PlotArea,
+    ) -> Option<Self> {
+        let (mut min_value, mut max_value) = values
+            .into_iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        if !min_value.is_finite() || !max_value.is_finite() {
+            return None;
+        }
+
+        if (max_value - min_value).abs() < f64::EPSILON {
+            let padding = (max_value.abs().max(1.0)) * 0.05;
+            min_value -= padding;
+            max_value += padding;
+        }
+
+        let span = (max_value - min_value).abs();
+        let padding = if span < 5.0 { 1.0 } else { span * 0.1 };
+
+        let mut total_seconds = end.signed_duration_since(start).num_seconds() as f64;
+        if total_seconds.abs() < 60.0 {
+            total_seconds = 60.0;
+        }
+
+        Some(Self {
+            area,
+            start,
+            total_seconds,
+            axis_min: min_value - padding,
+            axis_max: max_value + padding,
+        })
+    }
+
+    /// Horizontal position of `timestamp`, clamped to the plot area.
+    pub(crate) fn x(&self, timestamp: DateTime<Utc>) -> f64 {
+        let offset = timestamp.signed_duration_since(self.start).num_seconds() as f64;
+        let ratio = (offset / self.total_seconds).clamp(0.0, 1.0);
+        self.area.left + ratio * self.area.width
+    }
+
+    /// Vertical position of `value` (larger values higher), clamped to the plot area.
+    pub(crate) fn y(&self, value: f64) -> f64 {
+        let range = (self.axis_max - self.axis_min).max(1e-3);
+        let ratio = ((value - self.axis_min) / range).clamp(0.0, 1.0);
+        self.area.top + (1.0 - ratio) * self.area.height
+    }
+}
+
+/// Raw bytes of a base64 `data:` URL such as the one returned by
+/// `HTMLCanvasElement.toDataURL`. `None` for non-base64 or malformed input.
+pub(crate) fn data_url_bytes(url: &str) -> Option<Vec<u8>> {
+    let (header, payload) = url.strip_prefix("data:")?.split_once(',')?;
+    if !header.ends_with(";base64") {
+        return None;
+    }
+    STANDARD.decode(payload).ok()
+}
+
+/// Approximate advance of one character of `.timeline-chart-tick` text, in
+/// view-box units. Tick labels are small and mostly digits, so a fixed average
+/// is close enough to decide when a label needs shortening.
+pub(crate) const LABEL_CHAR_WIDTH: f64 = 5.6;
+
+/// Chart label shortened to fit its slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FittedLabel {
+    pub text: String,
+    pub truncated: bool,
+}
+
+/// `text` ellipsized so its estimated width stays within `max_width` view-box
+/// units. The ellipsis counts towards the width; at least one character is
+/// kept so the label never disappears entirely.
+pub(crate) fn fit_label(text: &str, max_width: f64) -> FittedLabel {
+    let max_chars = (max_width / LABEL_CHAR_WIDTH).floor().max(0.0) as usize;
+    let length = text.chars().count();
+    if length <= max_chars {
+        return FittedLabel {
+            text: text.to_string(),
+            truncated: false,
+        };
+    }
+    let kept: String = text
+        .chars()
+        .take(max_chars.saturating_sub(1).max(1))
+        .collect();
+    FittedLabel {
+        text: format!("{}…", kept.trim_end()),
+        truncated: true,
+    }
+}
+
+/// Narrowest bar drawn, so same-instant periods stay visible.
+const MIN_BAR_WIDTH: f64 = 2.0;
+
+/// Horizontal extent of a duration bar, in view units from the axis origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DurationBar {
+    pub x: f64,
+    pub width: f64,
+}
+
+/// Medications and encounters that carry both a start and a `period_end`.
+pub(crate) fn renders_as_duration(event: &TimelineEvent) -> bool {
+    matches!(
+        event.category,
+        EventCategory::Medication | EventCategory::Encounter
+    ) && event.occurred_at.is_some()
+        && event.period_end.is_some()
+}
+
+/// Shared time axis spanning every period: earliest start to latest end.
+pub(crate) fn duration_axis(events: &[&TimelineEvent]) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = events.iter().filter_map(|event| event.occurred_at).min()?;
+    let end = events
+        .iter()
+        .filter_map(|event| event.period_end.or(event.occurred_at))
+        .max()?;
+    Some((start, end.max(start)))
+}
+
+/// Position of the `start`..`end` period on an axis `axis_width` units wide.
+/// Periods are clamped to the axis; a zero-length axis puts every bar at the origin.
+pub(crate) fn duration_bar(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    axis: (DateTime<Utc>, DateTime<Utc>),
+    axis_width: f64,
+) -> DurationBar {
+    let (axis_start, axis_end) = axis;
+    let span = axis_end.signed_duration_since(axis_start).num_seconds() as f64;
+    let offset = |at: DateTime<Utc>| {
+        if span <= 0.0 {
+            return 0.0;
+        }
+        let seconds = at.signed_duration_since(axis_start).num_seconds() as f64;
+        (seconds / span).clamp(0.0, 1.0) * axis_width
+    };
+
+    let x = offset(start);
+    let width = (offset(end.max(start)) - x).max(MIN_BAR_WIDTH);
+    DurationBar {
+        x: x.min(axis_width - MIN_BAR_WIDTH).max(0.0),
+        width,
+    }
+}
+
+/// Latest charted point across `trends`, the anchor of the trend window.
+pub(crate) fn trend_anchor(trends: &[VitalTrend]) -> Option<DateTime<Utc>> {
+    trends
+        .iter()
+        .flat_map(|trend| trend.points.iter())
+        .filter_map(|point| point.recorded_at)
+        .max()
+}
+
+/// `trend` limited to points recorded within `window_hours` before `anchor`, so
+/// the chart axis zooms to the window. The snapshot keeps every point; when
+/// fewer than two plottable points fall inside the window the full trend is
+/// charted instead.
+pub(crate) fn windowed_trend(
+    trend: &VitalTrend,
+    anchor: Option<DateTime<Utc>>,
+    window_hours: Option<u32>,
+) -> Cow<'_, VitalTrend> {
+    let (Some(anchor), Some(hours)) = (anchor, window_hours) else {
+        return Cow::Borrowed(trend);
+    };
+    let since = anchor - Duration::hours(i64::from(hours));
+    let points: Vec<VitalTrendPoint> = trend
+        .points
+        .iter()
+        .filter(|point| point.recorded_at.is_some_and(|at| at >= since))
+        .cloned()
+        .collect();
+
+    let plottable = points.iter().filter(|point| point.value.is_some()).count();
+    if plottable < 2 {
+        return Cow::Borrowed(trend);
+    }
+    Cow::Owned(VitalTrend {
+        name: trend.name.clone(),
+        unit: trend.unit.clone(),
+        points,
+    })
+}
+
+/// Timestamped numeric points of `trend`, oldest first, so the last one is the
+/// latest reading whatever the input order.
+pub(crate) fn timed_points(trend: &VitalTrend) -> Vec<(DateTime<Utc>, f64)> {
+    let mut points: Vec<(DateTime<Utc>, f64)> = trend
+        .points
+        .iter()
+        .filter_map(|point| Some((point.recorded_at?, point.value?)))
+        .collect();
+    points.sort_by_key(|(at, _)| *at);
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trend_at(hours_ago: &[i64]) -> VitalTrend {
+        let anchor = DateTime::parse_from_rfc3339("2025-10-30T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        VitalTrend {
+            name: "Heart rate".into(),
+            unit: Some("/min".into()),
+            points: hours_ago
+                .iter()
+                .map(|hours| VitalTrendPoint {
+                    recorded_at: Some(anchor - Duration::hours(*hours)),
+                    value: Some(80.0 + *hours as f64),
+                    label: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn trend_window_excludes_points_older_than_window() {
+        let trend = trend_at(&[24 * 300, 24 * 30, 20, 6, 0]);
+        let anchor = trend_anchor(std::slice::from_ref(&trend));
+        let windowed = windowed_trend(&trend, anchor, Some(24));
+
+        let kept: Vec<f64> = windowed.points.iter().filter_map(|p| p.value).collect();
+        assert_eq!(kept, vec![100.0, 86.0, 80.0]);
+        assert_eq!(trend.points.len(), 5);
+    }
+
+    #[test]
+    fn trend_window_falls_back_to_full_data_below_two_points() {
+        let trend = trend_at(&[24 * 30, 24 * 7, 0]);
+        let anchor = trend_anchor(std::slice::from_ref(&trend));
+        assert_eq!(windowed_trend(&trend, anchor, Some(24)).points.len(), 3);
+        assert_eq!(windowed_trend(&trend, anchor, None).points.len(), 3);
+    }
+
+    #[test]
+    fn critical_spike_gets_critical_point_class() {
+        assert_eq!(
+            point_severity_class("Heart rate", 150.0, Severity::Moderate),
+            "is-critical"
+        );
+        assert_eq!(
+            point_severity_class("Heart rate", 82.0, Severity::Critical),
+            "is-moderate"
+        );
+        assert_eq!(
+            point_severity_class("Systolic", 210.0, Severity::Info),
+            "is-critical"
+        );
+    }
+
+    #[test]
+    fn critical_chart_uses_heavier_stroke() {
+        let critical = line_style(Severity::Critical, false);
+        assert_eq!(critical.stroke_width, "3.6");
+        assert!(critical.fill_area);
+
+        let secondary = line_style(Severity::Critical, true);
+        assert_eq!(secondary.stroke_width, "3");
+        assert!(!secondary.fill_area);
+
+        assert_eq!(line_style(Severity::Info, false).stroke_width, "2.4");
+    }
+
+    #[test]
+    fn chart_scale_maps_time_and_value_into_the_plot_area() {
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2025, 10, 30, 8, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 30, 12, 0, 0).unwrap();
+        let area = PlotArea {
+            left: 50.0,
+            top: 10.0,
+            width: 200.0,
+            height: 100.0,
+        };
+        let scale = ChartScale::new([80.0, 130.0], start, end, area).expect("finite values");
+
+        // 50-unit span padded by 10% on each side.
+        assert_eq!((scale.axis_min, scale.axis_max), (75.0, 135.0));
+        assert_eq!(scale.x(start), 50.0);
+        assert_eq!(
+            scale.x(Utc.with_ymd_and_hms(2025, 10, 30, 10, 0, 0).unwrap()),
+            150.0
+        );
+        assert_eq!(scale.x(end + chrono::Duration::hours(1)), 250.0);
+        assert_eq!(scale.y(135.0), 10.0);
+        assert_eq!(scale.y(105.0), 60.0);
+        assert_eq!(scale.y(0.0), 110.0);
+
+        assert!(ChartScale::new([f64::NAN], start, end, area).is_none());
+        assert!(ChartScale::new(std::iter::empty(), start, end, area).is_none());
+    }
+
+    #[test]
+    fn data_url_payload_is_base64_decoded() {
+        assert_eq!(
+            data_url_bytes("data:image/png;base64,iVBORw0KGgo="),
+            Some(vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a])
+        );
+        assert_eq!(data_url_bytes("data:,"), None);
+        assert_eq!(data_url_bytes("data:image/png;base64,@@@@"), None);
+    }
+
+    #[test]
+    fn timed_points_end_with_the_latest_reading() {
+        let values: Vec<f64> = timed_points(&trend_at(&[1, 5, 3]))
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(values, vec![85.0, 83.0, 81.0]);
+    }
+
+    #[test]
+    fn duration_bars_scale_to_the_shared_axis() {
+        use chrono::TimeZone;
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2025, 10, d, 0, 0, 0).unwrap();
+        let axis = (day(1), day(11));
+
+        assert_eq!(
+            duration_bar(day(1), day(6), axis, 200.0),
+            DurationBar {
+                x: 0.0,
+                width: 100.0
+            }
+        );
+        assert_eq!(
+            duration_bar(day(6), day(11), axis, 200.0),
+            DurationBar {
+                x: 100.0,
+                width: 100.0
+            }
+        );
+        // Same-instant periods keep a visible sliver.
+        assert_eq!(
+            duration_bar(day(3), day(3), axis, 200.0),
+            DurationBar {
+                x: 40.0,
+                width: 2.0
+            }
+        );
+    }
+
+    #[test]
+    fn unclassified_series_falls_back_to_group_severity() {
+        assert_eq!(
+            point_severity_class("Creatinine", 3.1, Severity::High),
+            "is-high"
+        );
+    }
+
+    #[test]
+    fn long_unit_label_is_ellipsized_to_the_configured_width() {
+        let label = "120 mL/min/1.73m2 (CKD-EPI)";
+        let fitted = fit_label(label, 40.0);
+        assert!(fitted.truncated);
+        assert!(fitted.text.ends_with('…'));
+        assert!(fitted.text.chars().count() as f64 * LABEL_CHAR_WIDTH <= 40.0);
+        assert!(label.starts_with(fitted.text.trim_end_matches('…')));
+
+        let short = fit_label("98 %", 40.0);
+        assert_eq!(short.text, "98 %");
+        assert!(!short.truncated);
+    }
+
+    #[test]
+    fn series_keep_their_colour_across_charts() {
+        let blood_pressure = ["Systolic", "Diastolic"];
+        let diastolic_only = ["Diastolic"];
+        let vitals = ["Heart rate", "SpO2", "Respiratory rate"];
+        let heart_rate = ["Heart rate"];
+
+        let colours = |labels: &[&str]| -> Vec<&'static str> {
+            labels.iter().map(|label| series_color(label)).collect()
+        };
+
+        assert_eq!(colours(&blood_pressure)[1], colours(&diastolic_only)[0]);
+        assert_eq!(colours(&vitals)[0], colours(&heart_rate)[0]);
+        assert_eq!(series_color("heart rate "), series_color("Heart rate"));
+
+        assert_eq!(series_color("Systolic"), "series-systolic");
+        assert_eq!(series_color("Diastolic"), "series-diastolic");
+        for label in ["Heart rate", "SpO2", "Glucose", "Potassium"] {
+            assert!(SERIES_PALETTE.contains(&series_color(label)));
+        }
+    }
+}
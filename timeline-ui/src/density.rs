@@ -0,0 +1,101 @@
+//! Layout density for embedding the timeline in narrow containers.
+//!
+//! Compact mode is exposed to CSS through the root `data-density` attribute and
+//! drops secondary metadata (relative-time lines, source chips) while rendering.
+
+use serde::Deserialize;
+use timeline_core::ResourceReference;
+
+use crate::render_context;
+
+/// Spacing and metadata level of the rendered view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Density {
+    /// Two-column dashboard with full metadata.
+    #[default]
+    Comfortable,
+    /// Single-column card with tighter spacing and no secondary metadata.
+    Compact,
+}
+
+impl Density {
+    /// Value of the root `data-density` attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Density::Comfortable => "comfortable",
+            Density::Compact => "compact",
+        }
+    }
+}
+
+/// Density of the current render pass.
+fn active() -> Density {
+    render_context::with(|context| context.density)
+}
+
+/// `data-density` value for the active density.
+pub(crate) fn attribute() -> &'static str {
+    active().as_str()
+}
+
+/// Keep `value` only when secondary metadata is shown (i.e. not in compact mode).
+pub(crate) fn secondary<T>(value: Option<T>) -> Option<T> {
+    value.filter(|_| active() != Density::Compact)
+}
+
+/// System and display text of an event's source chip, if one should be rendered.
+pub(crate) fn source_chip(source: Option<&ResourceReference>) -> Option<(String, String)> {
+    let source = secondary(source)?;
+    let system = source.system.as_deref().unwrap_or("FHIR").to_string();
+    let display = source
+        .display
+        .clone()
+        .or_else(|| source.reference.clone())
+        .unwrap_or_else(|| "Unknown source".to_string());
+    Some((system, display))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_context::{RenderContext, RenderGuard};
+
+    fn enter(density: Density) -> RenderGuard {
+        RenderContext {
+            density,
+            ..RenderContext::default()
+        }
+        .enter()
+    }
+
+    fn source() -> ResourceReference {
+        ResourceReference {
+            system: None,
+            reference: Some("Observation/obs-1".to_string()),
+            display: None,
+        }
+    }
+
+    #[test]
+    fn comfortable_mode_renders_source_chip() {
+        let _density = enter(Density::Comfortable);
+        assert_eq!(attribute(), "comfortable");
+        assert_eq!(
+            source_chip(Some(&source())),
+            Some(("FHIR".to_string(), "Observation/obs-1".to_string()))
+        );
+        assert_eq!(secondary(Some("3 hours ago")), Some("3 hours ago"));
+    }
+
+    #[test]
+    fn compact_mode_sets_attribute_and_omits_source_chip() {
+        {
+            let _density = enter(Density::Compact);
+            assert_eq!(attribute(), "compact");
+            assert_eq!(source_chip(Some(&source())), None);
+            assert_eq!(secondary(Some("3 hours ago")), None);
+        }
+        assert_eq!(attribute(), "comfortable");
+    }
+}
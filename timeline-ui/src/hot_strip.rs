@@ -0,0 +1,116 @@
+//! Selection of events for the "Priority watchlist" strip.
+//!
+//! The strip shows the newest critical/high events, up to [`URGENT_LIMIT`].
+//! Pinned bucket columns (e.g. "Vitals", "Labs") additionally get their latest
+//! event shown whatever its severity, after the urgent ones and without
+//! repeating an event already listed.
+
+use std::cmp::Ordering;
+
+use timeline_core::{Severity, TimelineEvent};
+
+use crate::buckets::categorize_event_for_summary;
+
+/// Most severity-ranked events shown; pinned events come on top of these.
+pub(crate) const URGENT_LIMIT: usize = 3;
+
+/// One strip entry; `pinned` marks events shown only because of their bucket.
+pub(crate) struct HotItem<'a> {
+    pub event: &'a TimelineEvent,
+    pub pinned: bool,
+}
+
+/// Events for the strip: urgent events newest first, then the latest event of
+/// each bucket in `pinned` (matched case-insensitively, in the given order).
+pub(crate) fn hot_strip_items<'a>(
+    events: &'a [TimelineEvent],
+    pinned: &[String],
+) -> Vec<HotItem<'a>> {
+    let mut urgent: Vec<&TimelineEvent> = events
+        .iter()
+        .filter(|event| event.severity.is_at_least(Severity::High))
+        .collect();
+    urgent.sort_by(|a, b| newest_first(a, b));
+    urgent.truncate(URGENT_LIMIT);
+
+    let mut items: Vec<HotItem<'a>> = urgent
+        .into_iter()
+        .map(|event| HotItem {
+            event,
+            pinned: false,
+        })
+        .collect();
+    for bucket in pinned {
+        let latest = events
+            .iter()
+            .filter(|event| categorize_event_for_summary(event).eq_ignore_ascii_case(bucket.trim()))
+            .min_by(|a, b| newest_first(a, b));
+        if let Some(event) = latest {
+            if !items.iter().any(|item| std::ptr::eq(item.event, event)) {
+                items.push(HotItem {
+                    event,
+                    pinned: true,
+                });
+            }
+        }
+    }
+    items
+}
+
+/// Newest first; events without a timestamp last.
+fn newest_first(a: &TimelineEvent, b: &TimelineEvent) -> Ordering {
+    b.occurred_at
+        .cmp(&a.occurred_at)
+        .then_with(|| a.tiebreak_cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use timeline_core::{EventCategory, ObservationCategory};
+
+    fn event(
+        id: &str,
+        hour: u32,
+        severity: Severity,
+        observation_category: Option<ObservationCategory>,
+    ) -> TimelineEvent {
+        TimelineEvent {
+            occurred_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, hour, 0, 0).unwrap()),
+            severity,
+            observation_category,
+            ..TimelineEvent::new(id, EventCategory::Observation, id)
+        }
+    }
+
+    fn ids<'a>(items: &[HotItem<'a>]) -> Vec<(&'a str, bool)> {
+        items
+            .iter()
+            .map(|item| (item.event.id.as_str(), item.pinned))
+            .collect()
+    }
+
+    #[test]
+    fn pinned_lab_appears_even_at_info_severity() {
+        let lab = Some(ObservationCategory::Laboratory);
+        let vitals = Some(ObservationCategory::VitalSigns);
+        let events = vec![
+            event("lactate-old", 6, Severity::Info, lab),
+            event("spo2-low", 8, Severity::Critical, vitals),
+            event("sodium", 9, Severity::Info, lab),
+            event("hr-high", 7, Severity::High, vitals),
+        ];
+
+        assert_eq!(
+            ids(&hot_strip_items(&events, &[])),
+            vec![("spo2-low", false), ("hr-high", false)]
+        );
+
+        let pinned = vec!["labs".to_string(), "Vitals".to_string()];
+        assert_eq!(
+            ids(&hot_strip_items(&events, &pinned)),
+            vec![("spo2-low", false), ("hr-high", false), ("sodium", true)]
+        );
+    }
+}
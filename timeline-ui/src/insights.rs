@@ -0,0 +1,186 @@
+//! Text of the trend insight cards.
+//!
+//! By default a card compares the first and last readings. In weighted mode
+//! the change is measured against the exponentially weighted average of the
+//! earlier readings ([`reportable_weighted_delta`]), so the card names that
+//! average as its starting point instead of the first reading.
+
+use chrono::{DateTime, Utc};
+use timeline_core::analytics::{reportable_delta, reportable_weighted_delta, TrendDeltaThresholds};
+use timeline_core::VitalTrend;
+
+use crate::locale;
+
+/// Rendered content of one insight card.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TrendInsight {
+    /// Value of `data-trend`: "up" or "down".
+    pub direction: &'static str,
+    pub arrow: &'static str,
+    /// E.g. "↑12 bpm in 2 hours" or "↓30 bpm vs recent average".
+    pub change: String,
+    /// E.g. "80 bpm → 92 bpm" or "110 bpm average → 80 bpm".
+    pub detail: String,
+    pub first_recorded_at: Option<DateTime<Utc>>,
+    pub last_recorded_at: Option<DateTime<Utc>>,
+}
+
+/// Insight for `trend`, or `None` when its change is below the threshold.
+pub(crate) fn trend_insight(
+    trend: &VitalTrend,
+    thresholds: &TrendDeltaThresholds,
+    weighted: bool,
+) -> Option<TrendInsight> {
+    let delta = if weighted {
+        reportable_weighted_delta(trend, thresholds)
+    } else {
+        reportable_delta(trend, thresholds)
+    }?;
+    let mut numeric_points = trend
+        .points
+        .iter()
+        .filter_map(|point| point.value.map(|value| (point, value)));
+    let (first_point, first_value) = numeric_points.next()?;
+    let (last_point, last_value) = numeric_points.next_back()?;
+
+    let unit = trend.unit.as_deref();
+    let (arrow, direction) = if delta > 0.0 {
+        ("↑", "up")
+    } else {
+        ("↓", "down")
+    };
+    let change_summary = format!("{arrow}{}", format_measurement(delta.abs(), unit));
+    let end_label = last_point
+        .label
+        .clone()
+        .unwrap_or_else(|| format_measurement(last_value, unit));
+
+    let (change, detail) = if weighted {
+        let average = format_measurement(last_value - delta, unit);
+        (
+            format!("{change_summary} vs recent average"),
+            format!("{average} average → {end_label}"),
+        )
+    } else {
+        let span_text = format_duration_span(first_point.recorded_at, last_point.recorded_at)
+            .unwrap_or_else(|| "recent readings".to_string());
+        let start_label = first_point
+            .label
+            .clone()
+            .unwrap_or_else(|| format_measurement(first_value, unit));
+        (
+            format!("{change_summary} in {span_text}"),
+            format!("{start_label} → {end_label}"),
+        )
+    };
+
+    Some(TrendInsight {
+        direction,
+        arrow,
+        change,
+        detail,
+        first_recorded_at: first_point.recorded_at,
+        last_recorded_at: last_point.recorded_at,
+    })
+}
+
+fn format_duration_span(
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Option<String> {
+    let start = start?;
+    let end = end?;
+    let mut delta = end.signed_duration_since(start);
+    if delta.num_seconds().abs() < 1 {
+        return Some("moments".to_string());
+    }
+
+    if delta.num_seconds() < 0 {
+        delta = -delta;
+    }
+
+    let total_minutes = delta.num_minutes();
+    if total_minutes < 1 {
+        return Some("moments".to_string());
+    }
+
+    let hours = total_minutes / 60;
+    let minutes = total_minutes % 60;
+    let mut parts = Vec::new();
+
+    if hours > 0 {
+        let unit = if hours == 1 { "hour" } else { "hours" };
+        parts.push(format!("{hours} {unit}"));
+    }
+
+    if minutes > 0 {
+        let unit = if minutes == 1 { "minute" } else { "minutes" };
+        parts.push(format!("{minutes} {unit}"));
+    }
+
+    Some(parts.join(" "))
+}
+
+pub(crate) fn format_numeric(value: f64) -> String {
+    let decimals = if value.abs() >= 10.0 { 0 } else { 1 };
+    locale::format_number(value, decimals)
+}
+
+pub(crate) fn format_measurement(value: f64, unit: Option<&str>) -> String {
+    let numeric = format_numeric(value);
+    match unit {
+        Some(unit) if !unit.is_empty() => format!("{numeric} {unit}"),
+        _ => numeric,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use timeline_core::VitalTrendPoint;
+
+    fn heart_rate(values: &[f64]) -> VitalTrend {
+        let start = Utc.with_ymd_and_hms(2025, 10, 30, 8, 0, 0).unwrap();
+        VitalTrend {
+            name: "Heart rate".into(),
+            unit: Some("bpm".into()),
+            points: values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| VitalTrendPoint {
+                    recorded_at: Some(start + Duration::minutes(30 * index as i64)),
+                    value: Some(*value),
+                    label: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn first_to_last_insight_spans_the_readings() {
+        let insight = trend_insight(
+            &heart_rate(&[80.0, 86.0, 100.0]),
+            &TrendDeltaThresholds::default(),
+            false,
+        )
+        .expect("rise is reported");
+
+        assert_eq!(insight.direction, "up");
+        assert_eq!(insight.change, "↑20 bpm in 1 hour");
+        assert_eq!(insight.detail, "80 bpm → 100 bpm");
+    }
+
+    #[test]
+    fn weighted_insight_starts_from_the_recent_average() {
+        let spike = heart_rate(&[80.0, 80.0, 140.0, 80.0]);
+        let thresholds = TrendDeltaThresholds::default();
+        assert_eq!(trend_insight(&spike, &thresholds, false), None);
+
+        let insight = trend_insight(&spike, &thresholds, true).expect("recent fall is reported");
+
+        assert_eq!(insight.direction, "down");
+        assert_eq!(insight.change, "↓30 bpm vs recent average");
+        assert_eq!(insight.detail, "110 bpm average → 80 bpm");
+    }
+}
@@ -0,0 +1,196 @@
+//! Grouping of allergy and medication items by therapeutic class.
+//!
+//! A class map names each class and lists keywords matched case-insensitively
+//! against the item label, so "Medication: Ceftriaxone 1 g IV" falls under the
+//! class listing "ceftriaxone". The first class (in map order) with a matching
+//! keyword wins; items no class claims are grouped under [`OTHER_CLASS`].
+
+use std::collections::BTreeMap;
+
+use timeline_core::CriticalItem;
+
+/// Heading for items without a matching class.
+pub(crate) const OTHER_CLASS: &str = "Other";
+
+/// Class name → label keywords.
+pub type ClassMap = BTreeMap<String, Vec<String>>;
+
+/// Common emergency drug classes, used when grouping without a configured map.
+pub fn default_class_map() -> ClassMap {
+    let classes: [(&str, &[&str]); 5] = [
+        (
+            "Antibiotics",
+            &[
+                "amoxicillin",
+                "ampicillin",
+                "azithromycin",
+                "cef",
+                "ciprofloxacin",
+                "clindamycin",
+                "doxycycline",
+                "levofloxacin",
+                "meropenem",
+                "metronidazole",
+                "penicillin",
+                "piperacillin",
+                "sulfamethoxazole",
+                "vancomycin",
+            ],
+        ),
+        (
+            "Anticoagulants",
+            &[
+                "apixaban",
+                "dabigatran",
+                "enoxaparin",
+                "heparin",
+                "rivaroxaban",
+                "warfarin",
+            ],
+        ),
+        (
+            "Opioids",
+            &[
+                "codeine",
+                "fentanyl",
+                "hydromorphone",
+                "morphine",
+                "oxycodone",
+            ],
+        ),
+        (
+            "Vasopressors",
+            &[
+                "dobutamine",
+                "dopamine",
+                "epinephrine",
+                "norepinephrine",
+                "phenylephrine",
+                "vasopressin",
+            ],
+        ),
+        (
+            "Antiemetics",
+            &["metoclopramide", "ondansetron", "prochlorperazine"],
+        ),
+    ];
+    classes
+        .into_iter()
+        .map(|(class, keywords)| {
+            (
+                class.to_string(),
+                keywords.iter().map(|keyword| keyword.to_string()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Class of `item` under `classes`, if any keyword matches its label.
+pub(crate) fn class_of<'a>(item: &CriticalItem, classes: &'a ClassMap) -> Option<&'a str> {
+    let label = item.label.to_lowercase();
+    classes
+        .iter()
+        .find(|(_, keywords)| {
+            keywords.iter().any(|keyword| {
+                let keyword = keyword.trim().to_lowercase();
+                !keyword.is_empty() && label.contains(&keyword)
+            })
+        })
+        .map(|(class, _)| class.as_str())
+}
+
+/// `items` grouped by class in map order, with [`OTHER_CLASS`] last. Empty
+/// groups are omitted and items keep their input order within a group.
+pub(crate) fn group_by_class<'a>(
+    items: &'a [CriticalItem],
+    classes: &ClassMap,
+) -> Vec<(String, Vec<&'a CriticalItem>)> {
+    let mut groups: Vec<(String, Vec<&'a CriticalItem>)> = classes
+        .keys()
+        .map(|class| (class.clone(), Vec::new()))
+        .collect();
+    let mut other = Vec::new();
+    for item in items {
+        match class_of(item, classes) {
+            Some(class) => {
+                if let Some((_, members)) = groups.iter_mut().find(|(name, _)| name == class) {
+                    members.push(item);
+                }
+            }
+            None => other.push(item),
+        }
+    }
+    groups.push((OTHER_CLASS.to_string(), other));
+    groups.retain(|(_, members)| !members.is_empty());
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use timeline_core::Severity;
+
+    fn medication(name: &str) -> CriticalItem {
+        CriticalItem {
+            label: format!("Medication: {name}"),
+            detail: None,
+            severity: Severity::High,
+            recorded_at: None,
+            reactions: Vec::new(),
+            watched: false,
+        }
+    }
+
+    fn labels<'a>(group: &(String, Vec<&'a CriticalItem>)) -> Vec<&'a str> {
+        group.1.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    #[test]
+    fn antibiotics_share_a_heading_and_unmatched_items_fall_under_other() {
+        let classes = ClassMap::from([
+            (
+                "Antibiotics".to_string(),
+                vec!["ceftriaxone".to_string(), "Vancomycin".to_string()],
+            ),
+            ("Anticoagulants".to_string(), vec!["heparin".to_string()]),
+        ]);
+        let items = [
+            medication("Ceftriaxone 1 g IV"),
+            medication("Acetaminophen 1 g PO"),
+            medication("Vancomycin 1.5 g IV"),
+        ];
+
+        let groups = group_by_class(&items, &classes);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "Antibiotics");
+        assert_eq!(
+            labels(&groups[0]),
+            vec![
+                "Medication: Ceftriaxone 1 g IV",
+                "Medication: Vancomycin 1.5 g IV"
+            ]
+        );
+        assert_eq!(groups[1].0, OTHER_CLASS);
+        assert_eq!(labels(&groups[1]), vec!["Medication: Acetaminophen 1 g PO"]);
+    }
+
+    #[test]
+    fn default_map_classifies_common_emergency_drugs() {
+        let classes = default_class_map();
+        assert_eq!(
+            class_of(&medication("Norepinephrine infusion"), &classes),
+            Some("Vasopressors")
+        );
+        assert_eq!(
+            class_of(&medication("Enoxaparin 40 mg SC"), &classes),
+            Some("Anticoagulants")
+        );
+        let allergy = CriticalItem {
+            label: "Allergy: Penicillin".to_string(),
+            ..medication("")
+        };
+        assert_eq!(class_of(&allergy, &classes), Some("Antibiotics"));
+        assert_eq!(class_of(&medication("Insulin glargine"), &classes), None);
+    }
+}
@@ -0,0 +1,127 @@
+//! Display labels shared by the renderer.
+
+use chrono::{DateTime, Utc};
+use timeline_core::{DatePrecision, PatientDemographics, Severity};
+
+pub(crate) fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "Critical",
+        Severity::High => "High",
+        Severity::Moderate => "Moderate",
+        Severity::Low => "Low",
+        Severity::Info => "Info",
+    }
+}
+
+pub(crate) fn severity_level(severity: Severity) -> &'static str {
+    severity.level_str()
+}
+
+/// Timestamp shown on timeline rows, only as precise as the source date, so a
+/// year-only onset reads "2019" rather than "01/01/2019 00:00".
+pub(crate) fn format_timestamp(
+    timestamp: Option<DateTime<Utc>>,
+    precision: DatePrecision,
+) -> String {
+    let Some(dt) = timestamp else {
+        return "--".to_string();
+    };
+    let pattern = match precision {
+        DatePrecision::Year => "%Y",
+        DatePrecision::Month => "%m/%Y",
+        DatePrecision::Day => "%m/%d/%Y",
+        DatePrecision::DateTime => "%m/%d/%Y %H:%M",
+    };
+    dt.format(pattern).to_string()
+}
+
+/// Short facts shown next to the patient name in the header banner.
+pub(crate) fn patient_banner_facts(patient: &PatientDemographics) -> Vec<String> {
+    let mut facts = Vec::new();
+    if let Some(age) = patient.age {
+        facts.push(format!("{age} y"));
+    }
+    if let Some(gender) = patient.gender.as_deref() {
+        facts.push(match gender {
+            "male" => "Male".to_string(),
+            "female" => "Female".to_string(),
+            "other" => "Other".to_string(),
+            other => other.to_string(),
+        });
+    }
+    if let Some(birth_date) = patient.birth_date {
+        facts.push(format!("DOB {}", birth_date.format("%m/%d/%Y")));
+    }
+    if patient.deceased {
+        facts.push(match patient.deceased_at {
+            Some(at) => format!("Deceased {}", at.format("%m/%d/%Y")),
+            None => "Deceased".to_string(),
+        });
+    }
+    facts
+}
+
+/// Placeholder for an empty vitals card; mentions vitals that exist but fall
+/// outside the recency window so clinicians do not assume none were taken.
+pub(crate) fn empty_vitals_message(older_vitals: usize, window_hours: Option<u32>) -> String {
+    let window = match window_hours {
+        Some(hours) => format!("in the last {hours}h"),
+        None => "in the configured window".to_string(),
+    };
+    match older_vitals {
+        0 => format!("No recent vital signs {window}."),
+        count => format!("No vitals {window} ({count} older recorded)."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    #[test]
+    fn banner_facts_list_demographics_in_order() {
+        let patient = PatientDemographics {
+            name: Some("Jane Doe".to_string()),
+            age: Some(50),
+            gender: Some("female".to_string()),
+            birth_date: NaiveDate::from_ymd_opt(1975, 4, 18),
+            deceased: true,
+            deceased_at: None,
+        };
+
+        assert_eq!(
+            patient_banner_facts(&patient),
+            vec!["50 y", "Female", "DOB 04/18/1975", "Deceased"]
+        );
+    }
+
+    #[test]
+    fn empty_vitals_message_surfaces_older_count() {
+        assert_eq!(
+            empty_vitals_message(4, Some(6)),
+            "No vitals in the last 6h (4 older recorded)."
+        );
+        assert_eq!(
+            empty_vitals_message(0, Some(6)),
+            "No recent vital signs in the last 6h."
+        );
+        assert_eq!(
+            empty_vitals_message(0, None),
+            "No recent vital signs in the configured window."
+        );
+    }
+
+    #[test]
+    fn partial_dates_render_without_time() {
+        let midnight = Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).single();
+        assert_eq!(format_timestamp(midnight, DatePrecision::Year), "2019");
+        assert_eq!(format_timestamp(midnight, DatePrecision::Month), "01/2019");
+        assert_eq!(format_timestamp(midnight, DatePrecision::Day), "01/01/2019");
+        assert_eq!(
+            format_timestamp(midnight, DatePrecision::DateTime),
+            "01/01/2019 00:00"
+        );
+        assert_eq!(format_timestamp(None, DatePrecision::Year), "--");
+    }
+}
@@ -0,0 +1,129 @@
+//! Arrangement of the event column: the bucketed category grid or a single
+//! chronological stream.
+//!
+//! The stream drops the bucket columns and lists events newest first under one
+//! heading per day, which reads better in narrow or scroll-averse embeddings.
+//! The critical column is the same in both layouts.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use timeline_core::{format_day_label, TimelineEvent};
+
+/// How the event column is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layout {
+    /// Day rows split into bucket columns (Vitals, Labs, …).
+    #[default]
+    Grid,
+    /// One time-descending list of events grouped by day.
+    Stream,
+}
+
+impl Layout {
+    /// Value of the root `data-layout` attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Layout::Grid => "grid",
+            Layout::Stream => "stream",
+        }
+    }
+}
+
+/// Events of one day in the stream layout.
+#[derive(Debug)]
+pub(crate) struct StreamDay<'a> {
+    /// Day heading relative to `now`, e.g. "Today" or "10/28/2025".
+    pub label: String,
+    pub events: Vec<&'a TimelineEvent>,
+}
+
+/// `events` newest first, split into consecutive days. Undated events come
+/// last under "Unknown time"; events at the same instant keep their input order.
+pub(crate) fn stream_days<'a>(
+    events: &[&'a TimelineEvent],
+    now: DateTime<Utc>,
+) -> Vec<StreamDay<'a>> {
+    let mut ordered = events.to_vec();
+    ordered.sort_by(|a, b| match (a.occurred_at, b.occurred_at) {
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+
+    let mut days: Vec<StreamDay<'a>> = Vec::new();
+    for event in ordered {
+        let label = format_day_label(event.occurred_at, now);
+        match days.last_mut() {
+            Some(day) if day.label == label => day.events.push(event),
+            _ => days.push(StreamDay {
+                label,
+                events: vec![event],
+            }),
+        }
+    }
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use timeline_core::EventCategory;
+
+    fn event(id: &str, category: EventCategory, at: Option<&str>) -> TimelineEvent {
+        TimelineEvent {
+            occurred_at: at.map(|at| {
+                DateTime::parse_from_rfc3339(at)
+                    .unwrap()
+                    .with_timezone(&Utc)
+            }),
+            ..TimelineEvent::new(id, category, id)
+        }
+    }
+
+    fn ids<'a>(day: &StreamDay<'a>) -> Vec<&'a str> {
+        day.events.iter().map(|event| event.id.as_str()).collect()
+    }
+
+    #[test]
+    fn stream_lists_every_bucket_in_one_descending_list_per_day() {
+        let now = DateTime::parse_from_rfc3339("2025-10-30T18:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let events = [
+            event(
+                "lab",
+                EventCategory::Observation,
+                Some("2025-10-29T08:00:00Z"),
+            ),
+            event("note", EventCategory::Note, None),
+            event(
+                "med",
+                EventCategory::Medication,
+                Some("2025-10-30T09:00:00Z"),
+            ),
+            event(
+                "proc",
+                EventCategory::Procedure,
+                Some("2025-10-30T12:00:00Z"),
+            ),
+            event(
+                "enc",
+                EventCategory::Encounter,
+                Some("2025-10-29T20:00:00Z"),
+            ),
+        ];
+        let refs: Vec<&TimelineEvent> = events.iter().collect();
+
+        let days = stream_days(&refs, now);
+
+        let labels: Vec<&str> = days.iter().map(|day| day.label.as_str()).collect();
+        assert_eq!(labels, vec!["Today", "Yesterday", "Unknown time"]);
+        assert_eq!(ids(&days[0]), vec!["proc", "med"]);
+        assert_eq!(ids(&days[1]), vec!["enc", "lab"]);
+        assert_eq!(ids(&days[2]), vec!["note"]);
+        assert_eq!(Layout::default(), Layout::Grid);
+        assert_eq!(Layout::Stream.as_str(), "stream");
+    }
+}
@@ -1,59 +1,99 @@
 //! Timeline UI component for the WebAssembly environment.
 
+// Pure rendering helpers are compiled on every target so they can be unit tested
+// natively; only the wasm renderer consumes them.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod badges;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod buckets;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod chart;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod density;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod hot_strip;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod insights;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod item_classes;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod labels;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod layout;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod locale;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod markdown;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod minimap;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod plural;
 #[cfg(target_arch = "wasm32")]
+mod png_export;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod relative_time;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod render_context;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod scope;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod sections;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod shortcuts;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod snapshot_input;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
 mod styles;
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+mod vital_order;
 
 #[cfg(target_arch = "wasm32")]
 mod wasm_ui {
-    use crate::styles;
+    use crate::badges::{badge_content, BadgeContent};
+    use crate::buckets::{
+        categorize_event_for_summary, chart_group_titles, group_by_source_system,
+    };
+    use crate::chart;
+    use crate::density::{self, Density};
+    use crate::hot_strip::{hot_strip_items, HotItem};
+    use crate::item_classes::{default_class_map, group_by_class, ClassMap};
+    use crate::labels::{
+        self, empty_vitals_message, patient_banner_facts, severity_label, severity_level,
+    };
+    use crate::insights::{format_measurement, format_numeric, trend_insight};
+    use crate::layout::{stream_days, Layout};
+    use crate::locale::{self, Locale};
+    use crate::markdown;
+    use crate::plural;
+    use crate::minimap::{minimap_bars, MAX_BARS};
+    use crate::relative_time::{
+        format_relative_time, RefreshClock, TimeGranularity, REFRESH_INTERVAL_MS,
+    };
+    use crate::render_context::{self, RenderContext};
+    use crate::sections::{section_order, CriticalSection};
+    use crate::shortcuts::{self, KeyPress, SeverityShortcuts, SEVERITY_FILTERS};
+    use crate::snapshot_input::{decode_snapshot, snapshot_from_json};
+    use crate::vital_order::order_vitals;
+    use crate::{scope, styles};
     use chrono::{DateTime, Duration, NaiveDate, Utc};
+    use serde::Deserialize;
     use serde_wasm_bindgen::from_value;
     use std::{
         cmp::Ordering,
         collections::{BTreeMap, HashMap, HashSet},
     };
+    use timeline_core::analytics::{TrendDeltaThresholds, EXPECTED_VITALS};
     use timeline_core::{
-        CriticalItem, CriticalSummary, DiagnosticKind, DiagnosticSnapshot, EventCategory, Severity,
+        format_day_label, CriticalItem, CriticalSummary, DatePrecision, DiagnosticKind,
+        DiagnosticSnapshot, EventCategory, PatientDemographics, Severity, SeverityCounts,
         TimelineEvent, TimelineSnapshot, VitalSnapshot, VitalTrend,
     };
     use wasm_bindgen::prelude::*;
-    use web_sys::{console, Document, Element, HtmlInputElement, Window};
+    use web_sys::{console, Document, Element, HtmlElement, HtmlInputElement, Window};
     use yew::events::InputEvent;
     use yew::prelude::*;
     use yew::TargetCast;
 
-    const VITAL_EVENT_KEYWORDS: &[&str] = &[
-        "heart rate",
-        "blood pressure",
-        "respiratory rate",
-        "spo2",
-        "oxygen saturation",
-        "temperature",
-        "pulse",
-    ];
-
-    const LAB_EVENT_KEYWORDS: &[&str] = &[
-        "lactate",
-        "troponin",
-        "culture",
-        "panel",
-        "cbc",
-        "chemistry",
-        "creatinine",
-        "glucose",
-        "magnesium",
-        "blood gas",
-    ];
-
-    const IMAGING_EVENT_KEYWORDS: &[&str] = &[
-        "ct",
-        "mri",
-        "x-ray",
-        "xray",
-        "ultrasound",
-        "radiograph",
-    ];
-
     const TIMELINE_BUCKET_COLUMNS: &[(&str, &str)] = &[
         ("Vitals", "Vitals"),
         ("Labs", "Labs"),
@@ -74,19 +114,11 @@ mod wasm_ui {
         query: String,
     }
 
-    #[derive(Debug, Default, Clone, Copy)]
-    struct SeverityCounts {
-        total: usize,
-        critical: usize,
-        high: usize,
-        moderate: usize,
-        low: usize,
-        info: usize,
-    }
-
     struct DayRow<'a> {
         label: String,
         key: String,
+        /// Calendar day of the row's events, targeted by minimap clicks.
+        day: Option<NaiveDate>,
         summary: String,
         default_collapsed: bool,
         is_expanded: bool,
@@ -163,16 +195,128 @@ mod wasm_ui {
     #[derive(Properties, PartialEq)]
     pub struct TimelineViewProps {
         pub snapshot: TimelineSnapshot,
+        /// Prefix for injected CSS and emitted class names (e.g. `acme` → `acme-timeline-root`).
+        #[prop_or_default]
+        pub style_scope: Option<String>,
+        /// Largest unit used for relative timestamps such as "6 weeks ago".
+        #[prop_or_default]
+        pub relative_time_granularity: TimeGranularity,
+        /// `Compact` renders a single-column card without secondary metadata.
+        #[prop_or_default]
+        pub density: Density,
+        /// Draw medications and encounters that have a period as duration bars on a
+        /// shared time axis instead of point events.
+        #[prop_or_default]
+        pub duration_bars: bool,
+        /// Decimal and grouping separators for rendered numbers.
+        #[prop_or_default]
+        pub locale: Locale,
+        /// Keys that toggle the severity filter chips.
+        #[prop_or_default]
+        pub severity_shortcuts: SeverityShortcuts,
+        /// Render event and critical item details as a safe Markdown subset.
+        #[prop_or_default]
+        pub markdown_details: bool,
+        /// Vital names in display order for the recent vitals card; unlisted vitals
+        /// follow by recency. `None` uses HR, BP, RR, SpO2, Temp.
+        #[prop_or_default]
+        pub vital_order: Option<Vec<String>>,
+        /// Chart only the last N hours of each vital trend, measured from the latest
+        /// trend point. Falls back to the full trend when fewer than two points remain.
+        #[prop_or_default]
+        pub trend_window_hours: Option<u32>,
+        /// Custom content for every severity badge (events, critical items, results,
+        /// groups and the watchlist), e.g. an icon set. Replaces the default label span.
+        #[prop_or_default]
+        pub badge_renderer: Option<Callback<Severity, Html>>,
+        /// Card order of the critical panel; unlisted cards follow in default order.
+        /// `None` puts code status first.
+        #[prop_or_default]
+        pub critical_sections: Option<Vec<CriticalSection>>,
+        /// Show a strip of per-day event bars above the grid; clicking a bar
+        /// scrolls to and expands that day.
+        #[prop_or_default]
+        pub minimap: bool,
+        /// Smallest change reported in the trend insights card, by vital name or
+        /// unit (e.g. 0.05 for pH, 5 mg/dL for glucose).
+        #[prop_or_default]
+        pub trend_delta_thresholds: TrendDeltaThresholds,
+        /// Group the allergy and medication cards under therapeutic class headings.
+        #[prop_or_default]
+        pub group_by_class: bool,
+        /// Class name → label keywords for `group_by_class`; `None` uses common
+        /// emergency drug classes.
+        #[prop_or_default]
+        pub class_map: Option<ClassMap>,
+        /// Bucket columns (e.g. "Vitals", "Labs") whose latest event always appears in the
+        /// priority watchlist, whatever its severity.
+        #[prop_or_default]
+        pub hot_strip_pinned: Vec<String>,
+        /// Compute trend insights from the latest reading against an exponentially weighted
+        /// average of earlier ones instead of first vs last, so spikes that resolve still show.
+        #[prop_or_default]
+        pub weighted_trend_insights: bool,
+        /// `Stream` lists events as one chronological column grouped by day instead of
+        /// the bucketed category grid.
+        #[prop_or_default]
+        pub layout: Layout,
+    }
+
+    #[derive(Deserialize, Default)]
+    struct JsViewOptions {
+        #[serde(default)]
+        style_scope: Option<String>,
+        #[serde(default)]
+        relative_time_granularity: TimeGranularity,
+        #[serde(default)]
+        density: Density,
+        #[serde(default)]
+        duration_bars: bool,
+        #[serde(default)]
+        locale: Locale,
+        #[serde(default)]
+        severity_shortcuts: SeverityShortcuts,
+        #[serde(default)]
+        markdown_details: bool,
+        #[serde(default)]
+        vital_order: Option<Vec<String>>,
+        #[serde(default)]
+        trend_window_hours: Option<u32>,
+        #[serde(default)]
+        critical_sections: Option<Vec<CriticalSection>>,
+        #[serde(default)]
+        minimap: bool,
+        #[serde(default)]
+        trend_delta_thresholds: TrendDeltaThresholds,
+        #[serde(default)]
+        group_by_class: bool,
+        #[serde(default)]
+        class_map: Option<ClassMap>,
+        #[serde(default)]
+        hot_strip_pinned: Vec<String>,
+        #[serde(default)]
+        weighted_trend_insights: bool,
+        #[serde(default)]
+        layout: Layout,
     }
 
     #[function_component(TimelineView)]
     fn timeline_view(props: &TimelineViewProps) -> Html {
         let snapshot = &props.snapshot;
+        let _context = RenderContext {
+            class_prefix: props.style_scope.as_deref().and_then(scope::prefix_for),
+            max_granularity: props.relative_time_granularity,
+            density: props.density,
+            locale: props.locale,
+            markdown: props.markdown_details,
+            badge_renderer: props.badge_renderer.clone(),
+        }
+        .enter();
 
-        use_effect_with((), |_| {
+        use_effect_with(props.style_scope.clone(), |style_scope| {
             if let Some(window) = web_sys::window() {
                 if let Some(document) = window.document() {
-                    if let Err(err) = styles::ensure_styles(&document) {
+                    if let Err(err) = styles::ensure_styles(&document, style_scope.as_deref()) {
                         console::error_1(&err);
                     }
                 }
@@ -180,9 +324,35 @@ mod wasm_ui {
             || ()
         });
 
+        // Re-render every minute so relative labels ("3 minutes ago") stay current.
+        let refreshed_at = use_state(Utc::now);
+        {
+            let refreshed_at = refreshed_at.clone();
+            use_effect_with((), move |_| {
+                let interval = start_relative_time_refresh(refreshed_at);
+                move || drop(interval)
+            });
+        }
+
         let filters = use_state(FilterState::default);
         let filters_value = (*filters).clone();
+        let filter_announcement = use_state(String::new);
+
+        {
+            let filters = filters.clone();
+            let announcement = filter_announcement.clone();
+            let shortcuts = props.severity_shortcuts.clone();
+            // Re-registered on every filter change so the listener toggles from the current state.
+            use_effect_with((filters_value.clone(), shortcuts), move |(_, shortcuts)| {
+                let listener =
+                    register_severity_shortcuts(filters, announcement, shortcuts.clone());
+                move || drop(listener)
+            });
+        }
+
         let expanded_groups = use_state(|| HashSet::<String>::new());
+        let focused_day = use_state(|| None::<NaiveDate>);
+        let group_by_system = use_state(|| false);
         let expanded_snapshot = (*expanded_groups).clone();
         let mut filtered_events: Vec<&TimelineEvent> = snapshot
             .events
@@ -190,10 +360,19 @@ mod wasm_ui {
             .filter(|event| event_matches_filters(event, &filters_value))
             .collect();
 
-        filtered_events.sort_by(|a, b| compare_datetimes(b.occurred_at, a.occurred_at));
+        filtered_events.sort_by(|a, b| compare_events_newest_first(a, b));
 
-        let grouped_events = group_events_by_day(&filtered_events);
-        let severity_counts = tally_severity(&filtered_events);
+        let (duration_events, point_events): (Vec<&TimelineEvent>, Vec<&TimelineEvent>) =
+            if props.duration_bars {
+                filtered_events
+                    .iter()
+                    .partition(|event| chart::renders_as_duration(event))
+            } else {
+                (Vec::new(), filtered_events.clone())
+            };
+
+        let grouped_events = group_events_by_day(&point_events);
+        let severity_counts = SeverityCounts::tally(filtered_events.iter().copied());
         let event_count_label = format_event_count(&severity_counts);
         let snapshot_recency = format_relative_time(Some(snapshot.generated_at))
             .unwrap_or_else(|| "just now".to_string());
@@ -217,48 +396,61 @@ mod wasm_ui {
 
         let severity_controls = render_severity_filters(filters.clone());
 
-        let events_view = if filtered_events.is_empty() {
-            html! { <div class="timeline-empty">{"No events match the current filters."}</div> }
+        let on_toggle_system_view = {
+            let group_by_system = group_by_system.clone();
+            Callback::from(move |_| group_by_system.set(!*group_by_system))
+        };
+
+        let events_view = if point_events.is_empty() && duration_events.is_empty() {
+            html! { <div class={scope::class("timeline-empty")}>{"No events match the current filters."}</div> }
+        } else if *group_by_system {
+            render_system_groups(&point_events)
+        } else if props.layout == Layout::Stream {
+            render_event_stream(&point_events)
         } else {
-            render_category_grid(grouped_events, expanded_groups.clone(), expanded_snapshot)
+            render_category_grid(
+                grouped_events,
+                expanded_groups.clone(),
+                expanded_snapshot,
+                *focused_day,
+            )
+        };
+        let minimap = if props.minimap {
+            render_minimap(snapshot, focused_day.clone())
+        } else {
+            Html::default()
         };
 
         html! {
-            <div class="timeline-root">
-                <aside class="critical-column">
-                    <header class="critical-header">
-                        <span class="critical-eyebrow">{"Emergency status"}</span>
+            <div class={scope::class("timeline-root")} data-density={density::attribute()} data-layout={props.layout.as_str()}>
+                <aside class={scope::class("critical-column")}>
+                    { render_patient_banner(snapshot.patient.as_ref()) }
+                    <header class={scope::class("critical-header")}>
+                        <span class={scope::class("critical-eyebrow")}>{"Emergency status"}</span>
                         <h2>{"Priority information"}</h2>
-                        <p class="critical-subhead">{ format!("Snapshot generated {snapshot_recency}") }</p>
+                        <p class={scope::class("critical-subhead")}>{ format!("Snapshot generated {snapshot_recency}") }</p>
                     </header>
-                    { render_code_status(&snapshot.critical) }
-                    { render_trend_insights(&snapshot.critical) }
-                    { render_vitals(&snapshot.critical.recent_vitals) }
-                    { render_diagnostics(&snapshot.critical) }
-                    { render_vital_trends(&snapshot.critical) }
-                    { render_critical_card("Clinical alerts", &snapshot.critical.alerts, "No urgent alerts.", CardVariant::Alert ) }
-                    { render_critical_card("Severe allergies", &snapshot.critical.allergies, "No high-risk allergies recorded.", CardVariant::Allergy) }
-                    { render_critical_card("Active medications", &snapshot.critical.medications, "No active medications.", CardVariant::Medication ) }
-                    { render_critical_card("High-risk chronic conditions", &snapshot.critical.chronic_conditions, "No high-risk chronic conditions recorded.", CardVariant::Condition) }
+                    { for section_order(props.critical_sections.as_deref()).into_iter().map(|section| render_critical_section(section, &snapshot.critical, props)) }
                 </aside>
-                <section class="timeline-column" aria-live="polite">
-                    { render_hot_strip(&snapshot.events) }
-                    <p class="timeline-updated">{
+                <section class={scope::class("timeline-column")} aria-live="polite">
+                    <div class={scope::class("visually-hidden")} role="status" aria-live="polite">{ (*filter_announcement).clone() }</div>
+                    { render_hot_strip(&snapshot.events, &props.hot_strip_pinned) }
+                    <p class={scope::class("timeline-updated")}>{
                         format!(
                             "Updated {}",
                             format_timestamp(Some(snapshot.generated_at))
                         )
                     }</p>
-                    <header class="timeline-toolbar">
-                        <div class="toolbar-group">
-                            <span class="toolbar-label">{"Filters"}</span>
+                    <header class={scope::class("timeline-toolbar")}>
+                        <div class={scope::class("toolbar-group")}>
+                            <span class={scope::class("toolbar-label")}>{"Filters"}</span>
                             { severity_controls }
                         </div>
-                        <div class="toolbar-summary">
-                            <span class="toolbar-count">{ event_count_label }</span>
+                        <div class={scope::class("toolbar-summary")}>
+                            <span class={scope::class("toolbar-count")}>{ event_count_label }</span>
                             { build_severity_badges(&severity_counts) }
                         </div>
-                        <div class="toolbar-search">
+                        <div class={scope::class("toolbar-search")}>
                             <input
                                 type="search"
                                 placeholder="Filter by keyword (e.g., blood pressure, sepsis)"
@@ -268,8 +460,18 @@ mod wasm_ui {
                             />
                             <button type="button" onclick={on_clear_filters.clone()} aria-label="Clear filters">{"Reset"}</button>
                         </div>
+                        <button
+                            type="button"
+                            class={classes!(scope::class("filter-chip"), group_by_system.then(|| scope::class("is-active")))}
+                            aria-pressed={group_by_system.to_string()}
+                            onclick={on_toggle_system_view}
+                        >
+                            {"Group by source system"}
+                        </button>
                     </header>
-                    <div class="timeline-events">
+                    { minimap }
+                    { render_duration_track(&duration_events) }
+                    <div class={scope::class("timeline-events")}>
                         { events_view }
                     </div>
                 </section>
@@ -277,23 +479,6 @@ mod wasm_ui {
         }
     }
 
-    fn tally_severity(events: &[&TimelineEvent]) -> SeverityCounts {
-        let mut counts = SeverityCounts::default();
-
-        for event in events {
-            counts.total += 1;
-            match event.severity {
-                Severity::Critical => counts.critical += 1,
-                Severity::High => counts.high += 1,
-                Severity::Moderate => counts.moderate += 1,
-                Severity::Low => counts.low += 1,
-                Severity::Info => counts.info += 1,
-            }
-        }
-
-        counts
-    }
-
     fn format_event_count(counts: &SeverityCounts) -> String {
         match counts.total {
             0 => "No events in view".to_string(),
@@ -328,13 +513,13 @@ mod wasm_ui {
         }
 
         html! {
-            <ul class="severity-summary" aria-label="Events by severity">
+            <ul class={scope::class("severity-summary")} aria-label="Events by severity">
                 {
                     for entries.into_iter().filter(|(_, count)| *count > 0).map(|(severity, count)| {
                         html! {
-                            <li class="severity-summary-item" data-level={severity_level(severity)}>
-                                <span class="severity-summary-label">{ severity_label(severity) }</span>
-                                <span class="severity-summary-count">{ count }</span>
+                            <li class={scope::class("severity-summary-item")} data-level={severity_level(severity)}>
+                                <span class={scope::class("severity-summary-label")}>{ severity_label(severity) }</span>
+                                <span class={scope::class("severity-summary-count")}>{ count }</span>
                             </li>
                         }
                     })
@@ -344,33 +529,22 @@ mod wasm_ui {
     }
 
     fn render_severity_filters(filters: UseStateHandle<FilterState>) -> Html {
-        let options = [
-            (None, "All"),
-            (Some(Severity::Critical), "Critical only"),
-            (Some(Severity::High), "High and above"),
-            (Some(Severity::Moderate), "Moderate and above"),
-        ];
-
         html! {
-            <div class="filter-chips" role="group" aria-label="Filter by severity">
+            <div class={scope::class("filter-chips")} role="group" aria-label="Filter by severity">
                 {
-                    for options.into_iter().map(|(level, label)| {
+                    for SEVERITY_FILTERS.into_iter().map(|(level, label)| {
                         let filters = filters.clone();
                         let is_active = filters.severity == level;
                         let onclick = Callback::from(move |_| {
                             let mut next = (*filters).clone();
-                            if next.severity == level {
-                                next.severity = None;
-                            } else {
-                                next.severity = level;
-                            }
+                            next.severity = shortcuts::toggle(next.severity, level);
                             filters.set(next);
                         });
 
                         html! {
                             <button
                                 type="button"
-                                class={classes!("filter-chip", is_active.then_some("is-active"))}
+                                class={classes!(scope::class("filter-chip"), is_active.then(|| scope::class("is-active")))}
                                 aria-pressed={is_active.to_string()}
                                 data-level={level.map(severity_level).unwrap_or("all")}
                                 onclick={onclick}
@@ -384,31 +558,153 @@ mod wasm_ui {
         }
     }
 
+    /// Removes the document `keydown` listener when dropped.
+    struct ShortcutListener {
+        document: Document,
+        callback: Closure<dyn FnMut(KeyboardEvent)>,
+    }
+
+    impl Drop for ShortcutListener {
+        fn drop(&mut self) {
+            let _ = self.document.remove_event_listener_with_callback(
+                "keydown",
+                self.callback.as_ref().unchecked_ref(),
+            );
+        }
+    }
+
+    fn register_severity_shortcuts(
+        filters: UseStateHandle<FilterState>,
+        announcement: UseStateHandle<String>,
+        shortcuts: SeverityShortcuts,
+    ) -> Option<ShortcutListener> {
+        let document = web_sys::window()?.document()?;
+        let callback = Closure::<dyn FnMut(KeyboardEvent)>::new(move |event: KeyboardEvent| {
+            let typing = event
+                .target()
+                .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+                .is_some_and(|element| {
+                    shortcuts::is_text_entry(&element.tag_name(), element.is_content_editable())
+                });
+            let key = event.key();
+            let press = KeyPress {
+                key: &key,
+                modified: event.ctrl_key() || event.alt_key() || event.meta_key(),
+                typing,
+            };
+            let Some(level) = shortcuts::filter_for_key(&shortcuts, &press) else {
+                return;
+            };
+
+            let mut next = (*filters).clone();
+            next.severity = shortcuts::toggle(next.severity, level);
+            announcement.set(shortcuts::announcement(next.severity));
+            filters.set(next);
+        });
+
+        document
+            .add_event_listener_with_callback("keydown", callback.as_ref().unchecked_ref())
+            .ok()?;
+        Some(ShortcutListener { document, callback })
+    }
+
+    /// Clears the relative-time refresh interval when dropped.
+    struct RefreshInterval {
+        window: Window,
+        handle: i32,
+        _callback: Closure<dyn FnMut()>,
+    }
+
+    impl Drop for RefreshInterval {
+        fn drop(&mut self) {
+            self.window.clear_interval_with_handle(self.handle);
+        }
+    }
+
+    fn start_relative_time_refresh(
+        refreshed_at: UseStateHandle<DateTime<Utc>>,
+    ) -> Option<RefreshInterval> {
+        let window = web_sys::window()?;
+        let mut clock = RefreshClock::new(Utc::now());
+        let callback = Closure::<dyn FnMut()>::new(move || {
+            let now = Utc::now();
+            if clock.tick(now) {
+                refreshed_at.set(now);
+            }
+        });
+        let handle = window
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                callback.as_ref().unchecked_ref(),
+                REFRESH_INTERVAL_MS,
+            )
+            .ok()?;
+        Some(RefreshInterval {
+            window,
+            handle,
+            _callback: callback,
+        })
+    }
+
     fn render_critical_card(
         title: &str,
         items: &[CriticalItem],
         empty_label: &str,
         variant: CardVariant,
+        classes: Option<&ClassMap>,
     ) -> Html {
+        let body = match classes {
+            _ if items.is_empty() => {
+                html! { <li class={scope::class("critical-empty")}>{ empty_label }</li> }
+            }
+            Some(classes) => html! {
+                for group_by_class(items, classes).into_iter().map(|(class, members)| html! {
+                    <li class={scope::class("critical-group")}>
+                        <h4 class={scope::class("critical-group-title")}>{ class }</h4>
+                        <ul>
+                            { for members.into_iter().map(render_critical_item) }
+                        </ul>
+                    </li>
+                })
+            },
+            None => html! { for items.iter().map(render_critical_item) },
+        };
+
         html! {
-            <section class="critical-card" data-variant={variant.data_attr()}>
+            <section class={scope::class("critical-card")} data-variant={variant.data_attr()}>
                 <header>
                     <h3>{ title }</h3>
-                    <span class="critical-count">{ items.len() }</span>
+                    <span class={scope::class("critical-count")}>{ items.len() }</span>
                 </header>
                 <ul>
-                    {
-                        if items.is_empty() {
-                            html! { <li class="critical-empty">{ empty_label }</li> }
-                        } else {
-                            html! { for items.iter().map(render_critical_item) }
-                        }
-                    }
+                    { body }
                 </ul>
             </section>
         }
     }
 
+    fn render_critical_section(
+        section: CriticalSection,
+        summary: &CriticalSummary,
+        props: &TimelineViewProps,
+    ) -> Html {
+        let classes = props
+            .group_by_class
+            .then(|| props.class_map.clone().unwrap_or_else(default_class_map));
+        match section {
+            CriticalSection::CodeStatus => render_code_status(summary),
+            CriticalSection::TrendInsights => render_trend_insights(summary, &props.trend_delta_thresholds, props.weighted_trend_insights),
+            CriticalSection::Vitals => render_vitals(summary, props.vital_order.as_deref()),
+            CriticalSection::Diagnostics => render_diagnostics(summary),
+            CriticalSection::VitalTrends => render_vital_trends(summary, props.trend_window_hours),
+            CriticalSection::Alerts => render_critical_card("Clinical alerts", &summary.alerts, "No urgent alerts.", CardVariant::Alert, None),
+            CriticalSection::Allergies => render_critical_card("Severe allergies", &summary.allergies, "No high-risk allergies recorded.", CardVariant::Allergy, classes.as_ref()),
+            CriticalSection::Medications => render_critical_card("Active medications", &summary.medications, "No active medications.", CardVariant::Medication, classes.as_ref()),
+            CriticalSection::DiscontinuedMedications if summary.discontinued_medications.is_empty() => Html::default(),
+            CriticalSection::DiscontinuedMedications => render_critical_card("Discontinued medications", &summary.discontinued_medications, "", CardVariant::Medication, classes.as_ref()),
+            CriticalSection::Conditions => render_critical_card("High-risk chronic conditions", &summary.chronic_conditions, "No high-risk chronic conditions recorded.", CardVariant::Condition, None),
+        }
+    }
+
     fn render_code_status(summary: &CriticalSummary) -> Html {
         let (status_text, status_level, helper_text) = match summary.code_status.as_ref() {
             Some(value) => (value.clone(), "affirm", "Confirmed"),
@@ -418,32 +714,36 @@ mod wasm_ui {
         let icon = if status_level == "affirm" { "✓" } else { "!" };
 
         html! {
-            <section class="critical-card code-status" data-variant="code-status">
+            <section class={scope::class("critical-card code-status")} data-variant="code-status" data-missing={summary.code_status.is_none().to_string()}>
                 <header>
-                    <div class="code-status-heading">
-                        <span class="code-status-icon" data-level={status_level} aria-hidden="true">{ icon }</span>
+                    <div class={scope::class("code-status-heading")}>
+                        <span class={scope::class("code-status-icon")} data-level={status_level} aria-hidden="true">{ icon }</span>
                         <h3>{"Code status"}</h3>
                     </div>
-                    <span class="critical-pill" data-level={status_level}>{ helper_text }</span>
+                    <span class={scope::class("critical-pill")} data-level={status_level}>{ helper_text }</span>
                 </header>
-                <p class="code-status-value" data-level={status_level}>{ status_text }</p>
+                <p class={scope::class("code-status-value")} data-level={status_level}>{ status_text }</p>
             </section>
         }
     }
 
-    fn render_vitals(vitals: &[VitalSnapshot]) -> Html {
+    fn render_vitals(summary: &CriticalSummary, order: Option<&[String]>) -> Html {
+        let vitals = match order {
+            Some(order) => order_vitals(&summary.recent_vitals, order),
+            None => order_vitals(&summary.recent_vitals, &EXPECTED_VITALS),
+        };
         html! {
-            <section class="critical-card" data-variant={CardVariant::Vitals.data_attr()}>
+            <section class={scope::class("critical-card")} data-variant={CardVariant::Vitals.data_attr()}>
                 <header>
                     <h3>{"Recent vital signs"}</h3>
-                    <span class="critical-count">{ vitals.len() }</span>
+                    <span class={scope::class("critical-count")}>{ vitals.len() }</span>
                 </header>
-                <ul class="vital-list">
+                <ul class={scope::class("vital-list")}>
                     {
                         if vitals.is_empty() {
-                            html! { <li class="critical-empty">{"No recent vital signs in the configured window."}</li> }
+                            html! { <li class={scope::class("critical-empty")}>{ empty_vitals_message(summary.older_vitals, summary.vital_window_hours) }</li> }
                         } else {
-                            html! { for vitals.iter().map(render_vital_item) }
+                            html! { for vitals.iter().copied().map(render_vital_item) }
                         }
                     }
                 </ul>
@@ -451,19 +751,39 @@ mod wasm_ui {
         }
     }
 
-    fn render_vital_trends(summary: &CriticalSummary) -> Html {
+    fn render_patient_banner(patient: Option<&PatientDemographics>) -> Html {
+        let Some(patient) = patient else {
+            return Html::default();
+        };
+        let name = patient
+            .name
+            .clone()
+            .unwrap_or_else(|| "Unknown patient".to_string());
+
+        html! {
+            <header class={scope::class("patient-banner")} data-deceased={patient.deceased.to_string()}>
+                <span class={scope::class("patient-name")}>{ name }</span>
+                <ul class={scope::class("patient-facts")}>
+                    { for patient_banner_facts(patient).into_iter().map(|fact| html! { <li>{ fact }</li> }) }
+                </ul>
+            </header>
+        }
+    }
+
+    fn render_vital_trends(summary: &CriticalSummary, window_hours: Option<u32>) -> Html {
         if summary.vital_trends.is_empty() {
             return Html::default();
         }
+        let anchor = chart::trend_anchor(&summary.vital_trends);
 
         html! {
-            <section class="critical-card trend-card" data-variant={CardVariant::Vitals.data_attr()}>
+            <section class={scope::class("critical-card trend-card")} data-variant={CardVariant::Vitals.data_attr()}>
                 <header>
                     <h3>{"Vital trends"}</h3>
-                    <span class="critical-count">{ summary.vital_trends.len() }</span>
+                    <span class={scope::class("critical-count")}>{ summary.vital_trends.len() }</span>
                 </header>
-                <ul class="trend-list">
-                    { for summary.vital_trends.iter().map(render_trend_item) }
+                <ul class={scope::class("trend-list")}>
+                    { for summary.vital_trends.iter().map(|trend| render_trend_item(&chart::windowed_trend(trend, anchor, window_hours))) }
                 </ul>
             </section>
         }
@@ -499,12 +819,12 @@ mod wasm_ui {
         };
 
         html! {
-            <section class="critical-card diagnostics-card" data-variant={CardVariant::Diagnostics.data_attr()}>
+            <section class={scope::class("critical-card diagnostics-card")} data-variant={CardVariant::Diagnostics.data_attr()}>
                 <header>
                     <h3>{"Recent diagnostics"}</h3>
-                    <span class="critical-count">{ total }</span>
+                    <span class={scope::class("critical-count")}>{ total }</span>
                 </header>
-                <div class="diagnostic-groups">
+                <div class={scope::class("diagnostic-groups")}>
                     { labs_html }
                     { imaging_html }
                 </div>
@@ -514,9 +834,9 @@ mod wasm_ui {
 
     fn render_diagnostic_group(label: &str, items: Vec<&DiagnosticSnapshot>) -> Html {
         html! {
-            <div class="diagnostic-group">
+            <div class={scope::class("diagnostic-group")}>
                 <h4>{ label }</h4>
-                <ul class="diagnostic-list">
+                <ul class={scope::class("diagnostic-list")}>
                     { for items.into_iter().map(render_diagnostic_item) }
                 </ul>
             </div>
@@ -524,90 +844,54 @@ mod wasm_ui {
     }
 
     fn render_diagnostic_item(item: &DiagnosticSnapshot) -> Html {
-        let severity_level = severity_level(item.severity);
-        let severity_label = severity_label(item.severity);
-        let relative = format_relative_time(item.recorded_at);
+        let relative = density::secondary(format_relative_time(item.recorded_at));
 
         html! {
-            <li class="diagnostic-item">
-                <div class="diagnostic-header">
-                    <span class="diagnostic-name">{ item.name.clone() }</span>
-                    <span class="severity-badge" data-level={severity_level}>{ severity_label }</span>
+            <li class={scope::class("diagnostic-item")} data-watched={item.watched.to_string()}>
+                <div class={scope::class("diagnostic-header")}>
+                    <span class={scope::class("diagnostic-name")}>{ item.name.clone() }</span>
+                    { render_watched_badge(item.watched) }
+                    { render_severity_badge("severity-badge", item.severity) }
                 </div>
-                <div class="diagnostic-value">{ item.value.clone() }</div>
-                <div class="diagnostic-meta">
+                <div class={scope::class("diagnostic-value")}>{ locale::localize_value(&item.value) }</div>
+                <div class={scope::class("diagnostic-meta")}>
                     { relative.map(|text| html! { <span>{ text }</span> }).unwrap_or_default() }
                 </div>
             </li>
         }
     }
 
-    fn render_trend_insights(summary: &CriticalSummary) -> Html {
+    fn render_trend_insights(
+        summary: &CriticalSummary,
+        thresholds: &TrendDeltaThresholds,
+        weighted: bool,
+    ) -> Html {
         let mut items: Vec<Html> = Vec::new();
 
         for trend in &summary.vital_trends {
-            let numeric_points: Vec<_> = trend
-                .points
-                .iter()
-                .filter_map(|point| point.value.map(|value| (point, value)))
-                .collect();
-
-            if numeric_points.len() < 2 {
-                continue;
-            }
-
-            let (first_point, first_value) = numeric_points.first().copied().unwrap();
-            let (last_point, last_value) = numeric_points.last().copied().unwrap();
-            let delta = last_value - first_value;
-
-            if delta.abs() < 0.5 {
+            let Some(insight) = trend_insight(trend, thresholds, weighted) else {
                 continue;
-            }
-
-            let direction = if delta > 0.0 { "up" } else { "down" };
-            let arrow = if delta > 0.0 { "↑" } else { "↓" };
-            let unit_suffix = trend.unit.as_deref().unwrap_or("");
-            let change_value = format_numeric(delta.abs());
-            let change_summary = if unit_suffix.is_empty() {
-                format!("{arrow}{change_value}")
-            } else {
-                format!("{arrow}{change_value} {unit_suffix}")
             };
-
-            let span_text = format_duration_span(first_point.recorded_at, last_point.recorded_at)
-                .unwrap_or_else(|| "recent readings".to_string());
-            let change_text = format!("{change_summary} in {span_text}");
-
-            let start_label = first_point
-                .label
-                .clone()
-                .unwrap_or_else(|| format_measurement(first_value, trend.unit.as_deref()));
-            let end_label = last_point
-                .label
-                .clone()
-                .unwrap_or_else(|| format_measurement(last_value, trend.unit.as_deref()));
-            let detail_text = format!("{start_label} → {end_label}");
-
-            let range_text = format_time_range(first_point.recorded_at, last_point.recorded_at);
-            let relative_text = format_relative_time(last_point.recorded_at);
+            let range_text = format_time_range(insight.first_recorded_at, insight.last_recorded_at);
+            let relative_text = format_relative_time(insight.last_recorded_at);
 
             items.push(html! {
-                <li class="insight-item" data-trend={direction}>
-                    <div class="insight-header">
-                        <span class="insight-arrow" aria-hidden="true">{ arrow }</span>
-                        <span class="insight-name">{ trend.name.clone() }</span>
+                <li class={scope::class("insight-item")} data-trend={insight.direction}>
+                    <div class={scope::class("insight-header")}>
+                        <span class={scope::class("insight-arrow")} aria-hidden="true">{ insight.arrow }</span>
+                        <span class={scope::class("insight-name")}>{ trend.name.clone() }</span>
                     </div>
-                    <div class="insight-change">{ change_text }</div>
-                    <div class="insight-detail">{ detail_text }</div>
-                    <div class="insight-meta">
+                    <div class={scope::class("insight-change")}>{ insight.change }</div>
+                    <div class={scope::class("insight-detail")}>{ insight.detail }</div>
+                    <div class={scope::class("insight-meta")}>
                         {
                             range_text
-                                .map(|text| html! { <span class="insight-range">{ text }</span> })
+                                .map(|text| html! { <span class={scope::class("insight-range")}>{ text }</span> })
                                 .unwrap_or_default()
                         }
                         {
                             relative_text
-                                .map(|text| html! { <span class="insight-relative">{ format!("Last recorded {text}") }</span> })
+                                .map(|text| html! { <span class={scope::class("insight-relative")}>{ format!("Last recorded {text}") }</span> })
                                 .unwrap_or_default()
                         }
                     </div>
@@ -619,12 +903,12 @@ mod wasm_ui {
             Html::default()
         } else {
             html! {
-                <section class="critical-card insights-card" data-variant={CardVariant::Insights.data_attr()}>
+                <section class={scope::class("critical-card insights-card")} data-variant={CardVariant::Insights.data_attr()}>
                     <header>
                         <h3>{"Trend insights"}</h3>
-                        <span class="critical-count">{ items.len() }</span>
+                        <span class={scope::class("critical-count")}>{ items.len() }</span>
                     </header>
-                    <ul class="insight-list">
+                    <ul class={scope::class("insight-list")}>
                         { for items }
                     </ul>
                 </section>
@@ -638,7 +922,7 @@ mod wasm_ui {
         let chart_html = chart_data
             .as_ref()
             .map(|data| build_measurement_chart(data, Severity::Info, ChartMode::SummaryByDay))
-            .unwrap_or_else(|| html! { <div class="trend-fallback">{"Not enough data to render a chart."}</div> });
+            .unwrap_or_else(|| html! { <div class={scope::class("trend-fallback")}>{"Not enough data to render a chart."}</div> });
 
         let latest_label = trend
             .points
@@ -668,22 +952,22 @@ mod wasm_ui {
         };
 
         html! {
-            <li class="trend-item">
-                <div class="trend-header">
-                    <span class="trend-name">{ trend.name.clone() }</span>
+            <li class={scope::class("trend-item")}>
+                <div class={scope::class("trend-header")}>
+                    <span class={scope::class("trend-name")}>{ trend.name.clone() }</span>
                     {
-                        trend.unit.as_ref().map(|unit| html! { <span class="trend-unit">{ unit.clone() }</span> }).unwrap_or_default()
+                        trend.unit.as_ref().map(|unit| html! { <span class={scope::class("trend-unit")}>{ unit.clone() }</span> }).unwrap_or_default()
                     }
                 </div>
-                <div class="trend-content">
+                <div class={scope::class("trend-content")}>
                     { chart_html }
-                    <div class="trend-meta">
-                        <span class="trend-latest">{ latest_label }</span>
+                    <div class={scope::class("trend-meta")}>
+                        <span class={scope::class("trend-latest")}>{ latest_label }</span>
                         {
-                            relative.map(|text| html! { <span class="trend-time">{ text }</span> }).unwrap_or_default()
+                            relative.map(|text| html! { <span class={scope::class("trend-time")}>{ text }</span> }).unwrap_or_default()
                         }
                         {
-                            delta_text.map(|text| html! { <span class="trend-delta" data-trend={delta_state}>{ text }</span> }).unwrap_or_default()
+                            delta_text.map(|text| html! { <span class={scope::class("trend-delta")} data-trend={delta_state}>{ text }</span> }).unwrap_or_default()
                         }
                     </div>
                 </div>
@@ -745,43 +1029,6 @@ mod wasm_ui {
         Some(format!("Δ {formatted}{unit_suffix}"))
     }
 
-    fn format_duration_span(
-        start: Option<DateTime<Utc>>,
-        end: Option<DateTime<Utc>>,
-    ) -> Option<String> {
-        let start = start?;
-        let end = end?;
-        let mut delta = end.signed_duration_since(start);
-        if delta.num_seconds().abs() < 1 {
-            return Some("moments".to_string());
-        }
-
-        if delta.num_seconds() < 0 {
-            delta = -delta;
-        }
-
-        let total_minutes = delta.num_minutes();
-        if total_minutes < 1 {
-            return Some("moments".to_string());
-        }
-
-        let hours = total_minutes / 60;
-        let minutes = total_minutes % 60;
-        let mut parts = Vec::new();
-
-        if hours > 0 {
-            let unit = if hours == 1 { "hour" } else { "hours" };
-            parts.push(format!("{hours} {unit}"));
-        }
-
-        if minutes > 0 {
-            let unit = if minutes == 1 { "minute" } else { "minutes" };
-            parts.push(format!("{minutes} {unit}"));
-        }
-
-        Some(parts.join(" "))
-    }
-
     fn format_time_range(
         start: Option<DateTime<Utc>>,
         end: Option<DateTime<Utc>>,
@@ -800,43 +1047,59 @@ mod wasm_ui {
         timestamp.format("%H:%M").to_string()
     }
 
-    fn format_numeric(value: f64) -> String {
-        if value.abs() >= 10.0 {
-            format!("{value:.0}")
-        } else {
-            format!("{value:.1}")
+    fn render_critical_item(item: &CriticalItem) -> Html {
+        html! {
+            <li class={scope::class("critical-item")}>
+                <div class={scope::class("critical-item-header")}>
+                    <span class={scope::class("critical-label")}>{ item.label.clone() }</span>
+                    { render_severity_badge("severity-badge", item.severity) }
+                    { render_watched_badge(item.watched) }
+                </div>
+                { item.detail.as_ref().map(render_detail).unwrap_or_default() }
+            </li>
         }
     }
 
-    fn format_measurement(value: f64, unit: Option<&str>) -> String {
-        let numeric = format_numeric(value);
-        match unit {
-            Some(unit) if !unit.is_empty() => format!("{numeric} {unit}"),
-            _ => numeric,
+    /// Severity badge from the integrator's renderer, or the default label span.
+    fn render_severity_badge(class: &str, severity: Severity) -> Html {
+        let renderer = render_context::with(|context| context.badge_renderer.clone());
+        match badge_content(severity, renderer.map(|renderer| move |severity| renderer.emit(severity))) {
+            BadgeContent::Custom(badge) => badge,
+            BadgeContent::Label { text, level } => html! {
+                <span class={scope::class(class)} data-level={level}>{ text }</span>
+            },
         }
     }
 
-    fn render_critical_item(item: &CriticalItem) -> Html {
-        let severity_label = severity_label(item.severity);
-        let severity_level = severity_level(item.severity);
+    fn render_detail(detail: &String) -> Html {
+        render_detail_text("critical-detail", detail)
+    }
+
+    /// Detail paragraph, or sanitized Markdown blocks when that mode is enabled.
+    fn render_detail_text(class_name: &str, detail: &str) -> Html {
+        if !markdown::enabled() {
+            return html! { <p class={scope::class(class_name)}>{ detail.to_string() }</p> };
+        }
+        // `to_html` escapes all input and only emits its own fixed tags.
+        let content = Html::from_html_unchecked(AttrValue::from(markdown::to_html(detail)));
         html! {
-            <li class="critical-item">
-                <div class="critical-item-header">
-                    <span class="critical-label">{ item.label.clone() }</span>
-                    <span class="severity-badge" data-level={severity_level}>{ severity_label }</span>
-                </div>
-                { item.detail.as_ref().map(render_detail).unwrap_or_default() }
-            </li>
+            <div class={classes!(scope::class(class_name), scope::class("is-markdown"))}>
+                { content }
+            </div>
         }
     }
 
-    fn render_detail(detail: &String) -> Html {
-        html! { <p class="critical-detail">{ detail.clone() }</p> }
+    fn render_watched_badge(watched: bool) -> Html {
+        if watched {
+            html! { <span class={scope::class("watched-badge")} title="On the watchlist">{"Watched"}</span> }
+        } else {
+            Html::default()
+        }
     }
 
     fn render_vital_item(vital: &VitalSnapshot) -> Html {
         let timestamp = format_timestamp(vital.recorded_at);
-        let relative = format_relative_time(vital.recorded_at);
+        let relative = density::secondary(format_relative_time(vital.recorded_at));
         let unit_to_render = vital.unit.as_ref().and_then(|unit| {
             let unit_lower = unit.to_ascii_lowercase();
             let value_lower = vital.value.to_ascii_lowercase();
@@ -847,54 +1110,50 @@ mod wasm_ui {
             }
         });
         html! {
-            <li class="vital-item">
-                <div class="vital-text">
-                    <span class="vital-name">{ vital.name.clone() }</span>
-                    <span class="vital-value">{ vital.value.clone() }</span>
-                    { unit_to_render.map(|unit| html! { <span class="vital-unit">{ unit }</span> }).unwrap_or_default() }
+            <li class={scope::class("vital-item")} data-watched={vital.watched.to_string()}>
+                <div class={scope::class("vital-text")}>
+                    <span class={scope::class("vital-name")}>{ vital.name.clone() }</span>
+                    { render_watched_badge(vital.watched) }
+                    <span class={scope::class("vital-value")}>{ locale::localize_value(&vital.value) }</span>
+                    { unit_to_render.map(|unit| html! { <span class={scope::class("vital-unit")}>{ unit }</span> }).unwrap_or_default() }
                 </div>
-                <div class="vital-meta">
-                    <span class="vital-time">{ timestamp }</span>
-                    { relative.map(|text| html! { <span class="vital-relative">{ text }</span> }).unwrap_or_default() }
+                <div class={scope::class("vital-meta")}>
+                    <span class={scope::class("vital-time")}>{ timestamp }</span>
+                    { relative.map(|text| html! { <span class={scope::class("vital-relative")}>{ text }</span> }).unwrap_or_default() }
                 </div>
             </li>
         }
     }
 
-    fn render_hot_strip(events: &[TimelineEvent]) -> Html {
-        let mut urgent: Vec<&TimelineEvent> = events
-            .iter()
-            .filter(|event| matches!(event.severity, Severity::Critical | Severity::High))
-            .collect();
-        urgent.sort_by(|a, b| compare_datetimes(b.occurred_at, a.occurred_at));
-        urgent.truncate(3);
-
-        if urgent.is_empty() {
+    fn render_hot_strip(events: &[TimelineEvent], pinned: &[String]) -> Html {
+        let items = hot_strip_items(events, pinned);
+        if items.is_empty() {
             return Html::default();
         }
 
         html! {
-            <aside class="hot-strip" aria-label="Urgent clinical events">
+            <aside class={scope::class("hot-strip")} aria-label="Urgent clinical events">
                 <h3>{"Priority watchlist"}</h3>
                 <ul>
-                    { for urgent.into_iter().map(render_hot_item) }
+                    { for items.into_iter().map(render_hot_item) }
                 </ul>
             </aside>
         }
     }
 
-    fn render_hot_item(event: &TimelineEvent) -> Html {
-        let relative = format_relative_time(event.occurred_at);
+    fn render_hot_item(item: HotItem<'_>) -> Html {
+        let event = item.event;
+        let relative = density::secondary(format_relative_time(event.occurred_at));
         html! {
-            <li class="hot-item">
-                <div class="hot-header">
-                    <span class="hot-title">{ event.title.clone() }</span>
-                    <span class="hot-severity" data-level={severity_level(event.severity)}>{ severity_label(event.severity) }</span>
+            <li class={scope::class("hot-item")} data-pinned={item.pinned.then_some("true")}>
+                <div class={scope::class("hot-header")}>
+                    <span class={scope::class("hot-title")}>{ event.title.clone() }</span>
+                    { render_severity_badge("hot-severity", event.severity) }
                 </div>
-                { event.detail.as_ref().map(|detail| html! { <p class="hot-detail">{ detail.clone() }</p> }).unwrap_or_default() }
-                <div class="hot-meta">
+                { event.detail.as_ref().map(|detail| html! { <p class={scope::class("hot-detail")}>{ detail.clone() }</p> }).unwrap_or_default() }
+                <div class={scope::class("hot-meta")}>
                     { relative.map(|text| html! { <span>{ text }</span> }).unwrap_or_default() }
-                    <span class="hot-category">{ category_label(event.category) }</span>
+                    <span class={scope::class("hot-category")}>{ category_label(event.category) }</span>
                 </div>
             </li>
         }
@@ -904,14 +1163,21 @@ mod wasm_ui {
         grouped_events: Vec<(String, Vec<&TimelineEvent>)>,
         expanded_groups: UseStateHandle<HashSet<String>>,
         expanded_snapshot: HashSet<String>,
+        focused_day: Option<NaiveDate>,
     ) -> Html {
         let mut bucket_totals: HashMap<&'static str, usize> = HashMap::new();
         let mut day_rows: Vec<DayRow<'_>> = Vec::new();
 
         for (index, (label, events)) in grouped_events.into_iter().enumerate() {
             let key = group_storage_key(&label, &events);
+            let day = events
+                .first()
+                .and_then(|event| event.occurred_at)
+                .map(|at| at.date_naive());
             let default_collapsed = should_collapse_group(index, &label, &events);
-            let is_expanded = expanded_snapshot.contains(&key) || !default_collapsed;
+            let is_expanded = expanded_snapshot.contains(&key)
+                || !default_collapsed
+                || (day.is_some() && day == focused_day);
             let summary = summarize_group(&events);
             let event_count = events.len();
             let mut buckets: HashMap<&'static str, Vec<&TimelineEvent>> = HashMap::new();
@@ -923,12 +1189,13 @@ mod wasm_ui {
             }
 
             for bucket_events in buckets.values_mut() {
-                bucket_events.sort_by(|a, b| compare_datetimes(b.occurred_at, a.occurred_at));
+                bucket_events.sort_by(|a, b| compare_events_newest_first(a, b));
             }
 
             day_rows.push(DayRow {
                 label,
                 key,
+                day,
                 summary,
                 default_collapsed,
                 is_expanded,
@@ -938,9 +1205,9 @@ mod wasm_ui {
         }
 
         html! {
-            <div class="timeline-category-grid">
-                <div class="timeline-category-head">
-                    <div class="timeline-category-corner">{"Day"}</div>
+            <div class={scope::class("timeline-category-grid")}>
+                <div class={scope::class("timeline-category-head")}>
+                    <div class={scope::class("timeline-category-corner")}>{"Day"}</div>
                     {
                         for TIMELINE_BUCKET_COLUMNS.iter().map(|(bucket, heading)| {
                             let count = bucket_totals.get(bucket).copied().unwrap_or(0);
@@ -959,9 +1226,9 @@ mod wasm_ui {
 
     fn render_category_header_cell(heading: &str, count: usize, bucket_slug: &'static str) -> Html {
         html! {
-            <div class="timeline-category-head-cell" data-bucket={bucket_slug}>
-                <span class="timeline-category-title">{ heading }</span>
-                <span class="timeline-category-count">{ count }</span>
+            <div class={scope::class("timeline-category-head-cell")} data-bucket={bucket_slug}>
+                <span class={scope::class("timeline-category-title")}>{ heading }</span>
+                <span class={scope::class("timeline-category-count")}>{ count }</span>
             </div>
         }
     }
@@ -973,7 +1240,11 @@ mod wasm_ui {
         let is_collapsed = row.default_collapsed && !row.is_expanded;
 
         html! {
-            <div class="timeline-category-row" data-group-key={row.key.clone()}>
+            <div
+                class={scope::class("timeline-category-row")}
+                data-group-key={row.key.clone()}
+                data-day={row.day.map(|day| day.to_string())}
+            >
                 { render_day_row_header(row, expanded_groups.clone()) }
                 {
                     for TIMELINE_BUCKET_COLUMNS.iter().map(|(bucket, _)| {
@@ -1003,7 +1274,7 @@ mod wasm_ui {
             html! {
                 <button
                     type="button"
-                    class="group-toggle"
+                    class={scope::class("group-toggle")}
                     aria-expanded={is_expanded.to_string()}
                     onclick={Callback::from(move |_| {
                         let mut next = (*handle).clone();
@@ -1025,15 +1296,15 @@ mod wasm_ui {
         html! {
             <div
                 class={classes!(
-                    "timeline-category-label",
-                    (!is_expanded && default_collapsed).then_some("is-collapsed"),
+                    scope::class("timeline-category-label"),
+                    (!is_expanded && default_collapsed).then(|| scope::class("is-collapsed")),
                 )}
             >
-                <span class="timeline-day-name">{ label }</span>
-                <span class="timeline-day-count">{ format!("{event_count} events") }</span>
+                <span class={scope::class("timeline-day-name")}>{ label }</span>
+                <span class={scope::class("timeline-day-count")}>{ format!("{event_count} events") }</span>
                 {
                     (!is_expanded && default_collapsed)
-                        .then(|| html! { <span class="timeline-day-summary">{ summary_text.clone() }</span> })
+                        .then(|| html! { <span class={scope::class("timeline-day-summary")}>{ summary_text.clone() }</span> })
                         .unwrap_or_default()
                 }
                 { button }
@@ -1044,24 +1315,24 @@ mod wasm_ui {
     fn render_category_cell(events: Option<&Vec<&TimelineEvent>>, is_collapsed: bool) -> Html {
         if is_collapsed {
             return html! {
-                <div class="timeline-category-cell is-collapsed">
-                    <span class="timeline-category-placeholder">{"Collapsed"}</span>
+                <div class={scope::class("timeline-category-cell is-collapsed")}>
+                    <span class={scope::class("timeline-category-placeholder")}>{"Collapsed"}</span>
                 </div>
             };
         }
 
         let Some(events) = events else {
             return html! {
-                <div class="timeline-category-cell is-empty">
-                    <span class="timeline-category-placeholder">{"--"}</span>
+                <div class={scope::class("timeline-category-cell is-empty")}>
+                    <span class={scope::class("timeline-category-placeholder")}>{"--"}</span>
                 </div>
             };
         };
 
         if events.is_empty() {
             return html! {
-                <div class="timeline-category-cell is-empty">
-                    <span class="timeline-category-placeholder">{"--"}</span>
+                <div class={scope::class("timeline-category-cell is-empty")}>
+                    <span class={scope::class("timeline-category-placeholder")}>{"--"}</span>
                 </div>
             };
         }
@@ -1069,7 +1340,7 @@ mod wasm_ui {
         let grouped = group_events_by_title(events.as_slice());
 
         html! {
-            <div class="timeline-category-cell">
+            <div class={scope::class("timeline-category-cell")}>
                 {
                     for grouped.iter().map(|group| render_grouped_category(group))
                 }
@@ -1080,14 +1351,14 @@ mod wasm_ui {
     fn group_events_by_title<'a>(events: &'a [&'a TimelineEvent]) -> Vec<GroupedEvents<'a>> {
         let mut grouped: BTreeMap<String, Vec<&'a TimelineEvent>> = BTreeMap::new();
 
-        for event in events {
-            grouped.entry(event.title.clone()).or_default().push(*event);
+        for (event, title) in events.iter().zip(chart_group_titles(events)) {
+            grouped.entry(title).or_default().push(*event);
         }
 
         let mut groups: Vec<GroupedEvents<'a>> = grouped
             .into_iter()
             .map(|(title, mut list)| {
-                list.sort_by(|a, b| compare_datetimes(b.occurred_at, a.occurred_at));
+                list.sort_by(|a, b| compare_events_newest_first(a, b));
                 GroupedEvents { title, events: list }
             })
             .collect();
@@ -1107,19 +1378,40 @@ mod wasm_ui {
         groups
     }
 
-    fn render_grouped_category(group: &GroupedEvents<'_>) -> Html {
-        let severity = group
-            .events
-            .iter()
-            .fold(Severity::Info, |current, event| {
-                if event.severity < current {
-                    event.severity
-                } else {
-                    current
+    /// "By system" view: one group per `source.system`, showing which EHR contributed what.
+    fn render_system_groups(events: &[&TimelineEvent]) -> Html {
+        let groups: Vec<GroupedEvents<'_>> = group_by_source_system(events)
+            .into_iter()
+            .map(|(title, events)| GroupedEvents { title, events })
+            .collect();
+
+        html! {
+            <div class={scope::class("timeline-system-groups")}>
+                { for groups.iter().map(render_grouped_category) }
+            </div>
+        }
+    }
+
+    fn render_event_stream(events: &[&TimelineEvent]) -> Html {
+        html! {
+            <ol class={scope::class("timeline-stream")}>
+                {
+                    for stream_days(events, Utc::now()).into_iter().map(|day| html! {
+                        <li class={scope::class("timeline-stream-day")}>
+                            <h3 class={scope::class("timeline-stream-day-label")}>{ day.label }</h3>
+                            <ul class={scope::class("timeline-stream-events")}>
+                                { for day.events.into_iter().map(render_event) }
+                            </ul>
+                        </li>
+                    })
                 }
-            });
-        let severity_label = severity_label(severity);
-        let severity_level = severity_level(severity);
+            </ol>
+        }
+    }
+
+    fn render_grouped_category(group: &GroupedEvents<'_>) -> Html {
+        let severity = Severity::most_severe(group.events.iter().map(|event| event.severity))
+            .unwrap_or(Severity::Info);
         let count = group.events.len();
         let count_label = if count == 1 {
             "1 entry".to_string()
@@ -1152,13 +1444,13 @@ mod wasm_ui {
 
         if let Some(range) = range_label {
             meta.push(html! {
-                <span class="timeline-group-range">{ format!("Range {range}") }</span>
+                <span class={scope::class("timeline-group-range")}>{ format!("Range {range}") }</span>
             });
         }
 
         if let Some(relative) = relative_label {
             meta.push(html! {
-                <span class="timeline-group-relative">{ format!("Last reading {relative}") }</span>
+                <span class={scope::class("timeline-group-relative")}>{ format!("Last reading {relative}") }</span>
             });
         }
 
@@ -1169,18 +1461,18 @@ mod wasm_ui {
             .unwrap_or_else(|| group.title.clone());
 
         html! {
-            <div class="timeline-category-group" key={key}>
-                <header class="timeline-group-header">
-                    <span class="timeline-group-title">{ group.title.clone() }</span>
-                    <span class="timeline-group-count">{ count_label }</span>
-                    <span class="severity-badge" data-level={severity_level}>{ severity_label }</span>
+            <div class={scope::class("timeline-category-group")} key={key}>
+                <header class={scope::class("timeline-group-header")}>
+                    <span class={scope::class("timeline-group-title")}>{ group.title.clone() }</span>
+                    <span class={scope::class("timeline-group-count")}>{ count_label }</span>
+                    { render_severity_badge("severity-badge", severity) }
                 </header>
                 {
                     if meta.is_empty() {
                         Html::default()
                     } else {
                         html! {
-                            <div class="timeline-group-meta">
+                            <div class={scope::class("timeline-group-meta")}>
                                 { for meta }
                             </div>
                         }
@@ -1196,16 +1488,16 @@ mod wasm_ui {
                         };
 
                         html! {
-                            <details class="timeline-group-details">
+                            <details class={scope::class("timeline-group-details")}>
                                 <summary>{ summary_label }</summary>
-                                <ul class="timeline-cell-list">
+                                <ul class={scope::class("timeline-cell-list")}>
                                     { for group.events.iter().map(|event| render_event(*event)) }
                                 </ul>
                             </details>
                         }
                     } else {
                         html! {
-                            <ul class="timeline-cell-list">
+                            <ul class={scope::class("timeline-cell-list")}>
                                 { for group.events.iter().map(|event| render_event(*event)) }
                             </ul>
                         }
@@ -1385,8 +1677,8 @@ mod wasm_ui {
             .collect::<Vec<_>>();
 
         html! {
-            <div class="timeline-group-chart" data-severity={severity_level}>
-                <div class="timeline-group-stats" data-series-count={data.series.len().to_string()}>
+            <div class={scope::class("timeline-group-chart")} data-severity={severity_level}>
+                <div class={scope::class("timeline-group-stats")} data-series-count={data.series.len().to_string()}>
                     { for stats }
                 </div>
                 { chart }
@@ -1405,59 +1697,44 @@ mod wasm_ui {
         const RIGHT_PAD: f64 = 16.0;
         const TOP_PAD: f64 = 14.0;
         const BOTTOM_PAD: f64 = 34.0;
+        // Value ticks are right-aligned 8 units left of the plot; keep a small
+        // margin from the view-box edge.
+        const Y_LABEL_WIDTH: f64 = LEFT_PAD - 12.0;
 
         let plot_width = VIEW_WIDTH - LEFT_PAD - RIGHT_PAD;
         let plot_height = VIEW_HEIGHT - TOP_PAD - BOTTOM_PAD;
         let severity_class = format!("is-{}", severity_level(severity));
 
-        let mut min_value = f64::INFINITY;
-        let mut max_value = f64::NEG_INFINITY;
-        for series in &data.series {
-            for point in &series.points {
-                min_value = min_value.min(point.value);
-                max_value = max_value.max(point.value);
-            }
-        }
-
-        if !min_value.is_finite() || !max_value.is_finite() {
+        let values = data
+            .series
+            .iter()
+            .flat_map(|series| series.points.iter().map(|point| point.value));
+        let area = chart::PlotArea {
+            left: LEFT_PAD,
+            top: TOP_PAD,
+            width: plot_width,
+            height: plot_height,
+        };
+        let Some(scale) = chart::ChartScale::new(values, data.start, data.end, area) else {
             return Html::default();
-        }
-
-        if (max_value - min_value).abs() < f64::EPSILON {
-            let padding = (max_value.abs().max(1.0)) * 0.05;
-            min_value -= padding;
-            max_value += padding;
-        }
-
-        let span = (max_value - min_value).abs();
-        let padding = if span < 5.0 { 1.0 } else { span * 0.1 };
-        let axis_min = min_value - padding;
-        let axis_max = max_value + padding;
-        let axis_range = (axis_max - axis_min).max(1e-3);
-
-        let duration = data.end.signed_duration_since(data.start);
-        let mut total_seconds = duration.num_seconds() as f64;
-        if total_seconds.abs() < 60.0 {
-            total_seconds = 60.0;
-        }
+        };
 
         let mut path_elements: Vec<Html> = Vec::new();
         let mut point_elements: Vec<Html> = Vec::new();
 
+        let baseline_y = TOP_PAD + plot_height;
+
         for (index, series) in data.series.iter().enumerate() {
+            let style = chart::line_style(severity, index > 0);
             let mut path = String::new();
             let mut first_point = true;
+            let mut x_extent: Option<(f64, f64)> = None;
 
             for point in &series.points {
-                let offset = point
-                    .timestamp
-                    .signed_duration_since(data.start)
-                    .num_seconds() as f64;
-                let ratio_x = (offset / total_seconds).clamp(0.0, 1.0);
-                let x = LEFT_PAD + ratio_x * plot_width;
+                let x = scale.x(point.timestamp);
+                let y = scale.y(point.value);
 
-                let ratio_y = ((point.value - axis_min) / axis_range).clamp(0.0, 1.0);
-                let y = TOP_PAD + (1.0 - ratio_y) * plot_height;
+                x_extent = Some(x_extent.map_or((x, x), |(start, _)| (start, x)));
 
                 if first_point {
                     path.push_str(&format!("M{:.2},{:.2}", x, y));
@@ -1467,9 +1744,9 @@ mod wasm_ui {
                 }
 
                 let point_class = classes!(
-                    "timeline-chart-point",
-                    severity_class.clone(),
-                    (index > 0).then_some("is-secondary")
+                    scope::class("timeline-chart-point"),
+                    scope::class(&chart::point_severity_class(&series.label, point.value, severity)),
+                    (index > 0).then(|| scope::class("is-secondary"))
                 );
 
                 let tooltip_time = match mode {
@@ -1484,7 +1761,7 @@ mod wasm_ui {
                         class={point_class.clone()}
                         cx={format!("{:.2}", x)}
                         cy={format!("{:.2}", y)}
-                        r="3"
+                        r={style.point_radius}
                     >
                         <title>{ format!("{tooltip_time} – {tooltip_value}") }</title>
                     </circle>
@@ -1492,28 +1769,46 @@ mod wasm_ui {
             }
 
             if !path.is_empty() {
+                if let (true, Some((start_x, end_x))) = (style.fill_area, x_extent) {
+                    let area = format!(
+                        "{path} L{end_x:.2},{baseline_y:.2} L{start_x:.2},{baseline_y:.2} Z"
+                    );
+                    path_elements.push(html! {
+                        <path
+                            class={classes!(scope::class("timeline-chart-area"), scope::class(&severity_class))}
+                            d={area}
+                        />
+                    });
+                }
+
                 let line_class = classes!(
-                    "timeline-chart-line",
-                    severity_class.clone(),
-                    (index > 0).then_some("is-secondary")
+                    scope::class("timeline-chart-line"),
+                    scope::class(&severity_class),
+                    (index > 0).then(|| scope::class("is-secondary"))
                 );
 
                 path_elements.push(html! {
-                    <path class={line_class} d={path.clone()} />
+                    <path
+                        class={line_class}
+                        d={path.clone()}
+                        stroke-width={style.stroke_width}
+                        style={series_color_style(&series.label)}
+                    />
                 });
             }
         }
 
-        let y_ticks = build_value_ticks(axis_min, axis_max, data.unit.as_deref());
-        let x_ticks = build_time_ticks(data, mode, total_seconds);
+        let y_ticks = build_value_ticks(scale.axis_min, scale.axis_max, data.unit.as_deref());
+        let x_ticks = build_time_ticks(data, mode, scale.total_seconds);
+        let x_label_width = plot_width / x_ticks.len().max(1) as f64;
 
         let grid_lines: Vec<Html> = y_ticks
             .iter()
             .map(|(value, label)| {
-                let ratio = ((value - axis_min) / axis_range).clamp(0.0, 1.0);
-                let y = TOP_PAD + (1.0 - ratio) * plot_height;
+                let y = scale.y(*value);
+                let fitted = chart::fit_label(label, Y_LABEL_WIDTH);
                 html! {
-                    <g class="timeline-chart-grid-row">
+                    <g class={scope::class("timeline-chart-grid-row")}>
                         <line
                             x1={format!("{:.2}", LEFT_PAD)}
                             y1={format!("{:.2}", y)}
@@ -1523,9 +1818,9 @@ mod wasm_ui {
                         <text
                             x={format!("{:.2}", LEFT_PAD - 8.0)}
                             y={format!("{:.2}", y + 4.0)}
-                            class="timeline-chart-tick"
+                            class={scope::class("timeline-chart-tick")}
                         >
-                            { label.clone() }
+                            { render_fitted_label(label, fitted) }
                         </text>
                     </g>
                 }
@@ -1536,8 +1831,9 @@ mod wasm_ui {
             .iter()
             .map(|(ratio, label)| {
                 let x = LEFT_PAD + ratio * plot_width;
+                let fitted = chart::fit_label(label, x_label_width);
                 html! {
-                    <g class="timeline-chart-grid-col">
+                    <g class={scope::class("timeline-chart-grid-col")}>
                         <line
                             x1={format!("{:.2}", x)}
                             y1={format!("{:.2}", TOP_PAD)}
@@ -1547,9 +1843,9 @@ mod wasm_ui {
                         <text
                             x={format!("{:.2}", x)}
                             y={format!("{:.2}", TOP_PAD + plot_height + 16.0)}
-                            class="timeline-chart-tick"
+                            class={scope::class("timeline-chart-tick")}
                         >
-                            { label.clone() }
+                            { render_fitted_label(label, fitted) }
                         </text>
                     </g>
                 }
@@ -1562,15 +1858,15 @@ mod wasm_ui {
         };
 
         html! {
-            <div class="timeline-group-plot" data-mode={mode_label}>
+            <div class={scope::class("timeline-group-plot")} data-mode={mode_label}>
                 <svg
                     viewBox={format!("0 0 {:.0} {:.0}", VIEW_WIDTH, VIEW_HEIGHT)}
-                    class="timeline-group-chart-plot"
+                    class={scope::class("timeline-group-chart-plot")}
                     role="img"
                     aria-hidden="true"
                 >
                     <rect
-                        class="timeline-chart-surface"
+                        class={scope::class("timeline-chart-surface")}
                         x={format!("{:.2}", LEFT_PAD)}
                         y={format!("{:.2}", TOP_PAD)}
                         width={format!("{:.2}", plot_width)}
@@ -1581,14 +1877,14 @@ mod wasm_ui {
                     { for grid_lines }
                     { for column_lines }
                     <line
-                        class="timeline-chart-axis-line"
+                        class={scope::class("timeline-chart-axis-line")}
                         x1={format!("{:.2}", LEFT_PAD)}
                         y1={format!("{:.2}", TOP_PAD + plot_height)}
                         x2={format!("{:.2}", LEFT_PAD + plot_width)}
                         y2={format!("{:.2}", TOP_PAD + plot_height)}
                     />
                     <line
-                        class="timeline-chart-axis-line"
+                        class={scope::class("timeline-chart-axis-line")}
                         x1={format!("{:.2}", LEFT_PAD)}
                         y1={format!("{:.2}", TOP_PAD)}
                         x2={format!("{:.2}", LEFT_PAD)}
@@ -1601,6 +1897,16 @@ mod wasm_ui {
         }
     }
 
+    /// Tick text shortened to its slot; the full label stays available as an
+    /// SVG tooltip when it had to be ellipsized.
+    fn render_fitted_label(full: &str, fitted: chart::FittedLabel) -> Html {
+        if fitted.truncated {
+            html! { <>{ fitted.text }<title>{ full.to_string() }</title></> }
+        } else {
+            html! { { fitted.text } }
+        }
+    }
+
     fn build_value_ticks(min: f64, max: f64, unit: Option<&str>) -> Vec<(f64, String)> {
         if !min.is_finite() || !max.is_finite() {
             return Vec::new();
@@ -1711,6 +2017,15 @@ mod wasm_ui {
         ticks
     }
 
+    /// Inline custom property giving a series its name-derived colour, read by the
+    /// chart line and the stat swatch.
+    fn series_color_style(label: &str) -> String {
+        format!(
+            "--timeline-series-color: var(--timeline-{})",
+            chart::series_color(label)
+        )
+    }
+
     fn render_series_stats(series: &MeasurementSeries<'_>, unit: Option<&str>) -> Html {
         if series.points.is_empty() {
             return Html::default();
@@ -1733,14 +2048,14 @@ mod wasm_ui {
         let (delta_label, delta_trend) = format_delta_display(latest.value - first.value, unit);
 
         html! {
-            <div class="timeline-group-stat-block">
-                <div class="stat-header">
-                    <span class="stat-label">{ series.label.clone() }</span>
-                    <span class="stat-value">{ latest_label }</span>
+            <div class={scope::class("timeline-group-stat-block")} style={series_color_style(&series.label)}>
+                <div class={scope::class("stat-header")}>
+                    <span class={scope::class("stat-label")}>{ series.label.clone() }</span>
+                    <span class={scope::class("stat-value")}>{ latest_label }</span>
                 </div>
-                <div class="stat-meta">
-                    <span class="stat-delta" data-trend={delta_trend}>{ delta_label }</span>
-                    <span class="stat-range">{ format!("Low {min_label} • High {max_label}") }</span>
+                <div class={scope::class("stat-meta")}>
+                    <span class={scope::class("stat-delta")} data-trend={delta_trend}>{ delta_label }</span>
+                    <span class={scope::class("stat-range")}>{ format!("Low {min_label} • High {max_label}") }</span>
                 </div>
             </div>
         }
@@ -1785,50 +2100,173 @@ mod wasm_ui {
     }
 
     fn render_event(event: &TimelineEvent) -> Html {
-        let severity_label = severity_label(event.severity);
         let severity_level = severity_level(event.severity);
-        let timestamp = format_timestamp(event.occurred_at);
-        let relative = format_relative_time(event.occurred_at);
+        let timestamp = labels::format_timestamp(event.occurred_at, event.occurred_at_precision);
+        let relative = density::secondary(format_relative_time(event.occurred_at));
         let category = category_label(event.category);
         let severity_class = format!("is-{}", severity_level);
 
         html! {
-            <li class={classes!("timeline-event", severity_class)}>
-                <div class="timeline-meta">
-                    <span class="timeline-time">{ timestamp }</span>
-                    { relative.map(|text| html! { <span class="timeline-relative">{ text }</span> }).unwrap_or_default() }
-                    <span class="timeline-category">{ category }</span>
-                    <span class="timeline-severity" data-level={severity_level}>{ severity_label }</span>
+            <li class={classes!(scope::class("timeline-event"), scope::class(&severity_class))}>
+                <div class={scope::class("timeline-meta")}>
+                    <span class={scope::class("timeline-time")}>{ timestamp }</span>
+                    { relative.map(|text| html! { <span class={scope::class("timeline-relative")}>{ text }</span> }).unwrap_or_default() }
+                    <span class={scope::class("timeline-category")}>{ category }</span>
+                    { render_severity_badge("timeline-severity", event.severity) }
                 </div>
-                <div class="timeline-body">
-                    <h3 class="timeline-title">{ event.title.clone() }</h3>
+                <div class={scope::class("timeline-body")}>
+                    <h3 class={scope::class("timeline-title")}>{ event.title.clone() }</h3>
+                    { render_event_tags(&event.tags) }
                     { event.detail.as_ref().map(render_event_detail).unwrap_or_default() }
+                    { for event.notes.iter().map(|note| render_detail_text("timeline-note", note)) }
                     { render_event_source(event) }
                 </div>
             </li>
         }
     }
 
+    fn render_duration_track(events: &[&TimelineEvent]) -> Html {
+        const LABEL_WIDTH: f64 = 180.0;
+        const AXIS_WIDTH: f64 = 420.0;
+        const ROW_HEIGHT: f64 = 24.0;
+
+        let Some(axis) = chart::duration_axis(events) else {
+            return Html::default();
+        };
+        let height = ROW_HEIGHT * events.len() as f64;
+
+        let rows = events.iter().enumerate().filter_map(|(index, event)| {
+            let start = event.occurred_at?;
+            let end = event.period_end?;
+            let bar = chart::duration_bar(start, end, axis, AXIS_WIDTH);
+            let y = ROW_HEIGHT * index as f64;
+            let severity_class = format!("is-{}", severity_level(event.severity));
+            let tooltip = format!(
+                "{}: {} – {}",
+                event.title,
+                format_timestamp(Some(start)),
+                format_timestamp(Some(end))
+            );
+            Some(html! {
+                <g class={scope::class("duration-row")}>
+                    <title>{ tooltip }</title>
+                    <text
+                        class={scope::class("duration-label")}
+                        x="0"
+                        y={format!("{:.2}", y + ROW_HEIGHT * 0.65)}
+                    >{ event.title.clone() }</text>
+                    <rect
+                        class={classes!(scope::class("duration-bar"), scope::class(&severity_class))}
+                        x={format!("{:.2}", LABEL_WIDTH + bar.x)}
+                        y={format!("{:.2}", y + 5.0)}
+                        width={format!("{:.2}", bar.width)}
+                        height={format!("{:.2}", ROW_HEIGHT - 10.0)}
+                        rx="4"
+                        ry="4"
+                    />
+                </g>
+            })
+        });
+
+        html! {
+            <section class={scope::class("duration-track")} aria-label="Medication and encounter durations">
+                <svg
+                    viewBox={format!("0 0 {:.0} {:.0}", LABEL_WIDTH + AXIS_WIDTH, height)}
+                    class={scope::class("duration-plot")}
+                    role="img"
+                >
+                    { for rows }
+                </svg>
+                <div class={scope::class("duration-axis")}>
+                    <span>{ format_timestamp(Some(axis.0)) }</span>
+                    <span>{ format_timestamp(Some(axis.1)) }</span>
+                </div>
+            </section>
+        }
+    }
+
+    fn render_minimap(
+        snapshot: &TimelineSnapshot,
+        focused_day: UseStateHandle<Option<NaiveDate>>,
+    ) -> Html {
+        let bars = minimap_bars(snapshot, MAX_BARS);
+        if bars.is_empty() {
+            return Html::default();
+        }
+
+        html! {
+            <nav class={scope::class("timeline-minimap")} aria-label="Timeline overview">
+                {
+                    for bars.into_iter().map(|bar| {
+                        let label = bar.label();
+                        let height = format!("height: {:.0}%", bar.scale * 100.0);
+                        let level = bar.severity.map(severity_level);
+                        let onclick = bar.target.map(|day| {
+                            let focused_day = focused_day.clone();
+                            Callback::from(move |_| {
+                                focused_day.set(Some(day));
+                                scroll_to_day(day);
+                            })
+                        });
+                        html! {
+                            <button
+                                type="button"
+                                class={scope::class("minimap-bar")}
+                                data-level={level}
+                                disabled={bar.target.is_none()}
+                                title={label.clone()}
+                                aria-label={label}
+                                {onclick}
+                            >
+                                <span class={scope::class("minimap-fill")} style={height}></span>
+                            </button>
+                        }
+                    })
+                }
+            </nav>
+        }
+    }
+
+    /// Scroll the day row of `day` into view, if it is rendered.
+    fn scroll_to_day(day: NaiveDate) {
+        let row = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| {
+                document
+                    .query_selector(&format!("[data-day=\"{day}\"]"))
+                    .ok()
+                    .flatten()
+            });
+        if let Some(row) = row {
+            row.scroll_into_view();
+        }
+    }
+
+    fn render_event_tags(tags: &[String]) -> Html {
+        if tags.is_empty() {
+            return Html::default();
+        }
+
+        html! {
+            <ul class={scope::class("timeline-tags")}>
+                { for tags.iter().map(|tag| html! { <li class={scope::class("timeline-tag")}>{ tag.clone() }</li> }) }
+            </ul>
+        }
+    }
+
     fn render_event_detail(detail: &String) -> Html {
-        html! { <p class="timeline-detail">{ detail.clone() }</p> }
+        render_detail_text("timeline-detail", detail)
     }
 
     fn render_event_source(event: &TimelineEvent) -> Html {
-        let Some(source) = event.source.as_ref() else {
+        let Some((system, display)) = density::source_chip(event.source.as_ref()) else {
             return Html::default();
         };
 
-        let system = source.system.as_deref().unwrap_or("FHIR");
-        let display = source
-            .display
-            .clone()
-            .or_else(|| source.reference.clone())
-            .unwrap_or_else(|| "Unknown source".to_string());
-
         html! {
-            <div class="timeline-source">
-                <span class="timeline-source-system">{ system }</span>
-                <span class="timeline-source-display">{ display }</span>
+            <div class={scope::class("timeline-source")}>
+                <span class={scope::class("timeline-source-system")}>{ system }</span>
+                <span class={scope::class("timeline-source-display")}>{ display }</span>
             </div>
         }
     }
@@ -1859,74 +2297,12 @@ mod wasm_ui {
 
         let mut phrases: Vec<String> = counts
             .into_iter()
-            .map(|(bucket, count)| format_bucket_phrase(bucket, count))
+            .map(|(bucket, count)| plural::bucket_phrase(bucket, count))
             .collect();
         phrases.sort();
         phrases.join(", ")
     }
 
-    fn categorize_event_for_summary(event: &TimelineEvent) -> &'static str {
-        match event.category {
-            EventCategory::Observation => {
-                let title = event.title.to_lowercase();
-                if is_vital_title(&title) {
-                    "Vitals"
-                } else if is_imaging_title(&title) {
-                    "Imaging"
-                } else if is_lab_title(&title) {
-                    "Labs"
-                } else {
-                    "Observations"
-                }
-            }
-            EventCategory::Medication => "Medications",
-            EventCategory::Condition => "Conditions",
-            EventCategory::Procedure => "Procedures",
-            EventCategory::Encounter => "Encounters",
-            EventCategory::Document => "Documents",
-            EventCategory::Note => "Notes",
-            EventCategory::Other => "Events",
-        }
-    }
-
-    fn is_vital_title(title: &str) -> bool {
-        VITAL_EVENT_KEYWORDS.iter().any(|keyword| title.contains(keyword))
-    }
-
-    fn is_lab_title(title: &str) -> bool {
-        LAB_EVENT_KEYWORDS.iter().any(|keyword| title.contains(keyword))
-    }
-
-    fn is_imaging_title(title: &str) -> bool {
-        IMAGING_EVENT_KEYWORDS
-            .iter()
-            .any(|keyword| title.split_whitespace().any(|token| token == *keyword))
-    }
-
-    fn format_bucket_phrase(bucket: &str, count: usize) -> String {
-        match bucket {
-            "Vitals" => pluralize(count, "vital", "vitals"),
-            "Labs" => pluralize(count, "lab", "labs"),
-            "Imaging" => pluralize(count, "study", "studies"),
-            "Observations" => pluralize(count, "observation", "observations"),
-            "Medications" => pluralize(count, "medication", "medications"),
-            "Conditions" => pluralize(count, "condition", "conditions"),
-            "Procedures" => pluralize(count, "procedure", "procedures"),
-            "Encounters" => pluralize(count, "encounter", "encounters"),
-            "Documents" => pluralize(count, "document", "documents"),
-            "Notes" => pluralize(count, "note", "notes"),
-            _ => pluralize(count, "event", "events"),
-        }
-    }
-
-    fn pluralize(count: usize, singular: &str, plural: &str) -> String {
-        if count == 1 {
-            format!("1 {singular}")
-        } else {
-            format!("{count} {plural}")
-        }
-    }
-
     fn category_label(category: EventCategory) -> &'static str {
         match category {
             EventCategory::Encounter => "Encounter",
@@ -1940,26 +2316,6 @@ mod wasm_ui {
         }
     }
 
-    fn severity_label(severity: Severity) -> &'static str {
-        match severity {
-            Severity::Critical => "Critical",
-            Severity::High => "High",
-            Severity::Moderate => "Moderate",
-            Severity::Low => "Low",
-            Severity::Info => "Info",
-        }
-    }
-
-    fn severity_level(severity: Severity) -> &'static str {
-        match severity {
-            Severity::Critical => "critical",
-            Severity::High => "high",
-            Severity::Moderate => "moderate",
-            Severity::Low => "low",
-            Severity::Info => "info",
-        }
-    }
-
     fn event_matches_filters(event: &TimelineEvent, filters: &FilterState) -> bool {
         if let Some(level) = filters.severity {
             if event.severity > level {
@@ -1983,77 +2339,12 @@ mod wasm_ui {
         haystack
             .into_iter()
             .flatten()
+            .chain(event.tags.iter().map(String::as_str))
             .any(|text| text.to_lowercase().contains(&query))
     }
 
     fn format_timestamp(timestamp: Option<DateTime<Utc>>) -> String {
-        timestamp
-            .map(|dt| dt.format("%m/%d/%Y %H:%M").to_string())
-            .unwrap_or_else(|| "--".to_string())
-    }
-
-    fn format_relative_time(timestamp: Option<DateTime<Utc>>) -> Option<String> {
-        let timestamp = timestamp?;
-        let now = Utc::now();
-        let delta = now.signed_duration_since(timestamp);
-        let is_future = delta.num_seconds() < 0;
-        let abs_delta: Duration = delta.abs();
-
-        if abs_delta.num_days() >= 1 {
-            let value = abs_delta.num_days();
-            let unit = if value == 1 { "day" } else { "days" };
-            if is_future {
-                Some(format!("in {value} {unit}"))
-            } else {
-                Some(format!("{value} {unit} ago"))
-            }
-        } else if abs_delta.num_hours() >= 1 {
-            let value = abs_delta.num_hours();
-            let unit = if value == 1 { "hour" } else { "hours" };
-            if is_future {
-                Some(format!("in {value} {unit}"))
-            } else {
-                Some(format!("{value} {unit} ago"))
-            }
-        } else if abs_delta.num_minutes() >= 1 {
-            let value = abs_delta.num_minutes();
-            let unit = if value == 1 { "minute" } else { "minutes" };
-            if is_future {
-                Some(format!("in {value} {unit}"))
-            } else {
-                Some(format!("{value} {unit} ago"))
-            }
-        } else {
-            if is_future {
-                Some("in moments".to_string())
-            } else {
-                Some("just now".to_string())
-            }
-        }
-    }
-
-    fn format_day_label(timestamp: Option<DateTime<Utc>>) -> String {
-        let Some(dt) = timestamp else {
-            return "Unknown time".to_string();
-        };
-
-        let today: NaiveDate = Utc::now().date_naive();
-        let date = dt.date_naive();
-        let delta_days = today.signed_duration_since(date).num_days();
-
-        if delta_days == 0 {
-            "Today".to_string()
-        } else if delta_days == 1 {
-            "Yesterday".to_string()
-        } else if delta_days == -1 {
-            "Tomorrow".to_string()
-        } else if (2..=6).contains(&delta_days) {
-            format!("{delta_days} days ago")
-        } else if (-6..=-2).contains(&delta_days) {
-            format!("in {} days", delta_days.abs())
-        } else {
-            dt.format("%m/%d/%Y").to_string()
-        }
+        labels::format_timestamp(timestamp, DatePrecision::DateTime)
     }
 
     fn group_events_by_day<'a>(
@@ -2062,9 +2353,10 @@ mod wasm_ui {
         let mut groups: Vec<(String, Vec<&'a TimelineEvent>)> = Vec::new();
         let mut current_label: Option<String> = None;
         let mut bucket: Vec<&'a TimelineEvent> = Vec::new();
+        let now = Utc::now();
 
         for event in events {
-            let label = format_day_label(event.occurred_at);
+            let label = format_day_label(event.occurred_at, now);
             match current_label {
                 Some(ref current) if current == &label => {
                     bucket.push(*event);
@@ -2086,6 +2378,11 @@ mod wasm_ui {
         groups
     }
 
+    /// Newest first; same-instant events keep the core tiebreak (severity, then id).
+    fn compare_events_newest_first(a: &TimelineEvent, b: &TimelineEvent) -> Ordering {
+        compare_datetimes(b.occurred_at, a.occurred_at).then_with(|| a.tiebreak_cmp(b))
+    }
+
     fn compare_datetimes(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>) -> Ordering {
         match (a, b) {
             (Some(a), Some(b)) => a.cmp(&b),
@@ -2096,7 +2393,43 @@ mod wasm_ui {
     }
 
     #[wasm_bindgen]
-    pub fn mount_timeline_view(selector: &str, snapshot: JsValue) -> Result<(), JsValue> {
+    pub fn mount_timeline_view(
+        selector: &str,
+        snapshot: JsValue,
+        options: Option<JsValue>,
+    ) -> Result<(), JsValue> {
+        let snapshot = decode_snapshot(serde_wasm_bindgen::Deserializer::from(snapshot))
+            .map_err(|message| {
+                snapshot_error(format!(
+                    "{message}. Large snapshots can be passed as a JSON string to mount_timeline_view_json."
+                ))
+            })?;
+        mount(selector, snapshot, options)
+    }
+
+    /// Same as [`mount_timeline_view`] for a snapshot serialized with
+    /// `JSON.stringify`, bypassing the `JsValue` conversion for large payloads.
+    #[wasm_bindgen]
+    pub fn mount_timeline_view_json(
+        selector: &str,
+        snapshot_json: &str,
+        options: Option<JsValue>,
+    ) -> Result<(), JsValue> {
+        let snapshot = snapshot_from_json(snapshot_json).map_err(snapshot_error)?;
+        mount(selector, snapshot, options)
+    }
+
+    fn snapshot_error(message: String) -> JsValue {
+        let error = JsValue::from_str(&message);
+        console::error_1(&error);
+        error
+    }
+
+    fn mount(
+        selector: &str,
+        snapshot: TimelineSnapshot,
+        options: Option<JsValue>,
+    ) -> Result<(), JsValue> {
         let window: Window =
             web_sys::window().ok_or_else(|| JsValue::from_str("window is not available"))?;
         let document: Document = window
@@ -2108,19 +2441,69 @@ mod wasm_ui {
             .map_err(|err| JsValue::from_str(&format!("Selector error: {err:?}")))?
             .ok_or_else(|| JsValue::from_str("Element not found for selector"))?;
 
-        let snapshot: TimelineSnapshot = from_value(snapshot)?;
+        let options: JsViewOptions = match options {
+            Some(value) if !value.is_undefined() && !value.is_null() => from_value(value)?,
+            _ => JsViewOptions::default(),
+        };
 
-        yew::Renderer::<TimelineView>::with_root_and_props(target, TimelineViewProps { snapshot })
-            .render();
+        yew::Renderer::<TimelineView>::with_root_and_props(
+            target,
+            TimelineViewProps {
+                snapshot,
+                style_scope: options.style_scope,
+                relative_time_granularity: options.relative_time_granularity,
+                density: options.density,
+                duration_bars: options.duration_bars,
+                locale: options.locale,
+                severity_shortcuts: options.severity_shortcuts,
+                markdown_details: options.markdown_details,
+                vital_order: options.vital_order,
+                trend_window_hours: options.trend_window_hours,
+                badge_renderer: None,
+                critical_sections: options.critical_sections,
+                minimap: options.minimap,
+                trend_delta_thresholds: options.trend_delta_thresholds,
+                group_by_class: options.group_by_class,
+                class_map: options.class_map,
+                hot_strip_pinned: options.hot_strip_pinned,
+                weighted_trend_insights: options.weighted_trend_insights,
+                layout: options.layout,
+            },
+        )
+        .render();
         Ok(())
     }
 }
 
 #[cfg(target_arch = "wasm32")]
-pub use wasm_ui::mount_timeline_view;
+pub use png_export::render_trend_png;
+#[cfg(target_arch = "wasm32")]
+pub use wasm_ui::{mount_timeline_view, mount_timeline_view_json};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn mount_timeline_view(
+    _: &str,
+    _: wasm_bindgen::JsValue,
+    _: Option<wasm_bindgen::JsValue>,
+) -> Result<(), wasm_bindgen::JsValue> {
+    Err(wasm_bindgen::JsValue::from_str(
+        "timeline-ui only supports the wasm32 compilation target",
+    ))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn mount_timeline_view_json(
+    _: &str,
+    _: &str,
+    _: Option<wasm_bindgen::JsValue>,
+) -> Result<(), wasm_bindgen::JsValue> {
+    Err(wasm_bindgen::JsValue::from_str(
+        "timeline-ui only supports the wasm32 compilation target",
+    ))
+}
 
 #[cfg(not(target_arch = "wasm32"))]
-pub fn mount_timeline_view(_: &str, _: wasm_bindgen::JsValue) -> Result<(), wasm_bindgen::JsValue> {
+pub fn render_trend_png(_: &str, _: u32, _: u32) -> Result<Vec<u8>, wasm_bindgen::JsValue> {
     Err(wasm_bindgen::JsValue::from_str(
         "timeline-ui only supports the wasm32 compilation target",
     ))
@@ -0,0 +1,177 @@
+//! Locale-aware number formatting for rendered values.
+//!
+//! Only numbers the UI formats itself are localized: chart ticks, stats and the
+//! leading measurement of vital and result values. Free-text details are shown
+//! as written, and snapshot data and machine-readable exports keep the
+//! canonical `.` decimal.
+
+use serde::Deserialize;
+
+use crate::render_context;
+
+/// Locale of rendered numbers, given as a BCP 47 tag in view options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum Locale {
+    /// `1,250.5`
+    #[default]
+    #[serde(rename = "en-US")]
+    EnUs,
+    /// `1.250,5`
+    #[serde(rename = "de-DE")]
+    DeDe,
+    /// `1 250,5` (narrow no-break space grouping)
+    #[serde(rename = "fr-FR")]
+    FrFr,
+    /// `1.250,5`
+    #[serde(rename = "vi-VN")]
+    ViVn,
+}
+
+impl Locale {
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Locale::EnUs => '.',
+            Locale::DeDe | Locale::FrFr | Locale::ViVn => ',',
+        }
+    }
+
+    pub fn group_separator(self) -> char {
+        match self {
+            Locale::EnUs => ',',
+            Locale::DeDe | Locale::ViVn => '.',
+            Locale::FrFr => '\u{202F}',
+        }
+    }
+}
+
+/// Locale of the current render pass.
+pub(crate) fn active() -> Locale {
+    render_context::with(|context| context.locale)
+}
+
+/// Format `value` with `decimals` fraction digits in the active locale.
+pub(crate) fn format_number(value: f64, decimals: usize) -> String {
+    format_number_in(active(), value, decimals)
+}
+
+/// Format `value` with `decimals` fraction digits and grouped thousands.
+pub(crate) fn format_number_in(locale: Locale, value: f64, decimals: usize) -> String {
+    let canonical = format!("{:.*}", decimals, value.abs());
+    let (integer, fraction) = match canonical.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (canonical.as_str(), None),
+    };
+
+    let mut output = String::new();
+    if value.is_sign_negative() && canonical.chars().any(|c| c != '0' && c != '.') {
+        output.push('-');
+    }
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            output.push(locale.group_separator());
+        }
+        output.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        output.push(locale.decimal_separator());
+        output.push_str(fraction);
+    }
+    output
+}
+
+/// `value` with its leading measurement (the "37.5" of "37.5 Cel", or both
+/// numbers of "120/80 mmHg") re-formatted in the active locale. Values that do
+/// not start with a plain number, such as codes ("E11.9"), dates or free text,
+/// are returned unchanged.
+pub(crate) fn localize_value(value: &str) -> String {
+    localize_value_in(active(), value)
+}
+
+pub(crate) fn localize_value_in(locale: Locale, value: &str) -> String {
+    let (measurement, rest) = match value.split_once(' ') {
+        Some((measurement, rest)) => (measurement, Some(rest)),
+        None => (value, None),
+    };
+    let numbers: Option<Vec<String>> = measurement
+        .split('/')
+        .map(|number| format_plain_number(locale, number))
+        .collect();
+    let Some(numbers) = numbers else {
+        return value.to_string();
+    };
+
+    let mut output = numbers.join("/");
+    if let Some(rest) = rest {
+        output.push(' ');
+        output.push_str(rest);
+    }
+    output
+}
+
+/// `text` re-formatted when it is a plain decimal number ("-12", "5.40"),
+/// keeping its number of fraction digits.
+fn format_plain_number(locale: Locale, text: &str) -> Option<String> {
+    let unsigned = text.strip_prefix('-').unwrap_or(text);
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+        Some(_) => return None,
+        None => (unsigned, ""),
+    };
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+        return None;
+    }
+    let value: f64 = text.parse().ok()?;
+    Some(format_number_in(locale, value, fraction.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_context::RenderContext;
+
+    #[test]
+    fn en_us_uses_period_decimal_and_comma_grouping() {
+        assert_eq!(format_number_in(Locale::EnUs, 37.5, 1), "37.5");
+        assert_eq!(format_number_in(Locale::EnUs, 250000.0, 0), "250,000");
+        assert_eq!(format_number_in(Locale::EnUs, -1234.56, 2), "-1,234.56");
+    }
+
+    #[test]
+    fn european_locales_use_comma_decimal() {
+        assert_eq!(format_number_in(Locale::DeDe, 37.5, 1), "37,5");
+        assert_eq!(format_number_in(Locale::DeDe, 1250.5, 1), "1.250,5");
+        assert_eq!(format_number_in(Locale::FrFr, 1250.5, 1), "1\u{202F}250,5");
+        assert_eq!(format_number_in(Locale::ViVn, 0.04, 1), "0,0");
+    }
+
+    #[test]
+    fn active_locale_is_restored_after_render() {
+        {
+            let _context = RenderContext {
+                locale: Locale::DeDe,
+                ..RenderContext::default()
+            }
+            .enter();
+            assert_eq!(format_number(98.6, 1), "98,6");
+            assert_eq!(localize_value("37.5 Cel"), "37,5 Cel");
+        }
+        assert_eq!(format_number(98.6, 1), "98.6");
+    }
+
+    #[test]
+    fn only_leading_measurements_are_localized() {
+        assert_eq!(localize_value_in(Locale::DeDe, "5.4 mmol/L"), "5,4 mmol/L");
+        assert_eq!(
+            localize_value_in(Locale::DeDe, "120/80.5 mmHg"),
+            "120/80,5 mmHg"
+        );
+        assert_eq!(localize_value_in(Locale::DeDe, "E11.9"), "E11.9");
+        assert_eq!(localize_value_in(Locale::DeDe, "30.10.2025"), "30.10.2025");
+        assert_eq!(
+            localize_value_in(Locale::DeDe, "Version 2.1 of the panel"),
+            "Version 2.1 of the panel"
+        );
+        assert_eq!(localize_value_in(Locale::EnUs, "5.4"), "5.4");
+    }
+}
@@ -0,0 +1,144 @@
+//! Opt-in rendering of detail text as a safe Markdown subset.
+//!
+//! Supported: line breaks, blank-line paragraphs, `**bold**` and `-`/`*` bullet
+//! lists. Everything else, including raw HTML, is escaped, so the output only
+//! ever contains the tags emitted here.
+
+use crate::render_context;
+
+/// `true` when details should be rendered through [`to_html`].
+pub(crate) fn enabled() -> bool {
+    render_context::with(|context| context.markdown)
+}
+
+/// Render `text` as HTML using the safe subset described in the module docs.
+pub(crate) fn to_html(text: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list: Vec<&str> = Vec::new();
+
+    for line in text.lines().map(str::trim_end) {
+        let trimmed = line.trim_start();
+        if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            flush_paragraph(&mut html, &mut paragraph);
+            list.push(item);
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+            flush_list(&mut html, &mut list);
+        } else {
+            flush_list(&mut html, &mut list);
+            paragraph.push(trimmed);
+        }
+    }
+    flush_paragraph(&mut html, &mut paragraph);
+    flush_list(&mut html, &mut list);
+    html
+}
+
+fn flush_paragraph(html: &mut String, lines: &mut Vec<&str>) {
+    if lines.is_empty() {
+        return;
+    }
+    let body: Vec<String> = lines.drain(..).map(inline).collect();
+    html.push_str(&format!("<p>{}</p>", body.join("<br>")));
+}
+
+fn flush_list(html: &mut String, items: &mut Vec<&str>) {
+    if items.is_empty() {
+        return;
+    }
+    html.push_str("<ul>");
+    for item in items.drain(..) {
+        html.push_str(&format!("<li>{}</li>", inline(item)));
+    }
+    html.push_str("</ul>");
+}
+
+/// Escape `text` and turn paired `**` markers into `<strong>`; an unpaired
+/// marker is kept as literal text.
+fn inline(text: &str) -> String {
+    let parts: Vec<&str> = text.split("**").collect();
+    let tag_markers = (parts.len() - 1) / 2 * 2;
+    let mut output = String::new();
+    for (index, part) in parts.iter().enumerate() {
+        if index > 0 {
+            if index <= tag_markers {
+                output.push_str(if index % 2 == 1 {
+                    "<strong>"
+                } else {
+                    "</strong>"
+                });
+            } else {
+                output.push_str("**");
+            }
+        }
+        output.push_str(&escape(part));
+    }
+    output
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_context::RenderContext;
+
+    #[test]
+    fn renders_bold_line_breaks_and_lists() {
+        let text = "**Assessment:** septic shock\non pressors\n\nPlan:\n- fluids 30 mL/kg\n* repeat **lactate**";
+        assert_eq!(
+            to_html(text),
+            "<p><strong>Assessment:</strong> septic shock<br>on pressors</p>\
+             <p>Plan:</p>\
+             <ul><li>fluids 30 mL/kg</li><li>repeat <strong>lactate</strong></li></ul>"
+        );
+    }
+
+    #[test]
+    fn raw_html_is_escaped() {
+        assert_eq!(
+            to_html("<script>alert('x')</script> & **<b>bold</b>**"),
+            "<p>&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; \
+             <strong>&lt;b&gt;bold&lt;/b&gt;</strong></p>"
+        );
+    }
+
+    #[test]
+    fn unpaired_bold_marker_stays_literal() {
+        assert_eq!(
+            to_html("MAP **65 then **70** later"),
+            "<p>MAP <strong>65 then </strong>70** later</p>"
+        );
+        assert_eq!(to_html("a ** b"), "<p>a ** b</p>");
+    }
+
+    #[test]
+    fn mode_is_restored_after_render() {
+        {
+            let _context = RenderContext {
+                markdown: true,
+                ..RenderContext::default()
+            }
+            .enter();
+            assert!(enabled());
+        }
+        assert!(!enabled());
+    }
+}
@@ -0,0 +1,178 @@
+//! Overview minimap: the whole timeline as a strip of bars above the grid.
+//!
+//! Bars cover every day from the first to the last event so quiet stretches stay
+//! visible. Long histories merge consecutive days so the strip never exceeds
+//! [`MAX_BARS`]; heights scale to the busiest bar and the color follows the most
+//! severe event in it.
+
+use chrono::{Duration, NaiveDate};
+use timeline_core::analytics::events_per_day_by_severity;
+use timeline_core::{Severity, TimelineSnapshot};
+
+/// Most bars drawn; longer spans put several days in each bar.
+pub(crate) const MAX_BARS: usize = 60;
+
+const SEVERITIES: [Severity; 5] = [
+    Severity::Critical,
+    Severity::High,
+    Severity::Moderate,
+    Severity::Low,
+    Severity::Info,
+];
+
+/// One bar of the strip, covering `start..=end`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MinimapBar {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub count: usize,
+    /// Most severe event in the bar; `None` for an empty bar.
+    pub severity: Option<Severity>,
+    /// Latest day in the bar with events, i.e. the day row a click opens.
+    pub target: Option<NaiveDate>,
+    /// Bar height relative to the busiest bar, from 0.0 to 1.0.
+    pub scale: f64,
+}
+
+impl MinimapBar {
+    /// Accessible label, e.g. "10/30/2025: 3 events".
+    pub(crate) fn label(&self) -> String {
+        let range = if self.start == self.end {
+            self.start.format("%m/%d/%Y").to_string()
+        } else {
+            format!(
+                "{} – {}",
+                self.start.format("%m/%d/%Y"),
+                self.end.format("%m/%d/%Y")
+            )
+        };
+        match self.count {
+            1 => format!("{range}: 1 event"),
+            count => format!("{range}: {count} events"),
+        }
+    }
+}
+
+/// Bars for every timestamped event of `snapshot`, oldest first, with at most
+/// `max_bars` bars. Empty when no event has a timestamp.
+pub(crate) fn minimap_bars(snapshot: &TimelineSnapshot, max_bars: usize) -> Vec<MinimapBar> {
+    let per_day = events_per_day_by_severity(snapshot);
+    let (Some(first), Some(last)) = (per_day.keys().next(), per_day.keys().next_back()) else {
+        return Vec::new();
+    };
+
+    let span_days = (*last - *first).num_days() as usize + 1;
+    let days_per_bar = span_days.div_ceil(max_bars.max(1));
+    let mut bars = Vec::new();
+    let mut start = *first;
+    while start <= *last {
+        let end = (start + Duration::days(days_per_bar as i64 - 1)).min(*last);
+        let mut bar = MinimapBar {
+            start,
+            end,
+            count: 0,
+            severity: None,
+            target: None,
+            scale: 0.0,
+        };
+        for (day, counts) in per_day.range(start..=end) {
+            bar.count += counts.total;
+            bar.target = Some(*day);
+            let present = SEVERITIES
+                .into_iter()
+                .filter(|severity| counts.count(*severity) > 0);
+            bar.severity = Severity::most_severe(bar.severity.into_iter().chain(present));
+        }
+        bars.push(bar);
+        start = end + Duration::days(1);
+    }
+
+    let busiest = bars.iter().map(|bar| bar.count).max().unwrap_or(0);
+    if busiest > 0 {
+        for bar in &mut bars {
+            bar.scale = bar.count as f64 / busiest as f64;
+        }
+    }
+    bars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone, Utc};
+    use timeline_core::{CriticalSummary, EventCategory, TimelineEvent, TimelineSnapshot};
+
+    fn event(id: &str, occurred_at: DateTime<Utc>, severity: Severity) -> TimelineEvent {
+        TimelineEvent {
+            occurred_at: Some(occurred_at),
+            severity,
+            ..TimelineEvent::new(id, EventCategory::Observation, id)
+        }
+    }
+
+    fn on(day: u32, month: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, month, day, 9, 0, 0).unwrap()
+    }
+
+    fn date(day: u32, month: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    fn snapshot(events: Vec<TimelineEvent>) -> TimelineSnapshot {
+        TimelineSnapshot::new(CriticalSummary::default(), events)
+    }
+
+    #[test]
+    fn multi_week_span_merges_days_into_bars() {
+        // 1 Oct – 28 Oct is 28 days; with 10 bars each bar covers 3 days.
+        let snapshot = snapshot(vec![
+            event("a", on(1, 10), Severity::Low),
+            event("b", on(2, 10), Severity::High),
+            event("c", on(2, 10), Severity::Info),
+            event("d", on(15, 10), Severity::Critical),
+            event("e", on(28, 10), Severity::Moderate),
+        ]);
+
+        let bars = minimap_bars(&snapshot, 10);
+        assert_eq!(bars.len(), 10);
+        assert_eq!((bars[0].start, bars[0].end), (date(1, 10), date(3, 10)));
+        assert_eq!(bars[0].count, 3);
+        assert_eq!(bars[0].severity, Some(Severity::High));
+        assert_eq!(bars[0].target, Some(date(2, 10)));
+        assert_eq!(bars[0].scale, 1.0);
+
+        assert_eq!(bars[1].count, 0);
+        assert_eq!(bars[1].severity, None);
+        assert_eq!(bars[1].target, None);
+        assert_eq!(bars[1].scale, 0.0);
+
+        let mid = &bars[4];
+        assert_eq!((mid.start, mid.end), (date(13, 10), date(15, 10)));
+        assert_eq!(mid.severity, Some(Severity::Critical));
+        assert!((mid.scale - 1.0 / 3.0).abs() < 1e-9);
+
+        let last = bars.last().unwrap();
+        assert_eq!((last.start, last.end), (date(28, 10), date(28, 10)));
+        assert_eq!(last.label(), "10/28/2025: 1 event");
+        assert_eq!(bars[0].label(), "10/01/2025 – 10/03/2025: 3 events");
+    }
+
+    #[test]
+    fn short_span_keeps_one_bar_per_day() {
+        let snapshot = snapshot(vec![
+            event("a", on(30, 10), Severity::Info),
+            event("b", on(1, 11), Severity::Info),
+        ]);
+        let bars = minimap_bars(&snapshot, MAX_BARS);
+        let days: Vec<NaiveDate> = bars.iter().map(|bar| bar.start).collect();
+        assert_eq!(days, vec![date(30, 10), date(31, 10), date(1, 11)]);
+        assert!(bars.iter().all(|bar| bar.start == bar.end));
+    }
+
+    #[test]
+    fn untimed_events_produce_no_bars() {
+        let mut untimed = event("a", on(1, 10), Severity::Info);
+        untimed.occurred_at = None;
+        assert!(minimap_bars(&snapshot(vec![untimed]), MAX_BARS).is_empty());
+    }
+}
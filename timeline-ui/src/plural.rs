@@ -0,0 +1,199 @@
+//! Localized count phrases such as "3 studies" for collapsed-day summaries.
+//!
+//! Each locale supplies a [`Pluralization`]: its plural rule (after CLDR,
+//! reduced to the categories the supported languages use) and the nouns for
+//! the grid's bucket columns. Counts are formatted with the locale's digit
+//! grouping.
+
+use crate::locale::{self, format_number_in, Locale};
+
+/// CLDR plural category of a count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PluralCategory {
+    One,
+    Other,
+}
+
+/// Plural rule and bucket nouns of one language.
+pub(crate) trait Pluralization {
+    fn category(&self, count: usize) -> PluralCategory;
+
+    /// Noun for the bucket column `bucket` (e.g. "Imaging") in `category`.
+    fn bucket_noun(&self, bucket: &str, category: PluralCategory) -> &'static str;
+}
+
+struct English;
+struct German;
+struct French;
+struct Vietnamese;
+
+impl Pluralization for English {
+    fn category(&self, count: usize) -> PluralCategory {
+        if count == 1 {
+            PluralCategory::One
+        } else {
+            PluralCategory::Other
+        }
+    }
+
+    fn bucket_noun(&self, bucket: &str, category: PluralCategory) -> &'static str {
+        let (one, other) = match bucket {
+            "Vitals" => ("vital", "vitals"),
+            "Labs" => ("lab", "labs"),
+            "Imaging" => ("study", "studies"),
+            "Observations" => ("observation", "observations"),
+            "Medications" => ("medication", "medications"),
+            "Conditions" => ("condition", "conditions"),
+            "Procedures" => ("procedure", "procedures"),
+            "Encounters" => ("encounter", "encounters"),
+            "Documents" => ("document", "documents"),
+            "Notes" => ("note", "notes"),
+            _ => ("event", "events"),
+        };
+        pick(category, one, other)
+    }
+}
+
+impl Pluralization for German {
+    fn category(&self, count: usize) -> PluralCategory {
+        English.category(count)
+    }
+
+    fn bucket_noun(&self, bucket: &str, category: PluralCategory) -> &'static str {
+        let (one, other) = match bucket {
+            "Vitals" => ("Vitalwert", "Vitalwerte"),
+            "Labs" => ("Laborwert", "Laborwerte"),
+            "Imaging" => ("Bildgebung", "Bildgebungen"),
+            "Observations" => ("Beobachtung", "Beobachtungen"),
+            "Medications" => ("Medikament", "Medikamente"),
+            "Conditions" => ("Erkrankung", "Erkrankungen"),
+            "Procedures" => ("Eingriff", "Eingriffe"),
+            "Encounters" => ("Besuch", "Besuche"),
+            "Documents" => ("Dokument", "Dokumente"),
+            "Notes" => ("Notiz", "Notizen"),
+            _ => ("Ereignis", "Ereignisse"),
+        };
+        pick(category, one, other)
+    }
+}
+
+impl Pluralization for French {
+    /// French treats 0 and 1 as singular.
+    fn category(&self, count: usize) -> PluralCategory {
+        if count <= 1 {
+            PluralCategory::One
+        } else {
+            PluralCategory::Other
+        }
+    }
+
+    fn bucket_noun(&self, bucket: &str, category: PluralCategory) -> &'static str {
+        let (one, other) = match bucket {
+            "Vitals" => ("constante", "constantes"),
+            "Labs" => ("analyse", "analyses"),
+            "Imaging" => ("examen d'imagerie", "examens d'imagerie"),
+            "Observations" => ("observation", "observations"),
+            "Medications" => ("médicament", "médicaments"),
+            "Conditions" => ("pathologie", "pathologies"),
+            "Procedures" => ("acte", "actes"),
+            "Encounters" => ("consultation", "consultations"),
+            "Documents" => ("document", "documents"),
+            "Notes" => ("note", "notes"),
+            _ => ("événement", "événements"),
+        };
+        pick(category, one, other)
+    }
+}
+
+impl Pluralization for Vietnamese {
+    /// Vietnamese nouns do not inflect for number.
+    fn category(&self, _count: usize) -> PluralCategory {
+        PluralCategory::Other
+    }
+
+    fn bucket_noun(&self, bucket: &str, _category: PluralCategory) -> &'static str {
+        match bucket {
+            "Vitals" => "chỉ số sinh tồn",
+            "Labs" => "xét nghiệm",
+            "Imaging" => "chẩn đoán hình ảnh",
+            "Observations" => "kết quả quan sát",
+            "Medications" => "thuốc",
+            "Conditions" => "bệnh lý",
+            "Procedures" => "thủ thuật",
+            "Encounters" => "lượt khám",
+            "Documents" => "tài liệu",
+            "Notes" => "ghi chú",
+            _ => "sự kiện",
+        }
+    }
+}
+
+fn pick(category: PluralCategory, one: &'static str, other: &'static str) -> &'static str {
+    match category {
+        PluralCategory::One => one,
+        PluralCategory::Other => other,
+    }
+}
+
+/// Pluralization rules of `locale`.
+pub(crate) fn rules(locale: Locale) -> &'static dyn Pluralization {
+    match locale {
+        Locale::EnUs => &English,
+        Locale::DeDe => &German,
+        Locale::FrFr => &French,
+        Locale::ViVn => &Vietnamese,
+    }
+}
+
+/// "3 studies" for `count` events in `bucket`, in the active locale.
+pub(crate) fn bucket_phrase(bucket: &str, count: usize) -> String {
+    bucket_phrase_in(locale::active(), bucket, count)
+}
+
+pub(crate) fn bucket_phrase_in(locale: Locale, bucket: &str, count: usize) -> String {
+    let rules = rules(locale);
+    let noun = rules.bucket_noun(bucket, rules.category(count));
+    format!("{} {noun}", format_number_in(locale, count as f64, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_context::RenderContext;
+
+    #[test]
+    fn english_keeps_irregular_plurals() {
+        assert_eq!(bucket_phrase_in(Locale::EnUs, "Imaging", 1), "1 study");
+        assert_eq!(bucket_phrase_in(Locale::EnUs, "Imaging", 3), "3 studies");
+        assert_eq!(bucket_phrase_in(Locale::EnUs, "Unknown", 2), "2 events");
+    }
+
+    #[test]
+    fn vietnamese_has_a_single_form() {
+        assert_eq!(
+            bucket_phrase_in(Locale::ViVn, "Imaging", 3),
+            "3 chẩn đoán hình ảnh"
+        );
+        assert_eq!(bucket_phrase_in(Locale::ViVn, "Labs", 1), "1 xét nghiệm");
+    }
+
+    #[test]
+    fn french_zero_is_singular_and_counts_are_grouped() {
+        assert_eq!(bucket_phrase_in(Locale::FrFr, "Notes", 0), "0 note");
+        assert_eq!(
+            bucket_phrase_in(Locale::FrFr, "Vitals", 1200),
+            "1\u{202F}200 constantes"
+        );
+        assert_eq!(bucket_phrase_in(Locale::DeDe, "Labs", 3), "3 Laborwerte");
+    }
+
+    #[test]
+    fn active_locale_selects_the_rules() {
+        let _context = RenderContext {
+            locale: Locale::DeDe,
+            ..RenderContext::default()
+        }
+        .enter();
+        assert_eq!(bucket_phrase("Imaging", 2), "2 Bildgebungen");
+    }
+}
@@ -0,0 +1,88 @@
+//! PNG export of a vital trend, drawn on an offscreen canvas so it can be
+//! pasted into notes. Uses the same [`ChartScale`] as the SVG charts.
+
+use timeline_core::{Severity, VitalTrend};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+use crate::chart::{self, ChartScale, PlotArea};
+
+const PADDING: f64 = 24.0;
+
+/// Draw `trend_json` (a serialized `VitalTrend`) as a `width`×`height` line
+/// chart and return the PNG bytes. Fails when the trend has no timestamped
+/// numeric points.
+#[wasm_bindgen]
+pub fn render_trend_png(trend_json: &str, width: u32, height: u32) -> Result<Vec<u8>, JsValue> {
+    let trend: VitalTrend = serde_json::from_str(trend_json)
+        .map_err(|err| JsValue::from_str(&format!("Invalid trend: {err}")))?;
+
+    let points = chart::timed_points(&trend);
+    let (Some(&(start, _)), Some(&(end, latest))) = (points.first(), points.last()) else {
+        return Err(JsValue::from_str("Trend has no numeric data to draw"));
+    };
+
+    let area = PlotArea {
+        left: PADDING,
+        top: PADDING,
+        width: (f64::from(width) - 2.0 * PADDING).max(1.0),
+        height: (f64::from(height) - 2.0 * PADDING).max(1.0),
+    };
+    let scale = ChartScale::new(points.iter().map(|(_, value)| *value), start, end, area)
+        .ok_or_else(|| JsValue::from_str("Trend has no numeric data to draw"))?;
+
+    let canvas: HtmlCanvasElement = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("document is not accessible"))?
+        .create_element("canvas")?
+        .dyn_into()?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("2d canvas context is not available"))?
+        .dyn_into()?;
+
+    context.set_fill_style_str("#ffffff");
+    context.fill_rect(0.0, 0.0, f64::from(width), f64::from(height));
+
+    context.set_stroke_style_str("#d0d5dd");
+    context.set_line_width(1.0);
+    context.begin_path();
+    context.move_to(area.left, area.top);
+    context.line_to(area.left, area.top + area.height);
+    context.line_to(area.left + area.width, area.top + area.height);
+    context.stroke();
+
+    let line_severity = chart::point_severity(&trend.name, latest, Severity::Info);
+    context.set_stroke_style_str(line_severity.color_hex());
+    context.set_line_width(2.0);
+    context.begin_path();
+    for (index, (at, value)) in points.iter().enumerate() {
+        let (x, y) = (scale.x(*at), scale.y(*value));
+        if index == 0 {
+            context.move_to(x, y);
+        } else {
+            context.line_to(x, y);
+        }
+    }
+    context.stroke();
+
+    for (at, value) in &points {
+        let severity = chart::point_severity(&trend.name, *value, Severity::Info);
+        context.set_fill_style_str(severity.color_hex());
+        context.begin_path();
+        context.arc(
+            scale.x(*at),
+            scale.y(*value),
+            3.0,
+            0.0,
+            std::f64::consts::TAU,
+        )?;
+        context.fill();
+    }
+
+    let url = canvas.to_data_url_with_type("image/png")?;
+    chart::data_url_bytes(&url).ok_or_else(|| JsValue::from_str("Canvas returned invalid PNG data"))
+}
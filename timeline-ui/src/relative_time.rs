@@ -0,0 +1,177 @@
+//! Human-readable relative timestamps ("3 hours ago", "in 2 weeks").
+//!
+//! The largest unit used is capped by the render pass's [`TimeGranularity`].
+//! Long-lived views re-render on a [`REFRESH_INTERVAL_MS`] timer so labels do
+//! not go stale.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::render_context;
+
+/// Largest unit a relative timestamp may be expressed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeGranularity {
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    #[default]
+    Year,
+}
+
+/// Period of the view's relative-label refresh timer.
+pub(crate) const REFRESH_INTERVAL_MS: i32 = 60_000;
+
+/// `true` once `now` falls in a later wall-clock minute than `rendered_at`,
+/// i.e. when a relative label may read differently.
+pub(crate) fn should_refresh(rendered_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    now.timestamp().div_euclid(60) > rendered_at.timestamp().div_euclid(60)
+}
+
+/// Last time relative labels were rendered, advanced by each refresh tick.
+pub(crate) struct RefreshClock {
+    rendered_at: DateTime<Utc>,
+}
+
+impl RefreshClock {
+    pub(crate) fn new(rendered_at: DateTime<Utc>) -> Self {
+        Self { rendered_at }
+    }
+
+    /// `true` (and records `now`) when the view should re-render at `now`.
+    pub(crate) fn tick(&mut self, now: DateTime<Utc>) -> bool {
+        let refresh = should_refresh(self.rendered_at, now);
+        if refresh {
+            self.rendered_at = now;
+        }
+        refresh
+    }
+}
+
+/// Format `timestamp` relative to the current time using the active granularity.
+pub(crate) fn format_relative_time(timestamp: Option<DateTime<Utc>>) -> Option<String> {
+    let max = render_context::with(|context| context.max_granularity);
+    Some(format_relative_to(timestamp?, Utc::now(), max))
+}
+
+/// Format `timestamp` relative to `now`, never using a unit larger than `max`.
+pub(crate) fn format_relative_to(
+    timestamp: DateTime<Utc>,
+    now: DateTime<Utc>,
+    max: TimeGranularity,
+) -> String {
+    let delta = now.signed_duration_since(timestamp);
+    let is_future = delta.num_seconds() < 0;
+    let abs_delta = delta.abs();
+
+    let minutes = abs_delta.num_minutes();
+    if minutes < 1 {
+        return if is_future { "in moments" } else { "just now" }.to_string();
+    }
+
+    let hours = abs_delta.num_hours();
+    let days = abs_delta.num_days();
+    let (value, unit) = if days >= 365 && max >= TimeGranularity::Year {
+        (days / 365, "year")
+    } else if days >= 60 && max >= TimeGranularity::Month {
+        (days / 30, "month")
+    } else if days >= 7 && max >= TimeGranularity::Week {
+        (days / 7, "week")
+    } else if days >= 1 && max >= TimeGranularity::Day {
+        (days, "day")
+    } else if hours >= 1 && max >= TimeGranularity::Hour {
+        (hours, "hour")
+    } else {
+        (minutes, "minute")
+    };
+
+    let plural = if value == 1 { "" } else { "s" };
+    if is_future {
+        format!("in {value} {unit}{plural}")
+    } else {
+        format!("{value} {unit}{plural} ago")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 10, 30, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn long_spans_use_week_and_year_tiers() {
+        let now = now();
+        assert_eq!(
+            format_relative_to(now - Duration::days(45), now, TimeGranularity::Year),
+            "6 weeks ago"
+        );
+        assert_eq!(
+            format_relative_to(now - Duration::days(400), now, TimeGranularity::Year),
+            "1 year ago"
+        );
+        assert_eq!(
+            format_relative_to(now - Duration::days(90), now, TimeGranularity::Year),
+            "3 months ago"
+        );
+        assert_eq!(
+            format_relative_to(now + Duration::days(14), now, TimeGranularity::Year),
+            "in 2 weeks"
+        );
+    }
+
+    #[test]
+    fn granularity_caps_the_largest_unit() {
+        let now = now();
+        assert_eq!(
+            format_relative_to(now - Duration::days(45), now, TimeGranularity::Day),
+            "45 days ago"
+        );
+        assert_eq!(
+            format_relative_to(now - Duration::hours(30), now, TimeGranularity::Hour),
+            "30 hours ago"
+        );
+    }
+
+    #[test]
+    fn refresh_waits_for_the_next_wall_clock_minute() {
+        let rendered_at = now() + Duration::seconds(10);
+        assert!(!should_refresh(
+            rendered_at,
+            rendered_at + Duration::seconds(45)
+        ));
+        assert!(should_refresh(
+            rendered_at,
+            rendered_at + Duration::seconds(50)
+        ));
+        // A clock stepped backwards never forces a refresh.
+        assert!(!should_refresh(
+            rendered_at,
+            rendered_at - Duration::minutes(5)
+        ));
+
+        let mut clock = RefreshClock::new(rendered_at);
+        assert!(clock.tick(rendered_at + Duration::seconds(60)));
+        assert!(!clock.tick(rendered_at + Duration::seconds(65)));
+        assert!(clock.tick(rendered_at + Duration::seconds(120)));
+    }
+
+    #[test]
+    fn sub_minute_spans_stay_coarse() {
+        let now = now();
+        assert_eq!(
+            format_relative_to(now - Duration::seconds(20), now, TimeGranularity::Year),
+            "just now"
+        );
+        assert_eq!(
+            format_relative_to(now + Duration::seconds(20), now, TimeGranularity::Year),
+            "in moments"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! View options read by the rendering helpers.
+//!
+//! The view installs one [`RenderContext`] for the duration of a render pass
+//! (rendering is synchronous and single-threaded in wasm), so helpers such as
+//! [`crate::scope::class`] or [`crate::locale::format_number`] can read the
+//! active options without threading them through every call.
+
+use std::cell::RefCell;
+
+use crate::density::Density;
+use crate::locale::Locale;
+use crate::relative_time::TimeGranularity;
+
+/// Options of the render pass in progress.
+#[derive(Default)]
+pub(crate) struct RenderContext {
+    /// Class-name prefix, e.g. `acme-` (see [`crate::scope::prefix_for`]).
+    pub class_prefix: Option<String>,
+    /// Largest unit of relative timestamps.
+    pub max_granularity: TimeGranularity,
+    pub density: Density,
+    pub locale: Locale,
+    /// Render details as the safe Markdown subset of [`crate::markdown`].
+    pub markdown: bool,
+    /// Integrator's severity badge renderer.
+    #[cfg(target_arch = "wasm32")]
+    pub badge_renderer: Option<yew::Callback<timeline_core::Severity, yew::Html>>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<RenderContext> = RefCell::new(RenderContext::default());
+}
+
+/// Restores the previous context when dropped.
+pub(crate) struct RenderGuard {
+    previous: RenderContext,
+}
+
+impl Drop for RenderGuard {
+    fn drop(&mut self) {
+        let previous = std::mem::take(&mut self.previous);
+        CONTEXT.with(|cell| *cell.borrow_mut() = previous);
+    }
+}
+
+impl RenderContext {
+    /// Install `self` until the returned guard is dropped.
+    pub(crate) fn enter(self) -> RenderGuard {
+        let previous = CONTEXT.with(|cell| cell.replace(self));
+        RenderGuard { previous }
+    }
+}
+
+/// Read the context installed for the current render pass.
+pub(crate) fn with<R>(read: impl FnOnce(&RenderContext) -> R) -> R {
+    CONTEXT.with(|cell| read(&cell.borrow()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_restores_the_previous_context() {
+        {
+            let _context = RenderContext {
+                locale: Locale::DeDe,
+                markdown: true,
+                ..RenderContext::default()
+            }
+            .enter();
+            assert_eq!(with(|context| context.locale), Locale::DeDe);
+            {
+                let _inner = RenderContext::default().enter();
+                assert!(!with(|context| context.markdown));
+            }
+            assert!(with(|context| context.markdown));
+        }
+        assert_eq!(with(|context| context.locale), Locale::EnUs);
+        assert_eq!(with(|context| context.density), Density::Comfortable);
+    }
+}
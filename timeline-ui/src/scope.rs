@@ -0,0 +1,79 @@
+//! Optional class-name prefix isolating the component from host-page CSS.
+//!
+//! The prefix of the current render pass lives in the
+//! [`RenderContext`](crate::render_context::RenderContext).
+
+use crate::render_context;
+
+/// `scope` without surrounding whitespace and trailing hyphens, if it is a
+/// plain CSS identifier (a letter or `_`, then letters, digits, `-` or `_`).
+/// Anything else is rejected rather than escaped, so a scope can never break
+/// out of the selectors and attribute queries it is written into.
+pub(crate) fn scope_name(scope: &str) -> Option<&str> {
+    let trimmed = scope.trim().trim_end_matches('-');
+    let mut chars = trimmed.chars();
+    let valid_start = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_');
+    let valid_rest = chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    (valid_start && valid_rest).then_some(trimmed)
+}
+
+/// Prefix applied for a scope name, e.g. `acme` becomes `acme-`.
+pub(crate) fn prefix_for(scope: &str) -> Option<String> {
+    scope_name(scope).map(|name| format!("{name}-"))
+}
+
+/// Apply the active prefix to each space-separated class name.
+pub(crate) fn class(names: &str) -> String {
+    render_context::with(|context| match context.class_prefix.as_deref() {
+        Some(prefix) => names
+            .split_whitespace()
+            .map(|name| format!("{prefix}{name}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+        None => names.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_context::RenderContext;
+
+    fn enter(scope: &str) -> render_context::RenderGuard {
+        RenderContext {
+            class_prefix: prefix_for(scope),
+            ..RenderContext::default()
+        }
+        .enter()
+    }
+
+    #[test]
+    fn class_names_carry_prefix_inside_scope() {
+        assert_eq!(class("timeline-root"), "timeline-root");
+        {
+            let _guard = enter("acme");
+            assert_eq!(
+                class("timeline-event is-critical"),
+                "acme-timeline-event acme-is-critical"
+            );
+        }
+        assert_eq!(class("timeline-root"), "timeline-root");
+    }
+
+    #[test]
+    fn blank_scope_is_ignored() {
+        let _guard = enter("  ");
+        assert_eq!(class("critical-card"), "critical-card");
+    }
+
+    #[test]
+    fn scope_must_be_a_css_identifier() {
+        assert_eq!(prefix_for(" acme- ").as_deref(), Some("acme-"));
+        assert_eq!(prefix_for("_my-app2").as_deref(), Some("_my-app2-"));
+        assert_eq!(prefix_for("2col"), None);
+        assert_eq!(prefix_for("acme\"] body {"), None);
+        assert_eq!(prefix_for("a.b"), None);
+    }
+}
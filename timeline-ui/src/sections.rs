@@ -0,0 +1,91 @@
+//! Order of the cards in the critical (left) panel.
+
+use serde::Deserialize;
+
+/// A card of the critical panel, named in view options by its snake_case key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CriticalSection {
+    CodeStatus,
+    TrendInsights,
+    Vitals,
+    Diagnostics,
+    VitalTrends,
+    Alerts,
+    Allergies,
+    Medications,
+    /// Only rendered when the snapshot lists discontinued medications.
+    DiscontinuedMedications,
+    Conditions,
+}
+
+/// Code status first so it is never scrolled out of view in an emergency.
+pub(crate) const DEFAULT_SECTION_ORDER: [CriticalSection; 10] = [
+    CriticalSection::CodeStatus,
+    CriticalSection::TrendInsights,
+    CriticalSection::Vitals,
+    CriticalSection::Diagnostics,
+    CriticalSection::VitalTrends,
+    CriticalSection::Alerts,
+    CriticalSection::Allergies,
+    CriticalSection::Medications,
+    CriticalSection::DiscontinuedMedications,
+    CriticalSection::Conditions,
+];
+
+/// Sections in render order: the configured ones first (duplicates dropped),
+/// then any section left out in default order, so a partial list never hides
+/// a card.
+pub(crate) fn section_order(configured: Option<&[CriticalSection]>) -> Vec<CriticalSection> {
+    let mut order: Vec<CriticalSection> = Vec::with_capacity(DEFAULT_SECTION_ORDER.len());
+    for section in configured
+        .unwrap_or_default()
+        .iter()
+        .chain(DEFAULT_SECTION_ORDER.iter())
+    {
+        if !order.contains(section) {
+            order.push(*section);
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(order: &[CriticalSection], section: CriticalSection) -> usize {
+        order
+            .iter()
+            .position(|candidate| *candidate == section)
+            .expect("section missing")
+    }
+
+    #[test]
+    fn code_status_renders_before_vitals_by_default() {
+        let order = section_order(None);
+        assert_eq!(order[0], CriticalSection::CodeStatus);
+        assert!(
+            position(&order, CriticalSection::CodeStatus)
+                < position(&order, CriticalSection::Vitals)
+        );
+    }
+
+    #[test]
+    fn configured_sections_lead_and_missing_ones_follow() {
+        let order = section_order(Some(&[
+            CriticalSection::Allergies,
+            CriticalSection::CodeStatus,
+            CriticalSection::Allergies,
+        ]));
+        assert_eq!(order.len(), DEFAULT_SECTION_ORDER.len());
+        assert_eq!(
+            order[..3],
+            [
+                CriticalSection::Allergies,
+                CriticalSection::CodeStatus,
+                CriticalSection::TrendInsights,
+            ]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Keyboard shortcuts for the severity filter chips.
+//!
+//! Keys map to the same filter levels as the chips and toggle the same way, so
+//! pressing a key twice clears the filter. Keypresses are ignored while the user
+//! types in a text field or holds a modifier.
+
+use serde::Deserialize;
+use timeline_core::Severity;
+
+/// Severity filter chips in display order; `None` shows every severity.
+pub(crate) const SEVERITY_FILTERS: [(Option<Severity>, &str); 4] = [
+    (None, "All"),
+    (Some(Severity::Critical), "Critical only"),
+    (Some(Severity::High), "High and above"),
+    (Some(Severity::Moderate), "Moderate and above"),
+];
+
+/// Key bound to each severity filter (matched case-insensitively).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SeverityShortcuts {
+    pub all: char,
+    pub critical: char,
+    pub high: char,
+    pub moderate: char,
+}
+
+impl Default for SeverityShortcuts {
+    fn default() -> Self {
+        Self {
+            all: 'a',
+            critical: 'c',
+            high: 'h',
+            moderate: 'm',
+        }
+    }
+}
+
+/// A `keydown` as seen by the filter shortcuts.
+pub(crate) struct KeyPress<'a> {
+    /// `KeyboardEvent.key`.
+    pub key: &'a str,
+    /// Ctrl, Alt or Meta is held.
+    pub modified: bool,
+    /// Focus is in a text field, so the key is input rather than a command.
+    pub typing: bool,
+}
+
+/// Filter level selected by `press`: `Some(None)` for "All", `None` when the
+/// key is not a shortcut or must be ignored.
+pub(crate) fn filter_for_key(
+    shortcuts: &SeverityShortcuts,
+    press: &KeyPress,
+) -> Option<Option<Severity>> {
+    if press.typing || press.modified {
+        return None;
+    }
+    let mut chars = press.key.chars();
+    let (Some(key), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+    let matches = |bound: char| bound.eq_ignore_ascii_case(&key);
+
+    if matches(shortcuts.all) {
+        Some(None)
+    } else if matches(shortcuts.critical) {
+        Some(Some(Severity::Critical))
+    } else if matches(shortcuts.high) {
+        Some(Some(Severity::High))
+    } else if matches(shortcuts.moderate) {
+        Some(Some(Severity::Moderate))
+    } else {
+        None
+    }
+}
+
+/// `true` for elements that take text input (`tag_name` as reported by the DOM).
+pub(crate) fn is_text_entry(tag_name: &str, content_editable: bool) -> bool {
+    content_editable
+        || ["INPUT", "TEXTAREA", "SELECT"]
+            .iter()
+            .any(|tag| tag.eq_ignore_ascii_case(tag_name))
+}
+
+/// Next filter after choosing `level`; choosing the active level clears it.
+pub(crate) fn toggle(current: Option<Severity>, level: Option<Severity>) -> Option<Severity> {
+    if current == level {
+        None
+    } else {
+        level
+    }
+}
+
+/// Text announced through the `aria-live` region after a filter change.
+pub(crate) fn announcement(level: Option<Severity>) -> String {
+    let label = SEVERITY_FILTERS
+        .iter()
+        .find(|(filter, _)| *filter == level)
+        .map_or("All", |(_, label)| *label);
+    format!("Severity filter: {label}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(key: &str) -> KeyPress<'_> {
+        KeyPress {
+            key,
+            modified: false,
+            typing: false,
+        }
+    }
+
+    #[test]
+    fn default_keys_map_to_each_filter() {
+        let shortcuts = SeverityShortcuts::default();
+        assert_eq!(filter_for_key(&shortcuts, &press("a")), Some(None));
+        assert_eq!(
+            filter_for_key(&shortcuts, &press("C")),
+            Some(Some(Severity::Critical))
+        );
+        assert_eq!(
+            filter_for_key(&shortcuts, &press("h")),
+            Some(Some(Severity::High))
+        );
+        assert_eq!(
+            filter_for_key(&shortcuts, &press("M")),
+            Some(Some(Severity::Moderate))
+        );
+        assert_eq!(filter_for_key(&shortcuts, &press("x")), None);
+        assert_eq!(filter_for_key(&shortcuts, &press("Enter")), None);
+    }
+
+    #[test]
+    fn keys_are_ignored_while_typing_or_with_modifiers() {
+        let shortcuts = SeverityShortcuts::default();
+        let typing = KeyPress {
+            typing: true,
+            ..press("c")
+        };
+        let modified = KeyPress {
+            modified: true,
+            ..press("c")
+        };
+        assert_eq!(filter_for_key(&shortcuts, &typing), None);
+        assert_eq!(filter_for_key(&shortcuts, &modified), None);
+
+        assert!(is_text_entry("INPUT", false));
+        assert!(is_text_entry("textarea", false));
+        assert!(is_text_entry("DIV", true));
+        assert!(!is_text_entry("BUTTON", false));
+    }
+
+    #[test]
+    fn configured_keys_replace_defaults() {
+        let shortcuts = SeverityShortcuts {
+            critical: '1',
+            ..SeverityShortcuts::default()
+        };
+        assert_eq!(
+            filter_for_key(&shortcuts, &press("1")),
+            Some(Some(Severity::Critical))
+        );
+        assert_eq!(filter_for_key(&shortcuts, &press("c")), None);
+    }
+
+    #[test]
+    fn repeated_key_clears_filter_and_is_announced() {
+        let level = Some(Severity::High);
+        let next = toggle(None, level);
+        assert_eq!(next, level);
+        assert_eq!(announcement(next), "Severity filter: High and above");
+        assert_eq!(toggle(next, level), None);
+        assert_eq!(announcement(None), "Severity filter: All");
+    }
+}
@@ -0,0 +1,130 @@
+//! Snapshot decoding for the mount entry points.
+//!
+//! Both the `JsValue` and the JSON string path go through the same decoder, so
+//! a bad snapshot is reported with the path of the failing field (e.g.
+//! `events[42].occurred_at`) instead of an opaque bindgen error.
+
+use serde::Deserializer;
+use timeline_core::TimelineSnapshot;
+
+/// Decode a snapshot, reporting the failing field and its likely cause.
+pub(crate) fn decode_snapshot<'de, D>(deserializer: D) -> Result<TimelineSnapshot, String>
+where
+    D: Deserializer<'de>,
+{
+    serde_path_to_error::deserialize(deserializer)
+        .map_err(|err| describe_error(&err.path().to_string(), &err.inner().to_string()))
+}
+
+/// Decode a snapshot serialized as JSON text.
+#[cfg(any(target_arch = "wasm32", test))]
+pub(crate) fn snapshot_from_json(json: &str) -> Result<TimelineSnapshot, String> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let snapshot = decode_snapshot(&mut deserializer)?;
+    deserializer
+        .end()
+        .map_err(|err| format!("Invalid snapshot JSON: {err}"))?;
+    Ok(snapshot)
+}
+
+fn describe_error(path: &str, message: &str) -> String {
+    let hint = if message.contains("recursion limit") {
+        " (payload is nested too deeply)"
+    } else if is_date_error(path, message) {
+        " (expected an RFC 3339 timestamp such as 2025-10-30T12:00:00Z)"
+    } else {
+        ""
+    };
+    if path.is_empty() || path == "." {
+        format!("Invalid snapshot: {message}{hint}")
+    } else {
+        format!("Invalid snapshot at `{path}`: {message}{hint}")
+    }
+}
+
+/// chrono's parse errors, or any failure on a timestamp field.
+fn is_date_error(path: &str, message: &str) -> bool {
+    let field = path.rsplit('.').next().unwrap_or(path);
+    field.ends_with("_at")
+        || field == "period_end"
+        || [
+            "input contains invalid characters",
+            "premature end of input",
+            "trailing input",
+            "input is out of range",
+        ]
+        .iter()
+        .any(|fragment| message.contains(fragment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use timeline_core::{
+        CriticalSummary, EventCategory, Severity, TimelineEvent, VitalTrend, VitalTrendPoint,
+    };
+
+    /// `count` events and trend points; thousands of each is the kind of payload
+    /// that strains the `JsValue` conversion.
+    fn snapshot_with(count: i64) -> TimelineSnapshot {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let events = (0..count)
+            .map(|index| TimelineEvent {
+                detail: Some(format!("{} /min", 60 + index % 80)),
+                occurred_at: Some(start + Duration::minutes(index)),
+                severity: Severity::Moderate,
+                tags: vec!["relevant-to-sepsis".to_string()],
+                ..TimelineEvent::new(
+                    format!("obs-{index}"),
+                    EventCategory::Observation,
+                    "Heart rate",
+                )
+            })
+            .collect();
+        let critical = CriticalSummary {
+            vital_trends: vec![VitalTrend {
+                name: "Heart rate".to_string(),
+                unit: Some("/min".to_string()),
+                points: (0..count)
+                    .map(|index| VitalTrendPoint {
+                        recorded_at: Some(start + Duration::minutes(index)),
+                        value: Some(60.0 + (index % 80) as f64),
+                        label: None,
+                    })
+                    .collect(),
+            }],
+            ..CriticalSummary::default()
+        };
+        TimelineSnapshot::new(critical, events)
+    }
+
+    #[test]
+    fn json_path_parses_large_snapshot() {
+        let snapshot = snapshot_with(10_000);
+        let json = serde_json::to_string(&snapshot).unwrap();
+
+        let parsed = snapshot_from_json(&json).expect("large snapshot should parse");
+        assert_eq!(parsed, snapshot);
+    }
+
+    #[test]
+    fn bad_timestamp_names_the_field() {
+        let mut value = serde_json::to_value(snapshot_with(100)).unwrap();
+        value["events"][42]["occurred_at"] = "30/10/2025 12:00".into();
+
+        let err = snapshot_from_json(&value.to_string()).unwrap_err();
+        assert!(err.contains("`events[42].occurred_at`"), "{err}");
+        assert!(err.contains("RFC 3339"), "{err}");
+    }
+
+    #[test]
+    fn trailing_text_is_rejected() {
+        let json = serde_json::to_string(&TimelineSnapshot::new(
+            CriticalSummary::default(),
+            Vec::new(),
+        ))
+        .unwrap();
+        assert!(snapshot_from_json(&format!("{json} x")).is_err());
+    }
+}
@@ -1,8 +1,9 @@
-#![cfg(target_arch = "wasm32")]
-
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::{JsCast, JsValue};
+#[cfg(target_arch = "wasm32")]
 use web_sys::{Document, Node};
 
+#[cfg(target_arch = "wasm32")]
 const STYLE_TAG_SELECTOR: &str = "style[data-timeline-ui]";
 
 /// Default CSS for the component along with easy-to-override design tokens.
@@ -27,6 +28,15 @@ pub const DEFAULT_STYLES: &str = r#"
   --timeline-group-accent: rgba(71, 84, 103, 0.18);
   --timeline-trend-border: rgba(148, 163, 184, 0.38);
   --timeline-trend-path: #2563eb;
+  --timeline-chart-line: rgba(71, 84, 103, 0.55);
+  --timeline-series-systolic: #c2410c;
+  --timeline-series-diastolic: #7c3aed;
+  --timeline-series-1: #2563eb;
+  --timeline-series-2: #0f766e;
+  --timeline-series-3: #a16207;
+  --timeline-series-4: #be185d;
+  --timeline-series-5: #4d7c0f;
+  --timeline-series-6: #0e7490;
   --timeline-severity-critical: #b42318;
   --timeline-severity-critical-bg: rgba(180, 35, 24, 0.1);
   --timeline-severity-high: #dc6803;
@@ -117,6 +127,16 @@ pub const DEFAULT_STYLES: &str = r#"
   color: var(--timeline-muted-strong);
 }
 
+.stat-label::before {
+  content: "";
+  display: inline-block;
+  width: 8px;
+  height: 8px;
+  margin-right: 6px;
+  border-radius: 50%;
+  background: var(--timeline-series-color, var(--timeline-chart-line));
+}
+
 .stat-value {
   font-size: 1.05rem;
   font-weight: 600;
@@ -198,36 +218,36 @@ pub const DEFAULT_STYLES: &str = r#"
 
 .timeline-chart-line {
   fill: none;
-  stroke-width: 2.4;
+  stroke: var(--timeline-series-color, var(--timeline-chart-line));
   stroke-linecap: round;
   stroke-linejoin: round;
 }
 
-.timeline-chart-line.is-critical,
+.timeline-chart-area {
+  fill: var(--timeline-severity-critical-bg);
+  stroke: none;
+}
+
 .timeline-chart-point.is-critical {
   stroke: var(--timeline-severity-critical);
   fill: var(--timeline-severity-critical);
 }
 
-.timeline-chart-line.is-high,
 .timeline-chart-point.is-high {
   stroke: var(--timeline-severity-high);
   fill: var(--timeline-severity-high);
 }
 
-.timeline-chart-line.is-moderate,
 .timeline-chart-point.is-moderate {
   stroke: var(--timeline-severity-moderate);
   fill: var(--timeline-severity-moderate);
 }
 
-.timeline-chart-line.is-low,
 .timeline-chart-point.is-low {
   stroke: var(--timeline-severity-low);
   fill: var(--timeline-severity-low);
 }
 
-.timeline-chart-line.is-info,
 .timeline-chart-point.is-info {
   stroke: rgba(71, 84, 103, 0.6);
   fill: rgba(71, 84, 103, 0.6);
@@ -302,6 +322,37 @@ pub const DEFAULT_STYLES: &str = r#"
   outline: none;
 }
 
+.patient-banner {
+  background: var(--timeline-card-bg);
+  border: 1px solid var(--timeline-card-border);
+  border-radius: calc(var(--timeline-radius) - 6px);
+  padding: 14px 20px;
+  display: flex;
+  flex-direction: column;
+  gap: 6px;
+}
+
+.patient-banner[data-deceased="true"] {
+  border-color: var(--timeline-muted);
+  opacity: 0.85;
+}
+
+.patient-name {
+  font-size: 1.1rem;
+  font-weight: 700;
+}
+
+.patient-facts {
+  list-style: none;
+  margin: 0;
+  padding: 0;
+  display: flex;
+  flex-wrap: wrap;
+  gap: 4px 12px;
+  color: var(--timeline-muted);
+  font-size: 0.9rem;
+}
+
 .critical-header {
   background: linear-gradient(120deg, rgba(248, 204, 84, 0.22), rgba(255, 247, 236, 0.55));
   border: 1px solid rgba(247, 192, 108, 0.5);
@@ -398,6 +449,20 @@ pub const DEFAULT_STYLES: &str = r#"
   line-height: 1.45;
 }
 
+.critical-group {
+  display: flex;
+  flex-direction: column;
+  gap: 8px;
+}
+
+.critical-group-title {
+  margin: 0;
+  font-size: 0.75rem;
+  letter-spacing: 0.08em;
+  text-transform: uppercase;
+  color: var(--timeline-muted);
+}
+
 .critical-empty {
   color: var(--timeline-muted);
   font-size: 0.9rem;
@@ -439,6 +504,17 @@ pub const DEFAULT_STYLES: &str = r#"
   color: var(--timeline-pill-affirm-text);
 }
 
+.code-status[data-missing="true"] {
+  border: 2px solid var(--timeline-pill-warning-text);
+  background: var(--timeline-pill-warning-bg);
+}
+
+.code-status[data-missing="true"] .code-status-value {
+  font-size: 1.25rem;
+  text-transform: uppercase;
+  letter-spacing: 0.04em;
+}
+
 .vital-list {
   display: flex;
   flex-direction: column;
@@ -682,6 +758,11 @@ pub const DEFAULT_STYLES: &str = r#"
   gap: 6px;
 }
 
+.hot-item[data-pinned="true"] {
+  border-style: dashed;
+  border-color: var(--timeline-card-border);
+}
+
 .hot-header {
   display: flex;
   align-items: baseline;
@@ -744,6 +825,18 @@ pub const DEFAULT_STYLES: &str = r#"
   color: var(--timeline-muted);
 }
 
+.visually-hidden {
+  position: absolute;
+  width: 1px;
+  height: 1px;
+  margin: -1px;
+  padding: 0;
+  overflow: hidden;
+  clip: rect(0 0 0 0);
+  white-space: nowrap;
+  border: 0;
+}
+
 .timeline-toolbar {
   background: var(--timeline-surface);
   border: 1px solid rgba(148, 163, 184, 0.3);
@@ -923,11 +1016,44 @@ pub const DEFAULT_STYLES: &str = r#"
   background: rgba(59, 130, 246, 0.2);
 }
 
+.timeline-system-groups {
+  display: flex;
+  flex-direction: column;
+  gap: 16px;
+}
+
 .timeline-events {
   margin: 0;
   padding: 0;
 }
 
+.timeline-stream {
+  list-style: none;
+  margin: 0;
+  padding: 0;
+  display: flex;
+  flex-direction: column;
+  gap: 20px;
+}
+
+.timeline-stream-day-label {
+  margin: 0 0 10px;
+  font-size: 0.85rem;
+  font-weight: 600;
+  color: var(--timeline-muted);
+  text-transform: uppercase;
+  letter-spacing: 0.04em;
+}
+
+.timeline-stream-events {
+  list-style: none;
+  margin: 0;
+  padding: 0 0 0 8px;
+  display: flex;
+  flex-direction: column;
+  gap: 12px;
+}
+
 .timeline-empty {
   background: rgba(248, 250, 252, 0.9);
   border: 1px dashed rgba(148, 163, 184, 0.5);
@@ -1354,6 +1480,32 @@ pub const DEFAULT_STYLES: &str = r#"
   color: var(--timeline-heading);
 }
 
+.watched-badge {
+  padding: 1px 6px;
+  border-radius: 999px;
+  border: 1px solid var(--timeline-trend-path);
+  color: var(--timeline-trend-path);
+  font-size: 0.7rem;
+  font-weight: 600;
+}
+
+.timeline-tags {
+  list-style: none;
+  margin: 0 0 6px 0;
+  padding: 0;
+  display: flex;
+  flex-wrap: wrap;
+  gap: 4px;
+}
+
+.timeline-tag {
+  padding: 1px 8px;
+  border-radius: 999px;
+  background: var(--timeline-group-accent);
+  color: var(--timeline-muted);
+  font-size: 0.75rem;
+}
+
 .timeline-detail {
   margin: 0;
   color: var(--timeline-muted-strong);
@@ -1361,6 +1513,29 @@ pub const DEFAULT_STYLES: &str = r#"
   line-height: 1.5;
 }
 
+.timeline-note {
+  margin: 6px 0 0;
+  padding-left: 10px;
+  border-left: 2px solid var(--timeline-card-border);
+  color: var(--timeline-muted);
+  font-size: 0.88rem;
+  font-style: italic;
+  line-height: 1.45;
+}
+
+.is-markdown p,
+.is-markdown ul {
+  margin: 0 0 6px;
+}
+
+.is-markdown ul {
+  padding-left: 18px;
+}
+
+.is-markdown > :last-child {
+  margin-bottom: 0;
+}
+
 .timeline-source {
   display: flex;
   gap: 8px;
@@ -1383,6 +1558,129 @@ pub const DEFAULT_STYLES: &str = r#"
   padding: 2px 8px;
 }
 
+.timeline-minimap {
+  display: flex;
+  align-items: flex-end;
+  gap: 2px;
+  height: 40px;
+  padding: 6px 8px;
+  border-radius: 12px;
+  background: rgba(148, 163, 184, 0.08);
+}
+
+.minimap-bar {
+  flex: 1;
+  display: flex;
+  align-items: flex-end;
+  height: 100%;
+  min-width: 2px;
+  padding: 0;
+  border: none;
+  background: transparent;
+  cursor: pointer;
+}
+
+.minimap-bar:disabled {
+  cursor: default;
+}
+
+.minimap-fill {
+  display: block;
+  width: 100%;
+  min-height: 1px;
+  border-radius: 2px 2px 0 0;
+  background: var(--timeline-severity-info);
+}
+
+.minimap-bar[data-level="critical"] .minimap-fill {
+  background: var(--timeline-severity-critical);
+}
+
+.minimap-bar[data-level="high"] .minimap-fill {
+  background: var(--timeline-severity-high);
+}
+
+.minimap-bar[data-level="moderate"] .minimap-fill {
+  background: var(--timeline-severity-moderate);
+}
+
+.minimap-bar[data-level="low"] .minimap-fill {
+  background: var(--timeline-severity-low);
+}
+
+.minimap-bar:focus-visible {
+  outline: 2px solid var(--timeline-trend-path);
+  outline-offset: 1px;
+}
+
+.duration-track {
+  display: flex;
+  flex-direction: column;
+  gap: 6px;
+  padding: 12px 14px;
+  border-radius: 14px;
+  background: rgba(148, 163, 184, 0.08);
+}
+
+.duration-plot {
+  width: 100%;
+  height: auto;
+}
+
+.duration-label {
+  font-size: 12px;
+  fill: var(--timeline-text);
+}
+
+.duration-bar {
+  fill: var(--timeline-severity-info);
+  opacity: 0.8;
+}
+
+.duration-bar.is-critical {
+  fill: var(--timeline-severity-critical);
+}
+
+.duration-bar.is-high {
+  fill: var(--timeline-severity-high);
+}
+
+.duration-bar.is-moderate {
+  fill: var(--timeline-severity-moderate);
+}
+
+.duration-bar.is-low {
+  fill: var(--timeline-severity-low);
+}
+
+.duration-axis {
+  display: flex;
+  justify-content: space-between;
+  padding-left: 30%;
+  font-size: 11px;
+  color: var(--timeline-muted);
+}
+
+.timeline-root[data-density="compact"] {
+  grid-template-columns: 1fr;
+  gap: 14px;
+  padding: 16px;
+}
+
+.timeline-root[data-density="compact"] .critical-column {
+  position: static;
+  gap: 10px;
+}
+
+.timeline-root[data-density="compact"] .timeline-event {
+  padding-top: 8px;
+  padding-bottom: 8px;
+}
+
+.timeline-root[data-density="compact"] .timeline-meta {
+  gap: 6px;
+}
+
 @media (max-width: 1080px) {
   .timeline-root {
     grid-template-columns: 1fr;
@@ -1446,8 +1744,161 @@ pub const DEFAULT_STYLES: &str = r#"
 }
 "#;
 
-pub fn ensure_styles(document: &Document) -> Result<(), JsValue> {
-    if document.query_selector(STYLE_TAG_SELECTOR)?.is_some() {
+/// Default styles with every class selector prefixed for `scope`.
+pub fn scoped_styles(scope: Option<&str>) -> String {
+    match scope.and_then(crate::scope::prefix_for) {
+        Some(prefix) => prefix_selectors(DEFAULT_STYLES, &prefix),
+        None => DEFAULT_STYLES.to_string(),
+    }
+}
+
+/// At-rules whose block holds style rules rather than declarations.
+const GROUPING_AT_RULES: &[&str] = &["@media", "@supports", "@container", "@layer"];
+
+/// Prefix the class selectors of `css`.
+///
+/// Only rule preludes are rewritten, at the top level and inside grouping
+/// at-rules such as `@media`, including class lists nested in `:is()` or
+/// `:not()`. Declaration blocks, other at-rules (`@keyframes`, `@font-face`),
+/// comments, strings and attribute selectors are copied unchanged.
+fn prefix_selectors(css: &str, prefix: &str) -> String {
+    let mut output = String::with_capacity(css.len() + 1024);
+    let mut rest = css;
+    while let Some(index) = next_delimiter(rest, b"{};") {
+        let (prelude, tail) = rest.split_at(index);
+        rest = &tail[1..];
+        if tail.as_bytes()[0] != b'{' {
+            output.push_str(prelude);
+            output.push_str(&tail[..1]);
+            continue;
+        }
+
+        let head = skip_comments(prelude);
+        if GROUPING_AT_RULES.iter().any(|rule| head.starts_with(rule)) {
+            output.push_str(prelude);
+            output.push('{');
+            continue;
+        }
+        if head.starts_with('@') {
+            output.push_str(prelude);
+        } else {
+            output.push_str(&prefix_class_names(prelude, prefix));
+        }
+        let end = block_end(rest);
+        output.push('{');
+        output.push_str(&rest[..end]);
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Byte index of the first of `delimiters` outside comments and strings.
+fn next_delimiter(css: &str, delimiters: &[u8]) -> Option<usize> {
+    let bytes = css.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                index = css[index + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| index + 2 + end + 2);
+            }
+            quote @ (b'"' | b'\'') => index = string_end(bytes, index, quote),
+            byte if delimiters.contains(&byte) => return Some(index),
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+/// Index just past the string starting with `quote` at `start`.
+fn string_end(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            byte if byte == quote => return index + 1,
+            _ => index += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Length of a block body up to and including its closing `}`, given the text
+/// just after the opening `{`.
+fn block_end(css: &str) -> usize {
+    let mut depth = 1;
+    let mut offset = 0;
+    while let Some(index) = next_delimiter(&css[offset..], b"{}") {
+        offset += index + 1;
+        if css.as_bytes()[offset - 1] == b'{' {
+            depth += 1;
+        } else {
+            depth -= 1;
+            if depth == 0 {
+                return offset;
+            }
+        }
+    }
+    css.len()
+}
+
+/// `prelude` without leading whitespace and comments.
+fn skip_comments(mut prelude: &str) -> &str {
+    loop {
+        prelude = prelude.trim_start();
+        match prelude
+            .strip_prefix("/*")
+            .and_then(|comment| comment.split_once("*/"))
+        {
+            Some((_, rest)) => prelude = rest,
+            None => return prelude,
+        }
+    }
+}
+
+/// `selector` with `prefix` inserted after each class-selector dot.
+fn prefix_class_names(selector: &str, prefix: &str) -> String {
+    let bytes = selector.as_bytes();
+    let mut output = String::with_capacity(selector.len() + prefix.len() * 4);
+    let mut copied = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                index = selector[index + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| index + 2 + end + 2);
+            }
+            b'[' => {
+                index = next_delimiter(&selector[index..], b"]")
+                    .map_or(bytes.len(), |end| index + end + 1);
+            }
+            b'.' if bytes
+                .get(index + 1)
+                .is_some_and(|next| next.is_ascii_alphabetic() || matches!(next, b'_' | b'-')) =>
+            {
+                output.push_str(&selector[copied..=index]);
+                output.push_str(prefix);
+                index += 1;
+                copied = index;
+            }
+            _ => index += 1,
+        }
+    }
+    output.push_str(&selector[copied..]);
+    output
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn ensure_styles(document: &Document, scope: Option<&str>) -> Result<(), JsValue> {
+    let scope = scope.and_then(crate::scope::scope_name);
+    let selector = match scope {
+        Some(scope) => format!("style[data-timeline-ui-scope=\"{scope}\"]"),
+        None => STYLE_TAG_SELECTOR.to_string(),
+    };
+    if document.query_selector(&selector)?.is_some() {
         return Ok(());
     }
 
@@ -1456,9 +1907,59 @@ pub fn ensure_styles(document: &Document) -> Result<(), JsValue> {
         .ok_or_else(|| JsValue::from_str("Document không có thẻ <head>"))?;
 
     let style_el = document.create_element("style")?;
-    style_el.set_attribute("data-timeline-ui", "v2")?;
-    style_el.set_text_content(Some(DEFAULT_STYLES));
+    match scope {
+        Some(scope) => style_el.set_attribute("data-timeline-ui-scope", scope)?,
+        None => style_el.set_attribute("data-timeline-ui", "v2")?,
+    }
+    style_el.set_text_content(Some(&scoped_styles(scope)));
     head.append_child(&style_el.clone().dyn_into::<Node>()?)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoped_styles_prefix_class_selectors() {
+        let css = scoped_styles(Some("acme"));
+        assert!(css.contains(".acme-timeline-root"));
+        assert!(css.contains(".acme-timeline-chart-point.acme-is-critical"));
+        assert!(!css.contains(".timeline-root"));
+        assert!(css.contains("0.85"));
+    }
+
+    #[test]
+    fn only_selectors_are_prefixed() {
+        let css = r#"
+/* .note { } */
+.a, :is(.b, .c) > .d[data-x=".e"] { background: url(img.png); content: ".f"; }
+@media (max-width: 40rem) {
+  .g:not(.h) { margin: .5em; }
+}
+@keyframes pulse { from { opacity: 0.5; } }
+"#;
+        assert_eq!(
+            prefix_selectors(css, "acme-"),
+            r#"
+/* .note { } */
+.acme-a, :is(.acme-b, .acme-c) > .acme-d[data-x=".e"] { background: url(img.png); content: ".f"; }
+@media (max-width: 40rem) {
+  .acme-g:not(.acme-h) { margin: .5em; }
+}
+@keyframes pulse { from { opacity: 0.5; } }
+"#
+        );
+    }
+
+    #[test]
+    fn invalid_scope_leaves_styles_unscoped() {
+        assert_eq!(scoped_styles(Some("x\"] *")), DEFAULT_STYLES);
+    }
+
+    #[test]
+    fn unscoped_styles_are_unchanged() {
+        assert_eq!(scoped_styles(None), DEFAULT_STYLES);
+    }
+}
@@ -0,0 +1,87 @@
+//! Display order of the recent vitals card. Without a configured order the
+//! card follows [`timeline_core::analytics::EXPECTED_VITALS`], which lists the
+//! converter's vital names in bedside order.
+
+use timeline_core::VitalSnapshot;
+
+/// Stable sort of `vitals` by their position in `order` (names compared
+/// case-insensitively). Unlisted vitals follow in their incoming (recency) order.
+pub(crate) fn order_vitals<'a, S: AsRef<str>>(
+    vitals: &'a [VitalSnapshot],
+    order: &[S],
+) -> Vec<&'a VitalSnapshot> {
+    let rank = |vital: &VitalSnapshot| {
+        order
+            .iter()
+            .position(|name| name.as_ref().eq_ignore_ascii_case(&vital.name))
+            .unwrap_or(order.len())
+    };
+    let mut ordered: Vec<&VitalSnapshot> = vitals.iter().collect();
+    ordered.sort_by_key(|vital| rank(vital));
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use timeline_core::analytics::EXPECTED_VITALS;
+    use timeline_core::Severity;
+
+    fn vital(name: &str, minute: u32) -> VitalSnapshot {
+        VitalSnapshot {
+            name: name.to_string(),
+            value: "1".to_string(),
+            recorded_at: Some(Utc.with_ymd_and_hms(2025, 10, 30, 8, minute, 0).unwrap()),
+            numeric_value: Some(1.0),
+            unit: None,
+            severity: Severity::Info,
+            watched: false,
+        }
+    }
+
+    fn names(vitals: Vec<&VitalSnapshot>) -> Vec<&str> {
+        vitals
+            .into_iter()
+            .map(|vital| vital.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn vitals_follow_clinical_order_regardless_of_recency() {
+        // Newest first, as the converter emits them.
+        let vitals = vec![
+            vital("Temperature", 50),
+            vital("BMI", 45),
+            vital("SpO2", 40),
+            vital("Heart rate", 30),
+            vital("Pain score", 20),
+            vital("Blood pressure", 10),
+            vital("Respiratory rate", 5),
+        ];
+
+        assert_eq!(
+            names(order_vitals(&vitals, &EXPECTED_VITALS)),
+            vec![
+                "Heart rate",
+                "Blood pressure",
+                "Respiratory rate",
+                "SpO2",
+                "Temperature",
+                "BMI",
+                "Pain score",
+            ]
+        );
+    }
+
+    #[test]
+    fn configured_order_is_case_insensitive() {
+        let vitals = vec![vital("Heart rate", 30), vital("SpO2", 40)];
+        let order = vec!["spo2".to_string()];
+
+        assert_eq!(
+            names(order_vitals(&vitals, &order)),
+            vec!["SpO2", "Heart rate"]
+        );
+    }
+}
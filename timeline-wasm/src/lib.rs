@@ -1,31 +1,9 @@
 //! Bridge WASM <-> JavaScript trung lập framework.
 
-use serde::Deserialize;
 use serde_wasm_bindgen::{from_value, to_value};
 use timeline_core::{TimelineConfig, TimelineError};
 use wasm_bindgen::prelude::*;
 
-#[derive(Deserialize)]
-struct JsTimelineConfig {
-    #[serde(default)]
-    vital_recent_hours: Option<u32>,
-    #[serde(default)]
-    clinical_event_days: Option<u32>,
-}
-
-impl From<JsTimelineConfig> for TimelineConfig {
-    fn from(cfg: JsTimelineConfig) -> Self {
-        let mut base = TimelineConfig::default();
-        if let Some(hours) = cfg.vital_recent_hours {
-            base.vital_recent_hours = hours;
-        }
-        if let Some(days) = cfg.clinical_event_days {
-            base.clinical_event_days = days;
-        }
-        base
-    }
-}
-
 #[wasm_bindgen]
 pub fn summarize_bundle(
     input_bundle: JsValue,
@@ -38,11 +16,10 @@ pub fn summarize_bundle(
         .map_err(|err| JsValue::from_str(&format!("Không đọc được JSON bundle: {err}")))?;
 
     let cfg = match config {
-        Some(js_cfg) => {
-            let cfg: JsTimelineConfig = from_value(js_cfg)
-                .map_err(|err| JsValue::from_str(&format!("Không đọc được config: {err}")))?;
-            TimelineConfig::from(cfg)
-        }
+        Some(js_cfg) => from_value::<serde_json::Value>(js_cfg)
+            .map_err(|err| err.to_string())
+            .and_then(|value| config_from_json(value).map_err(|err| err.to_string()))
+            .map_err(|err| JsValue::from_str(&format!("Không đọc được config: {err}")))?,
         None => TimelineConfig::default(),
     };
 
@@ -53,6 +30,47 @@ pub fn summarize_bundle(
         .map_err(|err| JsValue::from_str(&format!("Không serialize snapshot: {err}")))
 }
 
+/// Config từ JS: trường vắng mặt hoặc `null` lấy giá trị của `TimelineConfig::default()`
+/// (`null` được chấp nhận như khi bridge còn đọc từng trường dạng `Option`).
+fn config_from_json(mut value: serde_json::Value) -> Result<TimelineConfig, serde_json::Error> {
+    if let Some(fields) = value.as_object_mut() {
+        fields.retain(|_, field| !field.is_null());
+    }
+    serde_json::from_value(value)
+}
+
 fn format_timeline_error(err: TimelineError) -> String {
-    format!("Timeline error: {err}")
+    match err {
+        TimelineError::InvalidJson(detail) => {
+            format!("Timeline error: input is not valid JSON ({detail})")
+        }
+        TimelineError::NotABundle { found } => {
+            format!("Timeline error: expected a FHIR Bundle but received a {found} resource")
+        }
+        TimelineError::UnsupportedVersion { version } => {
+            format!("Timeline error: FHIR version {version} is not supported (R4 or R5 required)")
+        }
+        other => format!("Timeline error: {other}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn null_config_fields_use_the_defaults() {
+        let config = config_from_json(json!({
+            "vital_recent_hours": null,
+            "clinical_event_days": 7,
+            "emit_patient_alert": null
+        }))
+        .expect("config with null fields");
+
+        let defaults = TimelineConfig::default();
+        assert_eq!(config.vital_recent_hours, defaults.vital_recent_hours);
+        assert_eq!(config.emit_patient_alert, defaults.emit_patient_alert);
+        assert_eq!(config.clinical_event_days, 7);
+    }
 }
@@ -14,12 +14,52 @@ export interface CriticalItem {
   label: string;
   detail?: string | null;
   severity: Severity;
+  recorded_at?: string | null;
+  /** Structured allergy reactions, one per manifestation; empty for other items. */
+  reactions?: AllergyReaction[];
+  /** True when the item matches `watchlist`; kept regardless of its age. */
+  watched?: boolean;
+}
+
+export interface AllergyReaction {
+  manifestation: string;
+  severity?: Severity | null;
+  substance?: string | null;
+  exposure_route?: string | null;
 }
 
 export interface VitalSnapshot {
   name: string;
   value: string;
   recorded_at?: string | null;
+  numeric_value?: number | null;
+  unit?: string | null;
+  severity?: Severity;
+  watched?: boolean;
+}
+
+export interface VitalTrendPoint {
+  recorded_at?: string | null;
+  value?: number | null;
+  label?: string | null;
+}
+
+export interface VitalTrend {
+  name: string;
+  unit?: string | null;
+  points: VitalTrendPoint[];
+}
+
+export type DiagnosticKind = "lab" | "imaging" | "other";
+
+export interface DiagnosticSnapshot {
+  name: string;
+  value: string;
+  recorded_at?: string | null;
+  severity: Severity;
+  kind: DiagnosticKind;
+  unit?: string | null;
+  watched?: boolean;
 }
 
 export interface ResourceReference {
@@ -36,26 +76,129 @@ export interface TimelineEvent {
   occurred_at?: string | null;
   severity: Severity;
   source?: ResourceReference | null;
+  panel?: string | null;
+  observation_category?: ObservationCategory | null;
+  tags?: string[];
+  /** End of the period for medications/encounters; `occurred_at` is then the start. */
+  period_end?: string | null;
+  /** How precise `occurred_at` is; partial dates are normalised to midnight UTC. */
+  occurred_at_precision?: DatePrecision;
+  /** Free-text source annotations such as `Observation.note`, kept apart from `detail`. */
+  notes?: string[];
 }
 
+export type DatePrecision = "year" | "month" | "day" | "date_time";
+
+export type ObservationCategory = "vital_signs" | "laboratory" | "imaging" | "survey";
+
 export interface CriticalSummary {
   allergies: CriticalItem[];
   medications: CriticalItem[];
+  /** Stopped or not-taken medications when `separate_discontinued_medications` is on. */
+  discontinued_medications?: CriticalItem[];
   chronic_conditions: CriticalItem[];
   code_status?: string | null;
   alerts: CriticalItem[];
   recent_vitals: VitalSnapshot[];
+  vital_trends?: VitalTrend[];
+  recent_diagnostics?: DiagnosticSnapshot[];
+  blood_pressure_trend?: BloodPressureTrend | null;
+  older_vitals?: number;
+  vital_window_hours?: number | null;
+}
+
+export interface SeriesStats {
+  min: number;
+  max: number;
+  latest: number;
+}
+
+export interface BloodPressurePoint {
+  recorded_at?: string | null;
+  systolic: number;
+  diastolic: number;
+}
+
+export interface BloodPressureTrend {
+  unit?: string | null;
+  points: BloodPressurePoint[];
+  systolic?: SeriesStats | null;
+  diastolic?: SeriesStats | null;
 }
 
 export interface TimelineSnapshot {
+  schema_version: number;
   generated_at: string;
   critical: CriticalSummary;
   events: TimelineEvent[];
+  patient?: PatientDemographics | null;
+  warnings?: string[];
+  /** Number of events per category. */
+  category_summary?: Partial<Record<EventCategory, number>>;
+}
+
+export interface PatientDemographics {
+  name?: string | null;
+  age?: number | null;
+  gender?: string | null;
+  birth_date?: string | null;
+  deceased?: boolean;
+  deceased_at?: string | null;
 }
 
+/** Fields left out or set to `null` take their default value. */
 export interface SummarizeConfig {
   vital_recent_hours?: number;
   clinical_event_days?: number;
+  /** Also list demographics as a "Patient: …" alert (default `true`); they are always in `TimelineSnapshot.patient`. */
+  emit_patient_alert?: boolean;
+  capture_unknown_resources?: boolean;
+  /** Inclusive RFC 3339 lower bound for event-producing resources. */
+  from?: string | null;
+  /** Inclusive RFC 3339 upper bound for event-producing resources. */
+  to?: string | null;
+  future_tolerance_days?: number;
+  tag_rules?: TagRule[];
+  watchlist?: string[];
+  high_acuity_procedures?: string[];
+  /** RFC 3339 recency anchor, e.g. the bulk-data export time; skips the anchor scan. */
+  anchor_override?: string | null;
+  critical_item_order?: "severity_then_recency" | "severity_then_alphabetical";
+  /** Local severity dictionary: coding system → code → severity; overrides inferred severity. */
+  code_severity?: Record<string, Record<string, Severity>>;
+  /** Skip Observations whose `derivedFrom` source is in the same bundle (e.g. MAP from BP). */
+  suppress_derived_observations?: boolean;
+  /** Severity for observations no rule classifies; escalation still needs interpretation or referenceRange. */
+  unclassified_observation_severity?: Severity;
+  /** Latest consecutive abnormal readings that escalate a vital one level as "sustained"; null disables. */
+  sustained_abnormal_readings?: number | null;
+  /** Keep future-dated events (appointments); when false, events after the anchor override or now are dropped. */
+  include_future_events?: boolean;
+  /** RFC 3339 value written to `generated_at` instead of the current time, for reproducible output. */
+  generated_at?: string | null;
+  /** Move stopped/not-taken medications out of `medications` into `discontinued_medications`. */
+  separate_discontinued_medications?: boolean;
+  /** Rank active as-needed (PRN) medications `moderate` instead of `high`; off by default. */
+  lower_prn_medication_severity?: boolean;
+  /** Plausible range per vital label; values outside are dropped with a warning. An empty map disables the check. */
+  vital_physiologic_ranges?: Record<string, PhysiologicRange>;
+  /** Instant used for observations measured over an `effectivePeriod`. */
+  observation_period_timing?: "start" | "end" | "midpoint";
+  /** Stage keyword (matched against `Condition.stage[].summary`) → minimum severity for the condition. */
+  condition_stage_severity?: Record<string, Severity>;
+  /** Same-value readings of a vital at most this many seconds apart become one trend point (default 2). */
+  vital_duplicate_window_seconds?: number;
+}
+
+export interface PhysiologicRange {
+  min: number;
+  max: number;
+}
+
+export interface TagRule {
+  tag: string;
+  keywords?: string[];
+  min_severity?: Severity | null;
 }
 
 export function summarize_bundle(